@@ -0,0 +1,58 @@
+use ui_native::hooks::use_state;
+use ui_native::style::{AlignItems, FlexDirection, Style};
+use ui_native::widget::{Button, Text, View};
+use ui_native::{AppBuilder, Element, ElementLike, State};
+
+static STYLE: Style = Style {
+    flex_direction: FlexDirection::Column,
+    align_items: AlignItems::Centre,
+    ..Style::DEFAULT
+};
+
+/// a counter that keeps its own count
+struct Counter {
+    label: &'static str,
+}
+
+impl Element for Counter {
+    fn render(&self) -> ElementLike {
+        // the count is kept across renders
+        let count = use_state(|| 0);
+        let c = count.clone();
+
+        Box::new(
+            View::new()
+                .with_child(Text::new(format!("{}: {}", self.label, count.get())))
+                .with_child(
+                    Button::new()
+                        .with_label("increment")
+                        .with_on_click(move || c.update(|c| *c += 1)),
+                ),
+        )
+    }
+}
+
+fn main() {
+    let app = AppBuilder::new()
+        .with_title("counter")
+        .build()
+        .expect("failed to build app");
+
+    // the app is only rendered again when a state changes
+    let total = State::new(0);
+
+    app.launch(move || {
+        let t = total.clone();
+
+        View::new()
+            .with_style(&STYLE)
+            .with_child(Text::new(format!("reset {} times", total.get())))
+            .with_child(
+                Button::new()
+                    .with_label("reset")
+                    .with_on_click(move || t.update(|t| *t += 1)),
+            )
+            .with_child(Counter { label: "first" })
+            .with_child(Counter { label: "second" })
+    });
+}
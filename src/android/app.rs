@@ -7,6 +7,8 @@ use jni_bind::JReturnType;
 
 use crate::native_tree::{context::Context, NativeTree};
 use crate::shadow_tree::commit::commit_tree;
+use crate::shadow_tree::render::render_app;
+use crate::state::DirtyFlag;
 use crate::{AppBuilder, Application};
 
 pub struct NativeApp {
@@ -48,6 +50,9 @@ impl NativeApp {
         let mut has_input = false;
         let mut last_input_handle = Instant::now();
 
+        // marked when a state rendered by the app is mutated
+        let dirty = DirtyFlag::new();
+
         self.app
             .poll_events(Some(Duration::from_millis(100)), |event| {
                 // true if screen should rerender
@@ -55,8 +60,8 @@ impl NativeApp {
 
                 match event {
                     PollEvent::Timeout => {
-                        // only rerender if window is present
-                        should_render = window.is_some();
+                        // only rerender if window is present and state has changed
+                        should_render = window.is_some() && dirty.take();
                     }
                     PollEvent::Wake => {}
                     PollEvent::Main(MainEvent::InputAvailable) => {
@@ -95,16 +100,8 @@ impl NativeApp {
                 }
 
                 if should_render {
-                    // call render on the user defined app
-                    let mut element = app.render();
-
-                    // render the elements until a core component is returned
-                    let mut component = loop {
-                        match element.render() {
-                            Ok(c) => break c,
-                            Err(e) => element = e,
-                        }
-                    };
+                    // render the user defined app until a core component is returned
+                    let mut component = render_app(&mut app, shadow_tree.as_ref(), &dirty);
 
                     // generate command by comparing the new and old shadow tree
                     let commands = commit_tree(&mut component, shadow_tree.as_ref());
@@ -11,7 +11,7 @@ use crate::android::binding::{android, java};
 
 use crate::custom::MeasuredSize;
 use crate::native_tree::context::Context;
-use crate::native_tree::{AvalableSpace, NativeButtonImp, NativeStyledElement};
+use crate::native_tree::{AvalableSpace, NativeButtonImp, NativeFocusableImp, NativeStyledElement};
 use crate::style::*;
 
 use super::NativeElement;
@@ -59,6 +59,14 @@ impl NativeElement for NativeButton {
     }
 }
 
+// the binding does not expose the focus methods of views yet
+impl NativeFocusableImp for NativeButton {
+    fn focus(&self, _ctx: &mut Context) -> bool {
+        false
+    }
+    fn blur(&self, _ctx: &mut Context) {}
+}
+
 impl NativeButtonImp for NativeButton {
     fn new(ctx: &mut Context) -> Self {
         // create a button
@@ -53,6 +53,23 @@ impl NativeViewImp for NativeView {
             log::warn!(target: "main ui thread", "encountered error: {}", e)
         }
     }
+    fn move_child(&self, ctx: &mut Context, index: usize, elem: &dyn NativeElement) {
+        let view = elem.as_android_view();
+
+        let v: &ViewGroup = self.layout.as_ref();
+
+        // detach and attach the view, keeping its layout params
+        let re = view
+            .get_layout_params(&mut ctx.jni_env)
+            .and_then(|params| {
+                v.remove_view(&mut ctx.jni_env, view)?;
+                v.add_view(&mut ctx.jni_env, view, index as jint, params.as_ref())
+            });
+
+        if let Err(e) = re {
+            log::warn!(target: "main ui thread", "encountered error: {}", e)
+        }
+    }
     fn layout_child(
         &self,
         ctx: &mut Context,
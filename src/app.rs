@@ -1,5 +1,9 @@
+use std::sync::Arc;
+
+use crate::event::{Accelerator, AcceleratorCallback};
 use crate::imp::NativeApp;
 use crate::private::ElementLike;
+use crate::router::Router;
 
 pub trait Application: Send + Sync + 'static {
     fn render(&mut self) -> crate::ElementLike;
@@ -34,6 +38,9 @@ pub struct AppBuilder {
     pub(crate) title: Option<String>,
     pub(crate) width: Option<u32>,
     pub(crate) height: Option<u32>,
+    pub(crate) accelerators: Vec<(Accelerator, AcceleratorCallback)>,
+    pub(crate) router: Option<Router>,
+    pub(crate) url: Option<String>,
 }
 
 impl AppBuilder {
@@ -55,6 +62,37 @@ impl AppBuilder {
         self.height = Some(height);
         self
     }
+
+    /// register an application wide keyboard shortcut, e.g. `Ctrl+S`.
+    /// accelerators are triggered before the focused element receives the key.
+    ///
+    /// panics if the accelerator can not be parsed, see `Accelerator`.
+    pub fn with_accelerator<F>(mut self, accelerator: &str, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let accelerator = accelerator
+            .parse::<Accelerator>()
+            .unwrap_or_else(|e| panic!("invalid accelerator: {}", e));
+
+        self.accelerators.push((accelerator, Arc::new(callback)));
+        self
+    }
+
+    /// open the url given on the command line at launch, or the url set with `with_url`.
+    /// on linux, urls are also opened when the application is activated with one.
+    pub fn with_router(mut self, router: Router) -> Self {
+        self.router = Some(router);
+        self
+    }
+
+    /// url or path opened by the router at launch, e.g. a path saved from `Router::path`
+    /// to restore the navigation state.
+    /// a url with the scheme of the router given on the command line takes precedence.
+    pub fn with_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.url = Some(url.into());
+        self
+    }
     #[cfg(not(all(target_os = "android")))]
     pub fn build(self) -> Result<App, ()> {
         let app = NativeApp::new(self);
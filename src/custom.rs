@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 pub use crate::native_tree::MeasuredSize;
 use crate::shadow_tree::component::{CoreComponent, CustomNode};
+use crate::shadow_tree::render::render_element;
 use crate::style::{Style, StyleRef};
 
 /// native element shadow node implementation
@@ -101,6 +102,7 @@ impl crate::private::NativeElement for CustomElementWrapper {
         CoreComponent::Custom(Box::new(CustomNode {
             id: None,
             wrapper: self.clone(),
+            meta: Default::default(),
         }))
     }
     fn render(&mut self) {
@@ -131,16 +133,8 @@ where
         let render_children = move |child_components: &mut Vec<CoreComponent>| {
             let children = shadow1.children();
 
-            for child in children {
-                let component = match child.render() {
-                    Ok(comp) => comp,
-                    Err(mut e) => loop {
-                        match e.render() {
-                            Ok(comp) => break comp,
-                            Err(r) => e = r,
-                        }
-                    },
-                };
+            for (i, child) in children.enumerate() {
+                let component = render_element(child, i);
                 child_components.push(component);
             }
         };
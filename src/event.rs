@@ -0,0 +1,320 @@
+//! Input events delivered to elements.
+//!
+//! Pointer events are hit-tested against the computed layout of the native tree.
+//! The deepest node under the pointer is the target, `point_events` in the style
+//! of a node decides whether the node or its children can be a target.
+//! Down, up and move events bubble from the target to the root along its ancestors
+//! until a handler calls `PointerEvent::stop_propagation`.
+//! Enter and leave events are delivered to each node the pointer enters or leaves
+//! and do not bubble.
+//!
+//! Key events are delivered to the focused element and bubble the same way.
+//! Accelerators registered with `AppBuilder::with_accelerator` are matched
+//! before the focused element receives the event.
+
+use std::cell::Cell;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerEventKind {
+    Down,
+    Up,
+    Move,
+    Enter,
+    Leave,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerButton {
+    Primary,
+    Secondary,
+    Middle,
+    Other(u32),
+}
+
+#[derive(Debug)]
+pub struct PointerEvent {
+    pub kind: PointerEventKind,
+    /// the button pressed or released, none for move, enter and leave events
+    pub button: Option<PointerButton>,
+    /// position relative to the window
+    pub x: f32,
+    pub y: f32,
+    /// position relative to the node handling the event
+    pub local_x: f32,
+    pub local_y: f32,
+    stopped: Cell<bool>,
+}
+
+impl PointerEvent {
+    pub(crate) fn new(
+        kind: PointerEventKind,
+        button: Option<PointerButton>,
+        x: f32,
+        y: f32,
+    ) -> Self {
+        Self {
+            kind,
+            button,
+            x,
+            y,
+            local_x: x,
+            local_y: y,
+            stopped: Cell::new(false),
+        }
+    }
+
+    /// stop the event from bubbling to the ancestors of the current node
+    pub fn stop_propagation(&self) {
+        self.stopped.set(true);
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.stopped.get()
+    }
+}
+
+pub type PointerCallback = Arc<dyn Fn(&PointerEvent) + Send + Sync + 'static>;
+
+/// pointer callbacks of a node
+#[derive(Default, Clone)]
+pub(crate) struct PointerHandlers {
+    pub on_pointer_down: Option<PointerCallback>,
+    pub on_pointer_up: Option<PointerCallback>,
+    pub on_pointer_move: Option<PointerCallback>,
+    pub on_pointer_enter: Option<PointerCallback>,
+    pub on_pointer_leave: Option<PointerCallback>,
+}
+
+impl PointerHandlers {
+    pub fn get(&self, kind: PointerEventKind) -> Option<&PointerCallback> {
+        match kind {
+            PointerEventKind::Down => self.on_pointer_down.as_ref(),
+            PointerEventKind::Up => self.on_pointer_up.as_ref(),
+            PointerEventKind::Move => self.on_pointer_move.as_ref(),
+            PointerEventKind::Enter => self.on_pointer_enter.as_ref(),
+            PointerEventKind::Leave => self.on_pointer_leave.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEventKind {
+    Down,
+    Up,
+}
+
+/// modifier keys held while a key event is emitted
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub control: bool,
+    pub alt: bool,
+    /// the super, command or windows key
+    pub meta: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Self = Self {
+        shift: false,
+        control: false,
+        alt: false,
+        meta: false,
+    };
+}
+
+#[derive(Debug)]
+pub struct KeyEvent {
+    pub kind: KeyEventKind,
+    /// name of the key as reported by the platform, e.g. `a`, `Return` or `F5`
+    pub key: String,
+    /// the character produced by the key, if any
+    pub character: Option<char>,
+    /// hardware key code of the key
+    pub key_code: u32,
+    pub modifiers: Modifiers,
+    stopped: Cell<bool>,
+}
+
+impl KeyEvent {
+    pub(crate) fn new(
+        kind: KeyEventKind,
+        key: String,
+        character: Option<char>,
+        key_code: u32,
+        modifiers: Modifiers,
+    ) -> Self {
+        Self {
+            kind,
+            key,
+            character,
+            key_code,
+            modifiers,
+            stopped: Cell::new(false),
+        }
+    }
+
+    /// stop the event from bubbling to the ancestors of the current node.
+    /// the native widget does not receive a stopped event either.
+    pub fn stop_propagation(&self) {
+        self.stopped.set(true);
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.stopped.get()
+    }
+}
+
+pub type KeyCallback = Arc<dyn Fn(&KeyEvent) + Send + Sync + 'static>;
+
+/// key callbacks of a node
+#[derive(Default, Clone)]
+pub(crate) struct KeyHandlers {
+    pub on_key_down: Option<KeyCallback>,
+    pub on_key_up: Option<KeyCallback>,
+}
+
+impl KeyHandlers {
+    pub fn get(&self, kind: KeyEventKind) -> Option<&KeyCallback> {
+        match kind {
+            KeyEventKind::Down => self.on_key_down.as_ref(),
+            KeyEventKind::Up => self.on_key_up.as_ref(),
+        }
+    }
+}
+
+/// A key combination such as `Ctrl+S`.
+///
+/// Accelerators are parsed from `+` separated modifiers followed by a key name,
+/// modifiers are `Shift`, `Ctrl`, `Alt` and `Meta`. Key names are case insensitive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accelerator {
+    pub modifiers: Modifiers,
+    pub key: String,
+}
+
+impl Accelerator {
+    pub fn new<S: Into<String>>(modifiers: Modifiers, key: S) -> Self {
+        Self {
+            modifiers,
+            key: key.into(),
+        }
+    }
+
+    /// returns true if the key down event triggers the accelerator
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        event.kind == KeyEventKind::Down
+            && event.modifiers == self.modifiers
+            && event.key.eq_ignore_ascii_case(&self.key)
+    }
+}
+
+impl std::str::FromStr for Accelerator {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = s.split('+').map(|p| p.trim()).collect();
+
+        let key = parts.pop().unwrap_or_default();
+
+        if key.is_empty() {
+            return Err(anyhow::anyhow!("accelerator `{}` has no key", s));
+        }
+
+        let mut modifiers = Modifiers::NONE;
+
+        for part in parts {
+            match part.to_ascii_lowercase().as_str() {
+                "shift" => modifiers.shift = true,
+                "ctrl" | "control" => modifiers.control = true,
+                "alt" => modifiers.alt = true,
+                "meta" | "super" | "cmd" => modifiers.meta = true,
+                _ => return Err(anyhow::anyhow!("unknown modifier `{}` in `{}`", part, s)),
+            }
+        }
+
+        return Ok(Self::new(modifiers, key));
+    }
+}
+
+pub type AcceleratorCallback = Arc<dyn Fn() + Send + Sync + 'static>;
+
+/// invoke the first accelerator triggered by the event, returns true if any is triggered
+pub(crate) fn trigger_accelerators(
+    accelerators: &[(Accelerator, AcceleratorCallback)],
+    event: &KeyEvent,
+) -> bool {
+    for (accelerator, callback) in accelerators {
+        if accelerator.matches(event) {
+            callback();
+            return true;
+        }
+    }
+
+    return false;
+}
+
+#[test]
+fn test_accelerator() {
+    let save: Accelerator = "Ctrl+S".parse().unwrap();
+
+    assert_eq!(
+        save.modifiers,
+        Modifiers {
+            control: true,
+            ..Modifiers::NONE
+        }
+    );
+
+    let event = KeyEvent::new(
+        KeyEventKind::Down,
+        "s".into(),
+        Some('s'),
+        39,
+        save.modifiers,
+    );
+    assert!(save.matches(&event));
+
+    let event = KeyEvent::new(
+        KeyEventKind::Down,
+        "s".into(),
+        Some('s'),
+        39,
+        Modifiers::NONE,
+    );
+    assert!(!save.matches(&event));
+
+    assert!("Ctrl+".parse::<Accelerator>().is_err());
+    assert!("Hyper+S".parse::<Accelerator>().is_err());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_accelerator_dispatch() {
+    use crate::app::AppBuilder;
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, TextInput};
+    use crate::State;
+
+    let keys = State::new(Vec::<String>::new());
+    let (k, saved) = (keys.clone(), keys.clone());
+
+    let builder = AppBuilder::new().with_accelerator("Ctrl+S", move || {
+        saved.update(|k| k.push("save".into()));
+    });
+
+    let renderer = TestRenderer::from_builder(builder, move || {
+        let k = k.clone();
+
+        TextInput::new()
+            .with_on_key_down(move |e| k.update(|k| k.push(e.key.clone())))
+            .test_id("input")
+    });
+
+    assert!(renderer.find_by_test_id("input").unwrap().focus());
+    renderer.key_down("a");
+    // accelerators are not delivered to the focused node
+    renderer.key_down("Ctrl+S");
+
+    assert_eq!(keys.get(), ["a", "save"]);
+}
@@ -0,0 +1,96 @@
+//! Keyboard focus of `Button`, `Checkbox`, `Switch`, `Slider`, `Picker`, `TextInput` and `TextEdit`.
+//!
+//! The focus is moved programmatically through a `FocusHandle`, or with Tab and Shift+Tab.
+//! Tab moves the focus in tab order: elements with a positive tab index come first
+//! in increasing order, followed by elements with no or a zero tab index in tree order.
+//! Elements with a negative tab index can only be focused programmatically.
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::state::mark_dirty;
+
+/// A handle to move the keyboard focus to the element it is attached to.
+///
+/// Cloning a `FocusHandle` returns a handle to the same element.
+/// Requests are applied on the next frame once the element is mounted.
+#[derive(Clone, Default)]
+pub struct FocusHandle {
+    inner: Arc<Mutex<FocusHandleState>>,
+}
+
+#[derive(Default)]
+struct FocusHandleState {
+    focused: bool,
+    /// true to focus, false to blur
+    request: Option<bool>,
+}
+
+impl FocusHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// request the keyboard focus
+    pub fn focus(&self) {
+        self.inner.lock().request = Some(true);
+        mark_dirty();
+    }
+
+    /// remove the keyboard focus if the element is focused
+    pub fn blur(&self) {
+        self.inner.lock().request = Some(false);
+        mark_dirty();
+    }
+
+    /// returns true if the element has the keyboard focus
+    pub fn is_focused(&self) -> bool {
+        self.inner.lock().focused
+    }
+
+    /// returns true if both handles point to the same element
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    pub(crate) fn take_request(&self) -> Option<bool> {
+        self.inner.lock().request.take()
+    }
+
+    pub(crate) fn set_focused(&self, focused: bool) {
+        self.inner.lock().focused = focused;
+    }
+}
+
+impl std::fmt::Debug for FocusHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FocusHandle")
+            .field("focused", &self.is_focused())
+            .finish()
+    }
+}
+
+pub type FocusCallback = Arc<dyn Fn() + Send + Sync + 'static>;
+
+/// focus properties of a node
+#[derive(Default, Clone)]
+pub(crate) struct FocusProps {
+    pub handle: Option<FocusHandle>,
+    pub tab_index: Option<i32>,
+    pub on_focus: Option<FocusCallback>,
+    pub on_blur: Option<FocusCallback>,
+}
+
+impl FocusProps {
+    /// returns true if both hold the same handle and tab index
+    pub fn is_same(&self, other: &Self) -> bool {
+        let same_handle = match (&self.handle, &other.handle) {
+            (Some(a), Some(b)) => a.ptr_eq(b),
+            (None, None) => true,
+            _ => false,
+        };
+
+        same_handle && self.tab_index == other.tab_index
+    }
+}
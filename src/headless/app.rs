@@ -0,0 +1,232 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::app::Application;
+use crate::event::{trigger_accelerators, Accelerator, AcceleratorCallback, KeyEvent};
+use crate::native_tree::context::Context;
+use crate::native_tree::NativeTree;
+use crate::router::Router;
+use crate::shadow_tree::commit::commit_tree;
+use crate::shadow_tree::component::CoreComponent;
+use crate::shadow_tree::render::render_app;
+use crate::state::DirtyFlag;
+
+#[cfg(test)]
+use super::widget::DEFAULT_FONT_SIZE;
+
+pub struct HeadlessApp {
+    width: u32,
+    height: u32,
+    accelerators: Vec<(Accelerator, AcceleratorCallback)>,
+    router: Option<Router>,
+    url: Option<String>,
+}
+
+impl HeadlessApp {
+    pub fn new(builder: crate::AppBuilder) -> Self {
+        Self {
+            width: builder.width.unwrap_or(320),
+            height: builder.height.unwrap_or(320),
+            accelerators: builder.accelerators,
+            router: builder.router,
+            url: builder.url,
+        }
+    }
+
+    /// runs the render loop on the current thread, never returns
+    pub fn launch<A: Application>(self, app: A) {
+        // a deep link on the command line takes precedence over the url of the builder,
+        // the pages are opened once the navigators are rendered
+        if let Some(router) = &self.router {
+            let url = std::env::args()
+                .skip(1)
+                .find(|arg| router.is_deep_link(arg))
+                .or(self.url);
+
+            if let Some(url) = url {
+                router.open(&url);
+            }
+        }
+
+        let mut renderer = HeadlessRenderer::new(Box::new(app), self.width, self.height);
+        renderer.set_accelerators(self.accelerators);
+
+        loop {
+            renderer.frame(false);
+
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+}
+
+/// renders an application into a native tree of headless widgets
+pub(crate) struct HeadlessRenderer {
+    app: Box<dyn Application>,
+    /// marked when a state rendered by the app is mutated
+    dirty: Arc<DirtyFlag>,
+    comitted_tree: Option<CoreComponent>,
+    native_tree: NativeTree<'static>,
+    accelerators: Vec<(Accelerator, AcceleratorCallback)>,
+    width: u32,
+    height: u32,
+}
+
+impl HeadlessRenderer {
+    pub fn new(app: Box<dyn Application>, width: u32, height: u32) -> Self {
+        // the context is zero sized, leaking does not allocate
+        let ctx = Box::leak(Box::new(Context::dummy()));
+
+        Self {
+            app,
+            dirty: DirtyFlag::new(),
+            comitted_tree: None,
+            native_tree: NativeTree::new(ctx),
+            accelerators: Vec::new(),
+            width,
+            height,
+        }
+    }
+
+    /// runs a single frame.
+    /// the application is rendered if any state is mutated or `force_render` is true.
+    /// returns true if the application is rendered.
+    pub fn frame(&mut self, force_render: bool) -> bool {
+        // the dirty flag is always cleared
+        let dirty = self.dirty.take();
+        let render = dirty || force_render;
+
+        if render {
+            // element states are restored from the old tree
+            let mut comp = render_app(self.app.as_mut(), self.comitted_tree.as_ref(), &self.dirty);
+
+            // encode the new tree into commands by comparing to the old tree
+            let commands = commit_tree(&mut comp, self.comitted_tree.as_ref());
+
+            self.comitted_tree = Some(comp);
+
+            self.native_tree
+                .execute_commands(&mut Context::dummy(), commands);
+        }
+
+        // apply navigation commands
+        self.native_tree.check_update();
+
+        if render {
+            self.native_tree.compute_layout(
+                &mut Context::dummy(),
+                self.width as _,
+                self.height as _,
+            );
+            self.native_tree.compute_style();
+        }
+
+        return render;
+    }
+
+    /// resize the window, layout is recomputed
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+
+        self.native_tree
+            .compute_layout(&mut Context::dummy(), width as _, height as _);
+    }
+
+    pub fn set_accelerators(&mut self, accelerators: Vec<(Accelerator, AcceleratorCallback)>) {
+        self.accelerators = accelerators;
+    }
+
+    /// trigger the accelerators, or deliver the event to the focused node if none is triggered
+    pub fn key_event(&self, event: &KeyEvent) {
+        if trigger_accelerators(&self.accelerators, event) {
+            return;
+        }
+
+        self.native_tree.handle_key(event);
+    }
+
+    pub fn native_tree(&self) -> &NativeTree<'static> {
+        &self.native_tree
+    }
+
+    pub fn comitted_tree(&self) -> Option<&CoreComponent> {
+        self.comitted_tree.as_ref()
+    }
+}
+
+#[test]
+fn test_headless_render() {
+    use crate::native_tree::node::NativeComponent;
+    use crate::widget::{Button, Text, View};
+    use crate::State;
+
+    let count = State::new(0);
+    let c = count.clone();
+
+    let mut renderer = HeadlessRenderer::new(
+        Box::new(move || {
+            let c = c.clone();
+
+            View::new()
+                .with_child(Text::new(format!("count: {}", c.get())))
+                .with_child(
+                    Button::new()
+                        .with_label("add")
+                        .with_on_click(move || c.update(|c| *c += 1)),
+                )
+        }),
+        320,
+        240,
+    );
+
+    assert!(renderer.frame(true));
+
+    let root_id = renderer
+        .comitted_tree
+        .as_ref()
+        .and_then(|c| c.id())
+        .unwrap();
+    let root = renderer
+        .native_tree
+        .get_node(root_id)
+        .expect("root is mounted");
+    let root = root.component().widget().as_headless_widget().clone();
+
+    {
+        let record = root.record();
+        assert_eq!(record.kind, "view");
+        assert_eq!(record.children.len(), 2);
+
+        let text = record.children[0].record();
+        assert_eq!(text.text.as_deref(), Some("count: 0"));
+        // 8 characters of half the default font size
+        assert_eq!(text.frame.width, 8.0 * DEFAULT_FONT_SIZE / 2.0);
+
+        // children are laid out in a row
+        let button = record.children[1].record();
+        assert_eq!(button.text.as_deref(), Some("add"));
+        assert_eq!(button.frame.x, text.frame.width);
+    }
+
+    // click the button through the native tree
+    let button_id = match renderer.comitted_tree.as_ref() {
+        Some(CoreComponent::View(v)) => v.children[1].id().unwrap(),
+        _ => unreachable!(),
+    };
+
+    match renderer
+        .native_tree
+        .get_node(button_id)
+        .map(|n| n.component())
+    {
+        Some(NativeComponent::Button(b)) => b.click(),
+        _ => panic!("expecting button"),
+    }
+
+    assert_eq!(count.get(), 1);
+
+    renderer.frame(false);
+
+    let text = root.record().children[0].record().text.clone();
+    assert_eq!(text.as_deref(), Some("count: 1"));
+}
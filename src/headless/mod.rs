@@ -0,0 +1,29 @@
+//! In-memory backend that does not need a display.
+//!
+//! Widgets record their properties, children, layouts and callbacks
+//! instead of drawing, see `HeadlessWidget`.
+//! Enabled by the `headless` feature, it replaces the native backend.
+
+mod app;
+pub mod widget;
+
+pub(super) use app::HeadlessApp as NativeApp;
+pub(crate) use app::HeadlessRenderer;
+pub(super) use widget::NativeActivityIndicator;
+pub(super) use widget::NativeButton;
+pub use widget::NativeElement;
+pub(super) use widget::NativeImageView;
+pub(super) use widget::NativeListView;
+pub(super) use widget::NativeModal;
+pub(crate) use widget::NativeNavigator;
+pub(super) use widget::NativePicker;
+pub(super) use widget::NativeProgressBar;
+pub(super) use widget::NativeScrollView;
+pub(super) use widget::NativeSlider;
+pub(crate) use widget::NativeStackNavigator;
+pub(super) use widget::NativeText;
+pub(crate) use widget::NativeTextEdit;
+pub(crate) use widget::NativeTextInput;
+pub(super) use widget::NativeToggle;
+pub(super) use widget::NativeView;
+pub use widget::{Frame, HeadlessWidget, StyleRecord, WidgetRecord, DEFAULT_FONT_SIZE};
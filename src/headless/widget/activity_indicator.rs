@@ -0,0 +1,46 @@
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeActivityIndicatorImp};
+
+use super::{constrain, impl_styled_element, HeadlessWidget, DEFAULT_FONT_SIZE};
+
+pub struct NativeActivityIndicator {
+    widget: HeadlessWidget,
+    animating: RwLock<bool>,
+}
+
+impl NativeActivityIndicator {
+    pub fn is_animating(&self) -> bool {
+        *self.animating.read()
+    }
+}
+
+impl NativeActivityIndicatorImp for NativeActivityIndicator {
+    fn new(_ctx: &mut Context) -> Self {
+        Self {
+            widget: HeadlessWidget::new("activity indicator"),
+            animating: RwLock::new(false),
+        }
+    }
+    fn set_animating(&self, _ctx: &mut Context, animating: bool) {
+        *self.animating.write() = animating;
+    }
+}
+
+impl_styled_element!(NativeActivityIndicator, |this, width, height| {
+    // the spinner is a square as high as a line of text
+    let size = this
+        .widget
+        .record()
+        .style
+        .font_size
+        .unwrap_or(DEFAULT_FONT_SIZE);
+
+    MeasuredSize {
+        min_width: size,
+        natural_width: constrain(width, size),
+        min_height: size,
+        natural_height: constrain(height, size),
+    }
+});
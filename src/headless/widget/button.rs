@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeButtonImp, NativeFocusableImp};
+
+use super::{constrain, impl_styled_element, HeadlessWidget};
+
+/// space between the label and the border of a button
+const PADDING: f32 = 8.0;
+
+pub struct NativeButton {
+    widget: HeadlessWidget,
+    callback: Arc<RwLock<Option<Arc<dyn Fn() + Send + Sync>>>>,
+}
+
+impl NativeButton {
+    /// simulate a click, does nothing if the button is disabled
+    pub fn click(&self) {
+        if self.widget.record().disabled {
+            return;
+        }
+
+        // the lock is released before calling, the callback may update the button
+        let callback = self.callback.read().clone();
+
+        if let Some(cb) = callback {
+            cb();
+        }
+    }
+}
+
+impl NativeButtonImp for NativeButton {
+    fn new(_ctx: &mut Context) -> Self {
+        let widget = HeadlessWidget::new("button");
+        widget.record().text = Some(String::new());
+
+        Self {
+            widget,
+            callback: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn set_disabled(&self, _ctx: &mut Context, disabled: bool) {
+        self.widget.record().disabled = disabled;
+    }
+
+    fn set_label(&self, _ctx: &mut Context, text: String) {
+        self.widget.record().text = Some(text);
+    }
+
+    fn set_on_click(&self, _ctx: &mut Context, on_click: Option<Arc<dyn Fn() + Send + Sync>>) {
+        *self.callback.write() = on_click;
+    }
+}
+
+impl NativeFocusableImp for NativeButton {
+    fn focus(&self, _ctx: &mut Context) -> bool {
+        // disabled buttons can not be focused
+        !self.widget.record().disabled
+    }
+    fn blur(&self, _ctx: &mut Context) {}
+}
+
+impl_styled_element!(NativeButton, |this, width, height| {
+    let (min_width, natural_width, natural_height) = this.widget.measure_text();
+
+    MeasuredSize {
+        min_width: min_width + PADDING * 2.0,
+        natural_width: constrain(width, natural_width + PADDING * 2.0),
+        min_height: natural_height + PADDING,
+        natural_height: constrain(height, natural_height + PADDING),
+    }
+});
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::image::ImageSource;
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeImageViewImp};
+use crate::util::Comparable;
+
+use super::{constrain, impl_styled_element, HeadlessWidget};
+
+pub struct NativeImageView {
+    widget: HeadlessWidget,
+    src: RwLock<Arc<Comparable<dyn ImageSource>>>,
+}
+
+impl NativeImageView {
+    /// the image shown by the view
+    pub fn source(&self) -> Arc<Comparable<dyn ImageSource>> {
+        self.src.read().clone()
+    }
+}
+
+impl NativeImageViewImp for NativeImageView {
+    fn new(_ctx: &mut Context, src: Arc<Comparable<dyn ImageSource>>) -> Self {
+        Self {
+            widget: HeadlessWidget::new("image view"),
+            src: RwLock::new(src),
+        }
+    }
+    fn set_source(&self, _ctx: &mut Context, src: Arc<Comparable<dyn ImageSource>>) {
+        *self.src.write() = src;
+    }
+    fn check_update(&self) {}
+}
+
+impl_styled_element!(NativeImageView, |this, width, height| {
+    let (w, h) = this.src.read().size();
+
+    MeasuredSize {
+        min_width: 0.0,
+        natural_width: constrain(width, w as f32),
+        min_height: 0.0,
+        natural_height: constrain(height, h as f32),
+    }
+});
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeFocusableImp, NativeTextInputImp};
+use crate::widget::input::{InputFormatter, InputRules, InputType};
+
+use super::{constrain, impl_styled_element, HeadlessWidget, DEFAULT_FONT_SIZE};
+
+/// number of characters visible in an input without a known width
+const VISIBLE_CHARS: f32 = 20.0;
+
+pub struct NativeTextInput {
+    widget: HeadlessWidget,
+    background_text: RwLock<String>,
+    rules: RwLock<InputRules>,
+    text_changed_callback: Arc<RwLock<Option<Arc<dyn Fn(&str) + Sync + Send>>>>,
+    enter_callback: Arc<RwLock<Option<Arc<dyn Fn(&str) + Sync + Send>>>>,
+}
+
+impl NativeTextInput {
+    /// the text shown when the input is empty
+    pub fn background_text(&self) -> String {
+        self.background_text.read().clone()
+    }
+
+    /// the current text of the input
+    pub fn text(&self) -> String {
+        self.widget.record().text.clone().unwrap_or_default()
+    }
+
+    pub fn input_type(&self) -> InputType {
+        self.rules.read().input_type
+    }
+
+    /// simulate the user replacing the text,
+    /// the text is filtered and formatted by the rules of the input
+    pub fn input_text(&self, text: &str) {
+        let text = self.rules.read().apply(text);
+        self.widget.record().text = Some(text.clone());
+
+        let callback = self.text_changed_callback.read().clone();
+
+        if let Some(cb) = callback {
+            cb(&text);
+        }
+
+        // a controlled input is reset to its value if the callback rejected the text
+        crate::state::mark_dirty();
+    }
+
+    /// simulate the user pressing enter
+    pub fn press_enter(&self) {
+        let text = self.text();
+        let callback = self.enter_callback.read().clone();
+
+        if let Some(cb) = callback {
+            cb(&text);
+        }
+    }
+}
+
+impl NativeTextInputImp for NativeTextInput {
+    fn new(_ctx: &mut Context) -> Self {
+        let widget = HeadlessWidget::new("text input");
+        widget.record().text = Some(String::new());
+
+        Self {
+            widget,
+            background_text: RwLock::new(String::new()),
+            rules: RwLock::new(InputRules::default()),
+            text_changed_callback: Arc::new(RwLock::new(None)),
+            enter_callback: Arc::new(RwLock::new(None)),
+        }
+    }
+    fn set_background_text(&self, _ctx: &mut Context, text: &str) {
+        *self.background_text.write() = text.to_string();
+    }
+    fn set_input_type(&self, _ctx: &mut Context, input_type: InputType) {
+        self.rules.write().input_type = input_type;
+    }
+    fn set_max_length(&self, _ctx: &mut Context, max_length: Option<usize>) {
+        self.rules.write().max_length = max_length;
+    }
+    fn set_formatter(&self, _ctx: &mut Context, formatter: Option<InputFormatter>) {
+        self.rules.write().formatter = formatter;
+    }
+    fn set_text(&self, _ctx: &mut Context, text: &str) {
+        self.widget.record().text = Some(text.to_string());
+    }
+    fn set_on_text_changed(
+        &self,
+        _ctx: &mut Context,
+        callback: Option<Arc<dyn Fn(&str) + Sync + Send>>,
+    ) {
+        *self.text_changed_callback.write() = callback;
+    }
+    fn set_on_enter_pressed(
+        &self,
+        _ctx: &mut Context,
+        callback: Option<Arc<dyn Fn(&str) + Sync + Send>>,
+    ) {
+        *self.enter_callback.write() = callback;
+    }
+}
+
+impl NativeFocusableImp for NativeTextInput {
+    fn focus(&self, _ctx: &mut Context) -> bool {
+        true
+    }
+    fn blur(&self, _ctx: &mut Context) {}
+}
+
+impl_styled_element!(NativeTextInput, |this, width, height| {
+    let font_size = this
+        .widget
+        .record()
+        .style
+        .font_size
+        .unwrap_or(DEFAULT_FONT_SIZE);
+
+    let natural_width = VISIBLE_CHARS * font_size / 2.0;
+
+    MeasuredSize {
+        min_width: font_size,
+        natural_width: constrain(width, natural_width),
+        min_height: font_size,
+        natural_height: constrain(height, font_size * 1.5),
+    }
+});
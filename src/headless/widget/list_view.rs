@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeListViewImp};
+use crate::private::ElementLike;
+use crate::widget::flatlist::{ListViewDataSourceWrapper, ListViewWidgetFactoryWrapper};
+
+use super::{impl_styled_element, HeadlessWidget};
+
+/// list items are not rendered, there is no viewport to virtualise against.
+pub struct NativeListView {
+    widget: HeadlessWidget,
+    data: Arc<ListViewDataSourceWrapper>,
+    factory: Arc<ListViewWidgetFactoryWrapper>,
+}
+
+impl NativeListView {
+    /// number of items in the data source
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// render the element of an item with the factory, `None` if index is out of bound
+    pub fn render_item(&self, index: usize) -> Option<Box<dyn ElementLike>> {
+        let item = self.data.get(index)?;
+        Some(self.factory.render_item(index, item))
+    }
+}
+
+impl NativeListViewImp for NativeListView {
+    fn new(
+        _ctx: &mut Context,
+        data: Arc<ListViewDataSourceWrapper>,
+        render: Arc<ListViewWidgetFactoryWrapper>,
+    ) -> Self {
+        Self {
+            widget: HeadlessWidget::new("list view"),
+            data,
+            factory: render,
+        }
+    }
+}
+
+impl_styled_element!(NativeListView, |_this, _width, _height| MeasuredSize {
+    min_width: 0.0,
+    natural_width: 0.0,
+    min_height: 0.0,
+    natural_height: 0.0,
+});
@@ -0,0 +1,318 @@
+pub mod activity_indicator;
+pub mod button;
+pub mod image_view;
+pub mod input;
+pub mod list_view;
+pub mod modal;
+pub mod navigator;
+pub mod picker;
+pub mod progress_bar;
+pub mod scroll_view;
+pub mod slider;
+pub mod stack_navigator;
+pub mod text;
+pub mod text_edit;
+pub mod toggle;
+pub mod view;
+
+use std::any::Any;
+use std::sync::Arc;
+
+use parking_lot::{Mutex, MutexGuard};
+
+use crate::native_tree::AvalableSpace;
+use crate::style::{
+    BorderStyle, Colour, FontStyle, FontWeight, PointEvents, TextAlign, TextDecorationLine,
+    TextTransform,
+};
+
+pub use activity_indicator::NativeActivityIndicator;
+pub use button::NativeButton;
+pub use image_view::NativeImageView;
+pub use input::NativeTextInput;
+pub use list_view::NativeListView;
+pub use modal::NativeModal;
+pub use navigator::NativeNavigator;
+pub use picker::NativePicker;
+pub use progress_bar::NativeProgressBar;
+pub use scroll_view::NativeScrollView;
+pub use slider::NativeSlider;
+pub use stack_navigator::NativeStackNavigator;
+pub use text::NativeText;
+pub use text_edit::NativeTextEdit;
+pub use toggle::NativeToggle;
+pub use view::NativeView;
+
+/// font size used to measure text if none is set
+pub const DEFAULT_FONT_SIZE: f32 = 16.0;
+
+pub trait NativeElement: Any {
+    fn as_headless_widget(&self) -> &HeadlessWidget;
+}
+
+/// Handle to the in-memory record of a widget.
+///
+/// Cloning the handle does not clone the record.
+#[derive(Clone)]
+pub struct HeadlessWidget {
+    record: Arc<Mutex<WidgetRecord>>,
+}
+
+impl HeadlessWidget {
+    pub(crate) fn new(kind: &'static str) -> Self {
+        Self {
+            record: Arc::new(Mutex::new(WidgetRecord {
+                kind,
+                visible: true,
+                frame: Frame::default(),
+                children: Vec::new(),
+                text: None,
+                disabled: false,
+                style: StyleRecord::default(),
+            })),
+        }
+    }
+
+    /// lock the record of the widget
+    pub fn record(&self) -> MutexGuard<'_, WidgetRecord> {
+        self.record.lock()
+    }
+
+    /// returns true if both handles point to the same widget
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.record, &other.record)
+    }
+
+    pub(crate) fn insert_child(&self, index: usize, child: &HeadlessWidget) {
+        let mut record = self.record();
+        let index = index.min(record.children.len());
+        record.children.insert(index, child.clone());
+    }
+
+    pub(crate) fn remove_child(&self, child: &HeadlessWidget) {
+        self.record().children.retain(|c| !c.ptr_eq(child));
+    }
+
+    pub(crate) fn layout_child(&self, child: &HeadlessWidget, frame: Frame) {
+        // the record of the parent is not locked while the child is updated
+        child.record().frame = frame;
+    }
+
+    /// measure the text of the widget with fixed metrics:
+    /// every character is half the font size wide and every line is the font size high.
+    pub(crate) fn measure_text(&self) -> (f32, f32, f32) {
+        let record = self.record();
+        let font_size = record.style.font_size.unwrap_or(DEFAULT_FONT_SIZE);
+        let char_width = font_size / 2.0;
+
+        let text = record.text.as_deref().unwrap_or_default();
+
+        let longest_line = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        let longest_word = text
+            .split_whitespace()
+            .map(|w| w.chars().count())
+            .max()
+            .unwrap_or(0);
+        let lines = text.lines().count().max(1);
+
+        return (
+            longest_word as f32 * char_width,
+            longest_line as f32 * char_width,
+            lines as f32 * font_size,
+        );
+    }
+}
+
+impl std::fmt::Debug for HeadlessWidget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.record().fmt(f)
+    }
+}
+
+/// position and size of a widget relative to its parent
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Frame {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// properties recorded by a headless widget
+#[derive(Debug)]
+pub struct WidgetRecord {
+    /// the kind of widget, e.g. "button"
+    pub kind: &'static str,
+    pub visible: bool,
+    /// the last layout assigned by the parent
+    pub frame: Frame,
+    pub children: Vec<HeadlessWidget>,
+    /// label, text or content of the widget
+    pub text: Option<String>,
+    pub disabled: bool,
+    pub style: StyleRecord,
+}
+
+/// style properties set on a headless widget, `None` if never set
+#[derive(Debug, Default, Clone)]
+pub struct StyleRecord {
+    pub backface_visible: Option<bool>,
+    pub colour: Option<Colour>,
+    pub background_colour: Option<Colour>,
+    /// top, right, bottom, left
+    pub border_width: [Option<f32>; 4],
+    /// top left, top right, bottom right, bottom left
+    pub border_radius: [Option<f32>; 4],
+    /// top, right, bottom, left
+    pub border_colour: [Option<Colour>; 4],
+    pub border_style: Option<BorderStyle>,
+    pub opacity: Option<f32>,
+    pub points_event: Option<PointEvents>,
+    pub font_size: Option<f32>,
+    pub font_style: Option<FontStyle>,
+    pub font_weight: Option<FontWeight>,
+    pub letter_spacing: Option<f32>,
+    pub line_height: Option<f32>,
+    pub text_align: Option<TextAlign>,
+    pub text_decoration_line: Option<TextDecorationLine>,
+    pub text_decoration_colour: Option<Colour>,
+    pub text_shadow_colour: Option<Colour>,
+    pub text_shadow_radius: Option<f32>,
+    pub text_transform: Option<TextTransform>,
+}
+
+/// the size given by the layout if known, `natural` otherwise
+pub(crate) fn constrain(space: AvalableSpace, natural: f32) -> f32 {
+    match space {
+        AvalableSpace::Exact(f) => f,
+        AvalableSpace::AtMost(f) => natural.min(f),
+        AvalableSpace::Unknown => natural,
+    }
+}
+
+/// implements `NativeStyledElement` by recording the style into the widget record.
+/// `$measure` computes the `MeasuredSize` of the widget.
+macro_rules! impl_styled_element {
+    ($ty:ty, |$this:ident, $width:ident, $height:ident| $measure:expr) => {
+        impl crate::native_tree::NativeStyledElement for $ty {
+            fn measure(
+                &self,
+                _ctx: &mut crate::native_tree::context::Context,
+                known_width: crate::native_tree::AvalableSpace,
+                known_height: crate::native_tree::AvalableSpace,
+            ) -> anyhow::Result<crate::native_tree::MeasuredSize> {
+                let $this = self;
+                let $width = known_width;
+                let $height = known_height;
+                return Ok($measure);
+            }
+            fn set_visible(&self, _ctx: &mut crate::native_tree::context::Context, visible: bool) {
+                self.widget.record().visible = visible;
+            }
+            fn set_backface_visible(&self, visible: bool) {
+                self.widget.record().style.backface_visible = Some(visible);
+            }
+
+            fn set_colour(&self, colour: crate::style::Colour) {
+                self.widget.record().style.colour = Some(colour);
+            }
+            fn set_background_colour(&self, colour: crate::style::Colour) {
+                self.widget.record().style.background_colour = Some(colour);
+            }
+
+            fn set_border_top_width(&self, width: f32) {
+                self.widget.record().style.border_width[0] = Some(width);
+            }
+            fn set_border_right_width(&self, width: f32) {
+                self.widget.record().style.border_width[1] = Some(width);
+            }
+            fn set_border_bottom_width(&self, width: f32) {
+                self.widget.record().style.border_width[2] = Some(width);
+            }
+            fn set_border_left_width(&self, width: f32) {
+                self.widget.record().style.border_width[3] = Some(width);
+            }
+
+            fn set_border_top_left_radius(&self, radius: f32) {
+                self.widget.record().style.border_radius[0] = Some(radius);
+            }
+            fn set_border_top_right_radius(&self, radius: f32) {
+                self.widget.record().style.border_radius[1] = Some(radius);
+            }
+            fn set_border_bottom_right_radius(&self, radius: f32) {
+                self.widget.record().style.border_radius[2] = Some(radius);
+            }
+            fn set_border_bottom_left_radius(&self, radius: f32) {
+                self.widget.record().style.border_radius[3] = Some(radius);
+            }
+
+            fn set_border_top_colour(&self, colour: crate::style::Colour) {
+                self.widget.record().style.border_colour[0] = Some(colour);
+            }
+            fn set_border_right_colour(&self, colour: crate::style::Colour) {
+                self.widget.record().style.border_colour[1] = Some(colour);
+            }
+            fn set_border_bottom_colour(&self, colour: crate::style::Colour) {
+                self.widget.record().style.border_colour[2] = Some(colour);
+            }
+            fn set_border_left_colour(&self, colour: crate::style::Colour) {
+                self.widget.record().style.border_colour[3] = Some(colour);
+            }
+
+            fn set_border_style(&self, style: crate::style::BorderStyle) {
+                self.widget.record().style.border_style = Some(style);
+            }
+
+            fn set_opacity(&self, opacity: f32) {
+                self.widget.record().style.opacity = Some(opacity);
+            }
+            fn set_points_event(&self, event: crate::style::PointEvents) {
+                self.widget.record().style.points_event = Some(event);
+            }
+
+            fn set_font_size(&self, size: f32) {
+                self.widget.record().style.font_size = Some(size);
+            }
+            fn set_font_style(&self, style: crate::style::FontStyle) {
+                self.widget.record().style.font_style = Some(style);
+            }
+            fn set_font_weight(&self, weight: crate::style::FontWeight) {
+                self.widget.record().style.font_weight = Some(weight);
+            }
+
+            fn set_letter_spacing(&self, spacing: f32) {
+                self.widget.record().style.letter_spacing = Some(spacing);
+            }
+            fn set_line_height(&self, height: f32) {
+                self.widget.record().style.line_height = Some(height);
+            }
+
+            fn set_text_align(&self, align: crate::style::TextAlign) {
+                self.widget.record().style.text_align = Some(align);
+            }
+            fn set_text_decloration_line(&self, line: crate::style::TextDecorationLine) {
+                self.widget.record().style.text_decoration_line = Some(line);
+            }
+            fn set_text_decloration_colour(&self, colour: crate::style::Colour) {
+                self.widget.record().style.text_decoration_colour = Some(colour);
+            }
+            fn set_text_shadow_colour(&self, colour: crate::style::Colour) {
+                self.widget.record().style.text_shadow_colour = Some(colour);
+            }
+            fn set_text_shadow_radius(&self, radius: f32) {
+                self.widget.record().style.text_shadow_radius = Some(radius);
+            }
+            fn set_text_transform(&self, transform: crate::style::TextTransform) {
+                self.widget.record().style.text_transform = Some(transform);
+            }
+        }
+
+        impl super::NativeElement for $ty {
+            fn as_headless_widget(&self) -> &super::HeadlessWidget {
+                &self.widget
+            }
+        }
+    };
+}
+
+pub(crate) use impl_styled_element;
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeModalImp};
+
+use super::{impl_styled_element, Frame, HeadlessWidget, NativeElement};
+
+pub struct NativeModal {
+    /// placeholder in the parent
+    widget: HeadlessWidget,
+    /// the layer presented above the window content
+    layer: HeadlessWidget,
+    transparent: RwLock<bool>,
+    on_request_close: Arc<RwLock<Option<Arc<dyn Fn() + Send + Sync>>>>,
+}
+
+impl NativeModal {
+    /// the layer holding the child
+    pub fn layer(&self) -> &HeadlessWidget {
+        &self.layer
+    }
+
+    pub fn is_transparent(&self) -> bool {
+        *self.transparent.read()
+    }
+}
+
+impl NativeModalImp for NativeModal {
+    fn new(_ctx: &mut Context) -> Self {
+        let layer = HeadlessWidget::new("modal layer");
+        layer.record().visible = false;
+
+        Self {
+            widget: HeadlessWidget::new("modal"),
+            layer,
+            transparent: RwLock::new(false),
+            on_request_close: Arc::new(RwLock::new(None)),
+        }
+    }
+    fn set_visible(&self, _ctx: &mut Context, visible: bool) {
+        self.layer.record().visible = visible;
+    }
+    fn is_visible(&self, _ctx: &mut Context) -> bool {
+        self.layer.record().visible
+    }
+    fn set_transparent(&self, _ctx: &mut Context, transparent: bool) {
+        *self.transparent.write() = transparent;
+    }
+    fn set_child(&self, _ctx: &mut Context, child: &dyn NativeElement) {
+        self.layer.record().children = vec![child.as_headless_widget().clone()];
+    }
+    fn remove_child(&self, _ctx: &mut Context) {
+        self.layer.record().children.clear();
+    }
+    fn layout_child(
+        &self,
+        _ctx: &mut Context,
+        child: &dyn NativeElement,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    ) {
+        self.layer.layout_child(
+            child.as_headless_widget(),
+            Frame {
+                x,
+                y,
+                width,
+                height,
+            },
+        );
+    }
+    fn set_on_request_close(
+        &self,
+        _ctx: &mut Context,
+        on_request_close: Option<Arc<dyn Fn() + Send + Sync>>,
+    ) {
+        *self.on_request_close.write() = on_request_close;
+    }
+    fn request_close(&self, _ctx: &mut Context) {
+        // the lock is released before calling, the callback may update the modal
+        let callback = self.on_request_close.read().clone();
+
+        if let Some(cb) = callback {
+            cb();
+        }
+    }
+}
+
+// the placeholder takes no space in the parent
+impl_styled_element!(NativeModal, |_this, _width, _height| MeasuredSize {
+    min_width: 0.0,
+    natural_width: 0.0,
+    min_height: 0.0,
+    natural_height: 0.0,
+});
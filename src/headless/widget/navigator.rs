@@ -0,0 +1,269 @@
+use std::sync::Arc;
+
+use crossbeam_channel::{Receiver, TryRecvError};
+use parking_lot::{Mutex, RwLock};
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeNavigatorImp};
+use crate::shadow_tree::component::NavigatorCommand;
+use crate::shadow_tree::NodeID;
+use crate::widget::tab_navigator::{Tab, TabBarPosition};
+
+use super::{impl_styled_element, Frame, HeadlessWidget, NativeElement, DEFAULT_FONT_SIZE};
+
+struct NavigatorState {
+    dropped: bool,
+    tabs: Vec<Tab>,
+    tab_bar_position: TabBarPosition,
+    /// none until a tab is selected, the first tab is shown
+    selected: Option<String>,
+    /// previously selected tabs, the last is returned to on goback
+    history: Vec<String>,
+    children: Vec<(String, NodeID, HeadlessWidget)>,
+}
+
+impl NavigatorState {
+    fn selected(&self) -> Option<String> {
+        if let Some(name) = &self.selected {
+            return Some(name.clone());
+        }
+
+        return self.tabs.first().map(|t| t.name.clone());
+    }
+
+    /// select the tab, returns true if the selected tab changed
+    fn select(&mut self, name: &str) -> bool {
+        if !self.tabs.iter().any(|t| t.name == name) {
+            return false;
+        }
+
+        let previous = self.selected();
+
+        if previous.as_deref() == Some(name) {
+            return false;
+        }
+
+        if let Some(previous) = previous {
+            self.history.push(previous);
+        }
+
+        self.selected = Some(name.to_string());
+
+        return true;
+    }
+
+    /// select the previous tab, returns its name
+    fn back(&mut self) -> Option<String> {
+        let name = self.history.pop()?;
+
+        self.selected = Some(name.clone());
+
+        return Some(name);
+    }
+}
+
+pub struct NativeNavigator {
+    widget: HeadlessWidget,
+    command_recv: Receiver<NavigatorCommand>,
+    state: Mutex<NavigatorState>,
+    on_tab_change: Arc<RwLock<Option<Arc<dyn Fn(&str) + Send + Sync>>>>,
+}
+
+impl NativeNavigator {
+    /// tabs shown in the tab bar
+    pub fn tabs(&self) -> Vec<Tab> {
+        self.state.lock().tabs.clone()
+    }
+
+    /// name of the selected tab
+    pub fn selected_tab(&self) -> Option<String> {
+        self.state.lock().selected()
+    }
+
+    /// simulate the user pressing a tab in the tab bar
+    pub fn select_tab(&self, name: &str) {
+        let changed = {
+            let mut state = self.state.lock();
+            let changed = state.select(name);
+            self.update_record(&state);
+            changed
+        };
+
+        if changed {
+            self.tab_changed(name);
+        }
+    }
+
+    fn tab_changed(&self, name: &str) {
+        // visible page changes, layout must be recomputed
+        crate::state::mark_dirty();
+
+        // the lock is released before calling, the callback may update the navigator
+        let callback = self.on_tab_change.read().clone();
+
+        if let Some(cb) = callback {
+            cb(name);
+        }
+    }
+
+    /// only the visible page is a child of the widget record
+    fn update_record(&self, state: &NavigatorState) {
+        let selected = state.selected();
+
+        let visible = state
+            .children
+            .iter()
+            .find(|(n, _, _)| Some(n) == selected.as_ref())
+            .map(|(_, _, w)| w.clone());
+
+        self.widget.record().children = visible.into_iter().collect();
+    }
+}
+
+impl NativeNavigatorImp for NativeNavigator {
+    fn new(_ctx: &mut Context, command_recv: Receiver<NavigatorCommand>) -> Self {
+        Self {
+            widget: HeadlessWidget::new("tab navigator"),
+            command_recv,
+            state: Mutex::new(NavigatorState {
+                dropped: false,
+                tabs: Vec::new(),
+                tab_bar_position: TabBarPosition::default(),
+                selected: None,
+                history: Vec::new(),
+                children: Vec::new(),
+            }),
+            on_tab_change: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn add_child(&self, _ctx: &mut Context, child: &dyn NativeElement, name: &str, id: NodeID) {
+        let mut state = self.state.lock();
+
+        state
+            .children
+            .push((name.to_string(), id, child.as_headless_widget().clone()));
+
+        self.update_record(&state);
+    }
+
+    fn remove_child(&self, _ctx: &mut Context, name: &str) {
+        let mut state = self.state.lock();
+
+        state.children.retain(|(n, _, _)| n != name);
+
+        self.update_record(&state);
+    }
+
+    fn visible_child(&self, _ctx: &mut Context) -> Option<NodeID> {
+        let state = self.state.lock();
+        let selected = state.selected()?;
+
+        state
+            .children
+            .iter()
+            .find(|(n, _, _)| n == &selected)
+            .map(|(_, id, _)| *id)
+    }
+
+    fn layout_child(
+        &self,
+        _ctx: &mut Context,
+        child: &dyn NativeElement,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    ) {
+        self.widget.layout_child(
+            child.as_headless_widget(),
+            Frame {
+                x,
+                y,
+                width,
+                height,
+            },
+        );
+    }
+
+    fn should_retain(&self, _ctx: &mut Context) -> bool {
+        !self.state.lock().dropped
+    }
+
+    fn set_tabs(&self, _ctx: &mut Context, tabs: &[Tab]) {
+        let mut state = self.state.lock();
+        state.tabs = tabs.to_vec();
+
+        self.update_record(&state);
+    }
+
+    fn set_tab_bar_position(&self, _ctx: &mut Context, position: TabBarPosition) {
+        self.state.lock().tab_bar_position = position;
+    }
+
+    fn tab_bar_position(&self, _ctx: &mut Context) -> TabBarPosition {
+        self.state.lock().tab_bar_position
+    }
+
+    fn tab_bar_height(&self, _ctx: &mut Context) -> f32 {
+        2.0 * DEFAULT_FONT_SIZE
+    }
+
+    fn set_on_tab_change(
+        &self,
+        _ctx: &mut Context,
+        on_tab_change: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+    ) {
+        *self.on_tab_change.write() = on_tab_change;
+    }
+
+    fn check_update(&self) {
+        let mut changes = Vec::new();
+
+        {
+            let mut state = self.state.lock();
+
+            loop {
+                let cmd = match self.command_recv.try_recv() {
+                    Ok(cmd) => cmd,
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        state.dropped = true;
+                        break;
+                    }
+                };
+
+                match cmd {
+                    NavigatorCommand::Goback => {
+                        if let Some(name) = state.back() {
+                            changes.push(name);
+                        }
+                    }
+                    // tabs are not stacked, pushing a tab selects it
+                    NavigatorCommand::Push(name)
+                    | NavigatorCommand::PushWithTransition(name, _)
+                    | NavigatorCommand::Goto(name) => {
+                        if state.select(&name) {
+                            changes.push(name);
+                        }
+                    }
+                }
+            }
+
+            self.update_record(&state);
+        }
+
+        for name in changes {
+            self.tab_changed(&name);
+        }
+    }
+}
+
+impl_styled_element!(NativeNavigator, |_this, _width, _height| {
+    MeasuredSize {
+        min_width: 0.0,
+        natural_width: 0.0,
+        min_height: 0.0,
+        natural_height: 0.0,
+    }
+});
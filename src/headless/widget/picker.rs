@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeFocusableImp, NativePickerImp};
+
+use super::{constrain, impl_styled_element, HeadlessWidget, DEFAULT_FONT_SIZE};
+
+/// space between the label and the border of a picker
+const PADDING: f32 = 8.0;
+
+pub struct NativePicker {
+    widget: HeadlessWidget,
+    options: RwLock<Vec<String>>,
+    selected: RwLock<Option<usize>>,
+    callback: Arc<RwLock<Option<Arc<dyn Fn(usize) + Send + Sync>>>>,
+}
+
+impl NativePicker {
+    pub fn options(&self) -> Vec<String> {
+        self.options.read().clone()
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        *self.selected.read()
+    }
+
+    /// simulate the user picking an option, panics if the index is out of bounds
+    pub fn pick(&self, index: usize) {
+        assert!(index < self.options.read().len(), "option out of bounds");
+
+        self.select(Some(index));
+
+        // the lock is released before calling, the callback may update the picker
+        let callback = self.callback.read().clone();
+
+        if let Some(cb) = callback {
+            cb(index);
+        }
+
+        // the picker is reset to the selected prop if the callback rejected the option
+        crate::state::mark_dirty();
+    }
+
+    /// select an option and show its label
+    fn select(&self, selected: Option<usize>) {
+        let options = self.options.read();
+        let selected = selected.filter(|i| *i < options.len());
+
+        *self.selected.write() = selected;
+        self.widget.record().text = selected.map(|i| options[i].clone());
+    }
+}
+
+impl NativePickerImp for NativePicker {
+    fn new(_ctx: &mut Context) -> Self {
+        Self {
+            widget: HeadlessWidget::new("picker"),
+            options: RwLock::new(Vec::new()),
+            selected: RwLock::new(None),
+            callback: Arc::new(RwLock::new(None)),
+        }
+    }
+    fn splice_options(
+        &self,
+        _ctx: &mut Context,
+        position: usize,
+        removed: usize,
+        added: &[String],
+    ) {
+        self.options
+            .write()
+            .splice(position..position + removed, added.iter().cloned());
+
+        // keep the label of the selected option up to date
+        self.select(self.selected());
+    }
+    fn set_selected(&self, _ctx: &mut Context, selected: Option<usize>) {
+        self.select(selected);
+    }
+    fn set_on_select(
+        &self,
+        _ctx: &mut Context,
+        on_select: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    ) {
+        *self.callback.write() = on_select;
+    }
+}
+
+impl NativeFocusableImp for NativePicker {
+    fn focus(&self, _ctx: &mut Context) -> bool {
+        true
+    }
+    fn blur(&self, _ctx: &mut Context) {}
+}
+
+impl_styled_element!(NativePicker, |this, width, height| {
+    let font_size = this
+        .widget
+        .record()
+        .style
+        .font_size
+        .unwrap_or(DEFAULT_FONT_SIZE);
+
+    // the picker is as wide as the longest option, followed by the arrow
+    let longest = this
+        .options
+        .read()
+        .iter()
+        .map(|o| o.chars().count())
+        .max()
+        .unwrap_or(0);
+    let content_width = longest as f32 * font_size / 2.0 + font_size;
+
+    MeasuredSize {
+        min_width: content_width + PADDING * 2.0,
+        natural_width: constrain(width, content_width + PADDING * 2.0),
+        min_height: font_size + PADDING * 2.0,
+        natural_height: constrain(height, font_size + PADDING * 2.0),
+    }
+});
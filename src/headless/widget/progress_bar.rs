@@ -0,0 +1,71 @@
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeProgressBarImp};
+
+use super::{constrain, impl_styled_element, HeadlessWidget, DEFAULT_FONT_SIZE};
+
+/// natural width of a progress bar
+const LENGTH: f32 = 100.0;
+/// height of the bar without the text
+const THICKNESS: f32 = 4.0;
+
+pub struct NativeProgressBar {
+    widget: HeadlessWidget,
+    fraction: RwLock<f64>,
+    indeterminate: RwLock<bool>,
+}
+
+impl NativeProgressBar {
+    pub fn fraction(&self) -> f64 {
+        *self.fraction.read()
+    }
+
+    pub fn is_indeterminate(&self) -> bool {
+        *self.indeterminate.read()
+    }
+}
+
+impl NativeProgressBarImp for NativeProgressBar {
+    fn new(_ctx: &mut Context) -> Self {
+        Self {
+            widget: HeadlessWidget::new("progress bar"),
+            fraction: RwLock::new(0.0),
+            indeterminate: RwLock::new(false),
+        }
+    }
+    fn set_fraction(&self, _ctx: &mut Context, fraction: f64) {
+        *self.fraction.write() = fraction;
+    }
+    fn set_indeterminate(&self, _ctx: &mut Context, indeterminate: bool) {
+        *self.indeterminate.write() = indeterminate;
+    }
+    fn set_text(&self, _ctx: &mut Context, text: Option<&str>) {
+        self.widget.record().text = text.map(|t| t.to_string());
+    }
+}
+
+impl_styled_element!(NativeProgressBar, |this, width, height| {
+    let (min_width, natural_width, text_height) = this.widget.measure_text();
+    let has_text = this.widget.record().text.is_some();
+    let font_size = this
+        .widget
+        .record()
+        .style
+        .font_size
+        .unwrap_or(DEFAULT_FONT_SIZE);
+
+    // the text is shown above the bar
+    let text_height = if has_text {
+        text_height.max(font_size)
+    } else {
+        0.0
+    };
+
+    MeasuredSize {
+        min_width,
+        natural_width: constrain(width, natural_width.max(LENGTH)),
+        min_height: THICKNESS + text_height,
+        natural_height: constrain(height, THICKNESS + text_height),
+    }
+});
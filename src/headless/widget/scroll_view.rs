@@ -0,0 +1,104 @@
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeScrollViewImp};
+
+use super::{impl_styled_element, Frame, HeadlessWidget, NativeElement};
+
+struct ScrollState {
+    horizontal: f64,
+    vertical: f64,
+    horizontal_scrollable: bool,
+    vertical_scrollable: bool,
+}
+
+pub struct NativeScrollView {
+    widget: HeadlessWidget,
+    scroll: RwLock<ScrollState>,
+}
+
+impl NativeScrollView {
+    /// horizontal and vertical scroll location
+    pub fn scroll_location(&self) -> (f64, f64) {
+        let scroll = self.scroll.read();
+        (scroll.horizontal, scroll.vertical)
+    }
+
+    /// true if the view can be scrolled horizontally and vertically
+    pub fn scrollable(&self) -> (bool, bool) {
+        let scroll = self.scroll.read();
+        (scroll.horizontal_scrollable, scroll.vertical_scrollable)
+    }
+}
+
+impl NativeScrollViewImp for NativeScrollView {
+    fn new(_ctx: &mut Context) -> Self {
+        Self {
+            widget: HeadlessWidget::new("scroll view"),
+            scroll: RwLock::new(ScrollState {
+                horizontal: 0.0,
+                vertical: 0.0,
+                horizontal_scrollable: true,
+                vertical_scrollable: true,
+            }),
+        }
+    }
+    fn set_child(&self, _ctx: &mut Context, child: &dyn NativeElement) {
+        self.widget.record().children = vec![child.as_headless_widget().clone()];
+    }
+    fn remove_child(&self, _ctx: &mut Context) {
+        self.widget.record().children.clear();
+    }
+    fn layout_child(
+        &self,
+        _ctx: &mut Context,
+        child: &dyn NativeElement,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    ) {
+        self.widget.layout_child(
+            child.as_headless_widget(),
+            Frame {
+                x,
+                y,
+                width,
+                height,
+            },
+        );
+    }
+    fn scroll_to_horizontal(&self, _ctx: &mut Context, location: f64) {
+        let mut scroll = self.scroll.write();
+
+        if scroll.horizontal_scrollable {
+            scroll.horizontal = location;
+        }
+    }
+    fn scroll_to_vertical(&self, _ctx: &mut Context, location: f64) {
+        let mut scroll = self.scroll.write();
+
+        if scroll.vertical_scrollable {
+            scroll.vertical = location;
+        }
+    }
+    fn set_horizontal_scrollable(&self, _ctx: &mut Context, scrollable: bool) {
+        self.scroll.write().horizontal_scrollable = scrollable;
+    }
+    fn set_vertical_scrollable(&self, _ctx: &mut Context, scrollable: bool) {
+        self.scroll.write().vertical_scrollable = scrollable;
+    }
+    fn horizontal_scroll_location(&self, _ctx: &mut Context) -> f64 {
+        self.scroll.read().horizontal
+    }
+    fn vertical_scroll_location(&self, _ctx: &mut Context) -> f64 {
+        self.scroll.read().vertical
+    }
+}
+
+impl_styled_element!(NativeScrollView, |_this, _width, _height| MeasuredSize {
+    min_width: 0.0,
+    natural_width: 0.0,
+    min_height: 0.0,
+    natural_height: 0.0,
+});
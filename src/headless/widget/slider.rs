@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeFocusableImp, NativeSliderImp};
+use crate::widget::slider::{snap_value, Orientation};
+
+use super::{constrain, impl_styled_element, HeadlessWidget, DEFAULT_FONT_SIZE};
+
+/// natural length of a slider along its orientation
+const LENGTH: f32 = 100.0;
+
+struct SliderState {
+    value: f64,
+    min: f64,
+    max: f64,
+    step: f64,
+    orientation: Orientation,
+}
+
+pub struct NativeSlider {
+    widget: HeadlessWidget,
+    state: RwLock<SliderState>,
+    on_change: Arc<RwLock<Option<Arc<dyn Fn(f64) + Send + Sync>>>>,
+    on_release: Arc<RwLock<Option<Arc<dyn Fn(f64) + Send + Sync>>>>,
+}
+
+impl NativeSlider {
+    pub fn value(&self) -> f64 {
+        self.state.read().value
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.state.read().orientation
+    }
+
+    /// simulate the user dragging the slider to a value.
+    /// the value is snapped to the range and step like a native slider
+    pub fn slide_to(&self, value: f64) {
+        let value = {
+            let mut state = self.state.write();
+            let value = snap_value(value, state.min, state.max, state.step);
+
+            if value == state.value {
+                return;
+            }
+
+            state.value = value;
+            value
+        };
+
+        // the lock is released before calling, the callback may update the slider
+        let callback = self.on_change.read().clone();
+
+        if let Some(cb) = callback {
+            cb(value);
+        }
+
+        // the slider is reset to the value prop if the callback rejected the value
+        crate::state::mark_dirty();
+    }
+
+    /// simulate the user releasing the slider
+    pub fn release(&self) {
+        let value = self.value();
+        let callback = self.on_release.read().clone();
+
+        if let Some(cb) = callback {
+            cb(value);
+        }
+    }
+}
+
+impl NativeSliderImp for NativeSlider {
+    fn new(_ctx: &mut Context) -> Self {
+        Self {
+            widget: HeadlessWidget::new("slider"),
+            state: RwLock::new(SliderState {
+                value: 0.0,
+                min: 0.0,
+                max: 1.0,
+                step: 0.0,
+                orientation: Orientation::Horizontal,
+            }),
+            on_change: Arc::new(RwLock::new(None)),
+            on_release: Arc::new(RwLock::new(None)),
+        }
+    }
+    fn set_range(&self, _ctx: &mut Context, min: f64, max: f64) {
+        let mut state = self.state.write();
+        state.min = min;
+        state.max = max;
+        state.value = snap_value(state.value, min, max, state.step);
+    }
+    fn set_step(&self, _ctx: &mut Context, step: f64) {
+        self.state.write().step = step;
+    }
+    fn set_value(&self, _ctx: &mut Context, value: f64) {
+        let mut state = self.state.write();
+        state.value = snap_value(value, state.min, state.max, state.step);
+    }
+    fn set_orientation(&self, _ctx: &mut Context, orientation: Orientation) {
+        self.state.write().orientation = orientation;
+    }
+    fn set_on_change(&self, _ctx: &mut Context, on_change: Option<Arc<dyn Fn(f64) + Send + Sync>>) {
+        *self.on_change.write() = on_change;
+    }
+    fn set_on_release(
+        &self,
+        _ctx: &mut Context,
+        on_release: Option<Arc<dyn Fn(f64) + Send + Sync>>,
+    ) {
+        *self.on_release.write() = on_release;
+    }
+}
+
+impl NativeFocusableImp for NativeSlider {
+    fn focus(&self, _ctx: &mut Context) -> bool {
+        true
+    }
+    fn blur(&self, _ctx: &mut Context) {}
+}
+
+impl_styled_element!(NativeSlider, |this, width, height| {
+    let font_size = this
+        .widget
+        .record()
+        .style
+        .font_size
+        .unwrap_or(DEFAULT_FONT_SIZE);
+
+    // the track is as thick as a line of text
+    match this.orientation() {
+        Orientation::Horizontal => MeasuredSize {
+            min_width: font_size,
+            natural_width: constrain(width, LENGTH),
+            min_height: font_size,
+            natural_height: constrain(height, font_size),
+        },
+        Orientation::Vertical => MeasuredSize {
+            min_width: font_size,
+            natural_width: constrain(width, font_size),
+            min_height: font_size,
+            natural_height: constrain(height, LENGTH),
+        },
+    }
+});
@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, TryRecvError};
+use parking_lot::{Mutex, MutexGuard, RwLock};
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeStackNavigatorImp};
+use crate::shadow_tree::component::NavigatorCommand;
+use crate::shadow_tree::NodeID;
+use crate::widget::stack_navigator::{PageEvents, StackNavigatorTransition};
+
+use super::{impl_styled_element, Frame, HeadlessWidget, NativeElement};
+
+struct StackNavigatorState {
+    dropped: bool,
+    transition: StackNavigatorTransition,
+    duration: Duration,
+    /// the transition of the last navigation, true if going back
+    last_transition: Option<(StackNavigatorTransition, bool)>,
+    /// pages in the stack with the transition they were shown with
+    history: Vec<(NodeID, StackNavigatorTransition)>,
+    children: Vec<(String, NodeID, HeadlessWidget)>,
+    /// page pushed before it was added, an instance is pushed before it is rendered
+    pending: Option<(String, StackNavigatorTransition)>,
+    events: HashMap<String, PageEvents>,
+    /// the page focus was last given to
+    focused: Option<NodeID>,
+}
+
+impl StackNavigatorState {
+    fn find(&self, name: &str) -> Option<NodeID> {
+        self.children
+            .iter()
+            .find(|(n, _, _)| n == name)
+            .map(|(_, id, _)| *id)
+    }
+
+    fn name(&self, page: NodeID) -> Option<&str> {
+        self.children
+            .iter()
+            .find(|(_, id, _)| *id == page)
+            .map(|(n, _, _)| n.as_str())
+    }
+
+    fn events(&self, page: NodeID) -> PageEvents {
+        self.name(page)
+            .and_then(|name| self.events.get(name))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// names of the pages in the history, the visible page is last
+    fn history(&self) -> Vec<String> {
+        self.history
+            .iter()
+            .filter_map(|(page, _)| self.name(*page).map(|n| n.to_string()))
+            .collect()
+    }
+}
+
+pub struct NativeStackNavigator {
+    widget: HeadlessWidget,
+    command_recv: Receiver<NavigatorCommand>,
+    state: Mutex<StackNavigatorState>,
+    /// the history read by the `StackNavigator` handles
+    published: Arc<RwLock<Vec<String>>>,
+}
+
+impl NativeStackNavigator {
+    /// names of the pages in the history, the visible page is last
+    pub fn history(&self) -> Vec<String> {
+        self.state.lock().history()
+    }
+
+    /// the default transition and its duration
+    pub fn transition(&self) -> (StackNavigatorTransition, Duration) {
+        let state = self.state.lock();
+        (state.transition, state.duration)
+    }
+
+    /// the transition of the last navigation and whether it went back,
+    /// none before any navigation
+    pub fn last_transition(&self) -> Option<(StackNavigatorTransition, bool)> {
+        self.state.lock().last_transition
+    }
+
+    /// only the visible page is a child of the widget record
+    fn update_record(&self, state: &StackNavigatorState) {
+        let visible = state.history.last().and_then(|(page, _)| {
+            state
+                .children
+                .iter()
+                .find(|(_, id, _)| id == page)
+                .map(|(_, _, w)| w.clone())
+        });
+
+        self.widget.record().children = visible.into_iter().collect();
+
+        *self.published.write() = state.history();
+    }
+
+    /// returns false if the visible page cancels leaving, the lock is released while calling
+    fn may_leave(state: &mut MutexGuard<StackNavigatorState>) -> bool {
+        let guard = match state.history.last() {
+            Some((page, _)) => state.events(*page).on_before_leave,
+            None => None,
+        };
+
+        match guard {
+            Some(guard) => MutexGuard::unlocked(state, || guard()),
+            None => true,
+        }
+    }
+}
+
+impl NativeStackNavigatorImp for NativeStackNavigator {
+    fn new(
+        _ctx: &mut Context,
+        command_recv: Receiver<NavigatorCommand>,
+        history: Arc<RwLock<Vec<String>>>,
+    ) -> Self {
+        Self {
+            widget: HeadlessWidget::new("stack navigator"),
+            command_recv,
+            state: Mutex::new(StackNavigatorState {
+                dropped: false,
+                transition: StackNavigatorTransition::default(),
+                duration: Duration::ZERO,
+                last_transition: None,
+                history: Vec::new(),
+                children: Vec::new(),
+                pending: None,
+                events: HashMap::new(),
+                focused: None,
+            }),
+            published: history,
+        }
+    }
+
+    fn add_child(&self, _ctx: &mut Context, child: &dyn NativeElement, name: &str, id: NodeID) {
+        let mut state = self.state.lock();
+
+        state
+            .children
+            .push((name.to_string(), id, child.as_headless_widget().clone()));
+
+        // the first page is visible
+        if state.history.is_empty() {
+            state.history.push((id, StackNavigatorTransition::None));
+        }
+
+        // show the page if it was pushed before it was added
+        if state.pending.as_ref().map(|(n, _)| n.as_str()) == Some(name) {
+            let (_, transition) = state.pending.take().unwrap();
+
+            state.history.push((id, transition));
+            state.last_transition = Some((transition, false));
+        }
+
+        self.update_record(&state);
+    }
+
+    fn remove_child(&self, _ctx: &mut Context, name: &str) {
+        let mut state = self.state.lock();
+
+        if let Some(page) = state.find(name) {
+            state.children.retain(|(_, id, _)| *id != page);
+            state.history.retain(|(id, _)| *id != page);
+        }
+
+        self.update_record(&state);
+    }
+
+    fn visible_child(&self, _ctx: &mut Context) -> Option<NodeID> {
+        self.state.lock().history.last().map(|(id, _)| *id)
+    }
+
+    fn layout_child(
+        &self,
+        _ctx: &mut Context,
+        child: &dyn NativeElement,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    ) {
+        self.widget.layout_child(
+            child.as_headless_widget(),
+            Frame {
+                x,
+                y,
+                width,
+                height,
+            },
+        );
+    }
+
+    fn should_retain(&self, _ctx: &mut Context) -> bool {
+        !self.state.lock().dropped
+    }
+
+    fn set_transition(
+        &self,
+        _ctx: &mut Context,
+        transition: StackNavigatorTransition,
+        duration: Duration,
+    ) {
+        let mut state = self.state.lock();
+        state.transition = transition;
+        state.duration = duration;
+    }
+
+    fn set_page_events(&self, _ctx: &mut Context, events: Vec<(String, PageEvents)>) {
+        self.state.lock().events = events.into_iter().collect();
+    }
+
+    fn check_update(&self) {
+        let mut state = self.state.lock();
+
+        loop {
+            let cmd = match self.command_recv.try_recv() {
+                Ok(cmd) => cmd,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    state.dropped = true;
+                    break;
+                }
+            };
+
+            // visible page changes, layout must be recomputed
+            crate::state::mark_dirty();
+
+            let (name, transition) = match cmd {
+                NavigatorCommand::Goback => {
+                    if state.history.len() > 1 && Self::may_leave(&mut state) {
+                        // the page leaves the way it came in
+                        let (_, transition) = state.history.pop().unwrap();
+                        state.last_transition = Some((transition, true));
+                    }
+                    continue;
+                }
+                NavigatorCommand::Push(name) => {
+                    let transition = state.transition;
+                    (name, transition)
+                }
+                NavigatorCommand::PushWithTransition(name, transition) => (name, transition),
+                NavigatorCommand::Goto(name) => {
+                    let page = match state.find(&name) {
+                        Some(page) => page,
+                        None => continue,
+                    };
+
+                    // go back to the page if it is in history, push it otherwise
+                    if let Some(i) = state.history.iter().rposition(|(id, _)| *id == page) {
+                        if i + 1 < state.history.len() && Self::may_leave(&mut state) {
+                            let (_, transition) = *state.history.last().unwrap();
+                            state.history.truncate(i + 1);
+                            state.last_transition = Some((transition, true));
+                        }
+                        continue;
+                    }
+
+                    let transition = state.transition;
+                    (name, transition)
+                }
+            };
+
+            if let Some(page) = state.find(&name) {
+                state.history.push((page, transition));
+                state.last_transition = Some((transition, false));
+            } else {
+                // the instance is added once rendered
+                state.pending = Some((name, transition));
+            }
+        }
+
+        self.update_record(&state);
+
+        // focus follows the visible page
+        let visible = state.history.last().map(|(id, _)| *id);
+
+        if visible != state.focused {
+            let blurred = state.focused.map(|page| state.events(page));
+            let focused = visible.map(|page| state.events(page));
+            state.focused = visible;
+
+            // the lock is released before calling, the callbacks may navigate
+            drop(state);
+
+            if let Some(cb) = blurred.and_then(|e| e.on_blur) {
+                cb();
+            }
+            if let Some(cb) = focused.and_then(|e| e.on_focus) {
+                cb();
+            }
+        }
+    }
+}
+
+impl_styled_element!(NativeStackNavigator, |_this, _width, _height| {
+    MeasuredSize {
+        min_width: 0.0,
+        natural_width: 0.0,
+        min_height: 0.0,
+        natural_height: 0.0,
+    }
+});
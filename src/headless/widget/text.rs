@@ -0,0 +1,32 @@
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeTextImp};
+
+use super::{constrain, impl_styled_element, HeadlessWidget};
+
+pub struct NativeText {
+    widget: HeadlessWidget,
+}
+
+impl NativeTextImp for NativeText {
+    fn new(_ctx: &mut Context, text: &str) -> Self {
+        let widget = HeadlessWidget::new("text");
+        widget.record().text = Some(text.to_string());
+
+        Self { widget }
+    }
+
+    fn set_text(&self, _ctx: &mut Context, text: &str) {
+        self.widget.record().text = Some(text.to_string());
+    }
+}
+
+impl_styled_element!(NativeText, |this, width, height| {
+    let (min_width, natural_width, natural_height) = this.widget.measure_text();
+
+    MeasuredSize {
+        min_width,
+        natural_width: constrain(width, natural_width),
+        min_height: natural_height,
+        natural_height: constrain(height, natural_height),
+    }
+});
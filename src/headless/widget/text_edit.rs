@@ -0,0 +1,140 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeFocusableImp, NativeTextEditImp};
+use crate::widget::text_edit::WrapMode;
+
+use super::{constrain, impl_styled_element, HeadlessWidget};
+
+pub struct NativeTextEdit {
+    widget: HeadlessWidget,
+    selection: RwLock<Range<usize>>,
+    read_only: RwLock<bool>,
+    wrap_mode: RwLock<WrapMode>,
+    text_changed_callback: Arc<RwLock<Option<Arc<dyn Fn(&str) + Sync + Send>>>>,
+    selection_changed_callback: Arc<RwLock<Option<Arc<dyn Fn(Range<usize>) + Sync + Send>>>>,
+}
+
+impl NativeTextEdit {
+    /// the current content of the edit
+    pub fn text(&self) -> String {
+        self.widget.record().text.clone().unwrap_or_default()
+    }
+
+    /// the selected range in characters
+    pub fn selection(&self) -> Range<usize> {
+        self.selection.read().clone()
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        *self.read_only.read()
+    }
+
+    pub fn wrap_mode(&self) -> WrapMode {
+        *self.wrap_mode.read()
+    }
+
+    /// simulate the user replacing the content, the cursor is moved to the end.
+    /// does nothing if the edit is read only
+    pub fn input_text(&self, text: &str) {
+        if self.is_read_only() {
+            return;
+        }
+
+        self.widget.record().text = Some(text.to_string());
+
+        let callback = self.text_changed_callback.read().clone();
+
+        if let Some(cb) = callback {
+            cb(text);
+        }
+
+        let end = text.chars().count();
+        self.select(end..end);
+    }
+
+    /// simulate the user selecting a range in characters
+    pub fn select(&self, selection: Range<usize>) {
+        let len = self.text().chars().count();
+        let selection = selection.start.min(len)..selection.end.min(len);
+
+        if *self.selection.read() == selection {
+            return;
+        }
+
+        *self.selection.write() = selection.clone();
+
+        let callback = self.selection_changed_callback.read().clone();
+
+        if let Some(cb) = callback {
+            cb(selection);
+        }
+    }
+}
+
+impl NativeTextEditImp for NativeTextEdit {
+    fn new(_ctx: &mut Context) -> Self {
+        let widget = HeadlessWidget::new("text edit");
+        widget.record().text = Some(String::new());
+
+        Self {
+            widget,
+            selection: RwLock::new(0..0),
+            read_only: RwLock::new(false),
+            wrap_mode: RwLock::new(WrapMode::None),
+            text_changed_callback: Arc::new(RwLock::new(None)),
+            selection_changed_callback: Arc::new(RwLock::new(None)),
+        }
+    }
+    fn set_text(&self, _ctx: &mut Context, text: &str) {
+        self.widget.record().text = Some(text.to_string());
+
+        // keep the selection inside the content
+        let selection = self.selection();
+        self.select(selection);
+    }
+    fn set_selection(&self, _ctx: &mut Context, selection: Range<usize>) {
+        self.select(selection);
+    }
+    fn set_read_only(&self, _ctx: &mut Context, read_only: bool) {
+        *self.read_only.write() = read_only;
+    }
+    fn set_wrap_mode(&self, _ctx: &mut Context, mode: WrapMode) {
+        *self.wrap_mode.write() = mode;
+    }
+    fn set_on_text_changed(
+        &self,
+        _ctx: &mut Context,
+        callback: Option<Arc<dyn Fn(&str) + Sync + Send>>,
+    ) {
+        *self.text_changed_callback.write() = callback;
+    }
+    fn set_on_selection_changed(
+        &self,
+        _ctx: &mut Context,
+        callback: Option<Arc<dyn Fn(Range<usize>) + Sync + Send>>,
+    ) {
+        *self.selection_changed_callback.write() = callback;
+    }
+}
+
+impl NativeFocusableImp for NativeTextEdit {
+    fn focus(&self, _ctx: &mut Context) -> bool {
+        true
+    }
+    fn blur(&self, _ctx: &mut Context) {}
+}
+
+impl_styled_element!(NativeTextEdit, |this, width, height| {
+    let (min_width, natural_width, natural_height) = this.widget.measure_text();
+
+    MeasuredSize {
+        min_width,
+        natural_width: constrain(width, natural_width),
+        min_height: natural_height,
+        natural_height: constrain(height, natural_height),
+    }
+});
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeFocusableImp, NativeToggleImp};
+use crate::shadow_tree::component::ToggleKind;
+
+use super::{constrain, impl_styled_element, HeadlessWidget, DEFAULT_FONT_SIZE};
+
+/// space between the indicator and the label
+const SPACING: f32 = 6.0;
+
+pub struct NativeToggle {
+    widget: HeadlessWidget,
+    kind: ToggleKind,
+    checked: RwLock<bool>,
+    callback: Arc<RwLock<Option<Arc<dyn Fn(bool) + Send + Sync>>>>,
+}
+
+impl NativeToggle {
+    pub fn is_checked(&self) -> bool {
+        *self.checked.read()
+    }
+
+    /// simulate the user toggling the checkbox or switch, does nothing if it is disabled
+    pub fn toggle(&self) {
+        if self.widget.record().disabled {
+            return;
+        }
+
+        let checked = {
+            let mut checked = self.checked.write();
+            *checked = !*checked;
+            *checked
+        };
+
+        // the lock is released before calling, the callback may update the toggle
+        let callback = self.callback.read().clone();
+
+        if let Some(cb) = callback {
+            cb(checked);
+        }
+
+        // the toggle is reset to the checked prop if the callback rejected the toggle
+        crate::state::mark_dirty();
+    }
+}
+
+impl NativeToggleImp for NativeToggle {
+    fn new(_ctx: &mut Context, kind: ToggleKind) -> Self {
+        let widget = HeadlessWidget::new(match kind {
+            ToggleKind::Checkbox => "checkbox",
+            ToggleKind::Switch => "switch",
+        });
+        widget.record().text = Some(String::new());
+
+        Self {
+            widget,
+            kind,
+            checked: RwLock::new(false),
+            callback: Arc::new(RwLock::new(None)),
+        }
+    }
+    fn set_label(&self, _ctx: &mut Context, label: &str) {
+        self.widget.record().text = Some(label.to_string());
+    }
+    fn set_checked(&self, _ctx: &mut Context, checked: bool) {
+        *self.checked.write() = checked;
+    }
+    fn set_disabled(&self, _ctx: &mut Context, disabled: bool) {
+        self.widget.record().disabled = disabled;
+    }
+    fn set_on_toggle(
+        &self,
+        _ctx: &mut Context,
+        on_toggle: Option<Arc<dyn Fn(bool) + Send + Sync>>,
+    ) {
+        *self.callback.write() = on_toggle;
+    }
+}
+
+impl NativeFocusableImp for NativeToggle {
+    fn focus(&self, _ctx: &mut Context) -> bool {
+        !self.widget.record().disabled
+    }
+    fn blur(&self, _ctx: &mut Context) {}
+}
+
+impl_styled_element!(NativeToggle, |this, width, height| {
+    let (min_width, natural_width, text_height) = this.widget.measure_text();
+    let font_size = this
+        .widget
+        .record()
+        .style
+        .font_size
+        .unwrap_or(DEFAULT_FONT_SIZE);
+
+    // the indicator is as high as a line of text, a switch is twice as wide
+    let indicator_height = font_size;
+    let indicator_width = match this.kind {
+        ToggleKind::Checkbox => font_size,
+        ToggleKind::Switch => font_size * 2.0,
+    };
+    let spacing = if natural_width > 0.0 { SPACING } else { 0.0 };
+
+    MeasuredSize {
+        min_width: indicator_width + spacing + min_width,
+        natural_width: constrain(width, indicator_width + spacing + natural_width),
+        min_height: indicator_height,
+        natural_height: constrain(height, text_height.max(indicator_height)),
+    }
+});
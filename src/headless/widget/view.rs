@@ -0,0 +1,65 @@
+use crate::native_tree::context::Context;
+use crate::native_tree::{MeasuredSize, NativeViewImp};
+
+use super::{impl_styled_element, Frame, HeadlessWidget, NativeElement};
+
+pub struct NativeView {
+    widget: HeadlessWidget,
+}
+
+impl NativeViewImp for NativeView {
+    fn new(_ctx: &mut Context) -> Self {
+        Self {
+            widget: HeadlessWidget::new("view"),
+        }
+    }
+
+    fn insert_child(&self, _ctx: &mut Context, index: usize, child: &dyn NativeElement) {
+        let mut record = self.widget.record();
+
+        // replace the child at index, or append
+        if index < record.children.len() {
+            record.children[index] = child.as_headless_widget().clone();
+        } else {
+            record.children.push(child.as_headless_widget().clone());
+        }
+    }
+
+    fn remove_child(&self, _ctx: &mut Context, child: &dyn NativeElement) {
+        self.widget.remove_child(child.as_headless_widget());
+    }
+
+    fn move_child(&self, _ctx: &mut Context, index: usize, child: &dyn NativeElement) {
+        let child = child.as_headless_widget();
+
+        self.widget.remove_child(child);
+        self.widget.insert_child(index, child);
+    }
+
+    fn layout_child(
+        &self,
+        _ctx: &mut Context,
+        child: &dyn NativeElement,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    ) {
+        self.widget.layout_child(
+            child.as_headless_widget(),
+            Frame {
+                x,
+                y,
+                width,
+                height,
+            },
+        );
+    }
+}
+
+impl_styled_element!(NativeView, |_this, _width, _height| MeasuredSize {
+    min_width: 0.0,
+    natural_width: 0.0,
+    min_height: 0.0,
+    natural_height: 0.0,
+});
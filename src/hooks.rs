@@ -0,0 +1,275 @@
+//! Hooks keep per-instance state of an element across renders.
+//!
+//! Hooks must be called inside `Element::render`, in the same order on every render.
+//! The state is bound to the position of the element in the tree and is
+//! discarded once the element is removed from the tree.
+//!
+//! Lifecycle hooks (`on_mount`, `on_unmount` and `on_update`) are invoked on the
+//! main thread once the native node rendered by the element is committed.
+
+use std::sync::Arc;
+
+use parking_lot::{Mutex, MutexGuard};
+
+use crate::focus::FocusHandle;
+use crate::shadow_tree::render::{find_context, register_lifecycle, use_hook};
+use crate::state::State;
+use crate::widget::TextEditHandle;
+
+/// returns a `State` that persists across renders.
+/// `init` is only called on the first render.
+pub fn use_state<T, F>(init: F) -> State<T>
+where
+    T: Send + Sync + 'static,
+    F: FnOnce() -> T,
+{
+    use_hook(|| State::new(init()), |s: &mut State<T>| s.clone())
+}
+
+/// returns a mutable reference that persists across renders.
+/// Unlike `use_state`, mutating the value does not trigger a render.
+/// `init` is only called on the first render.
+pub fn use_ref<T, F>(init: F) -> Ref<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T,
+{
+    use_hook(|| Ref::new(init()), |r: &mut Ref<T>| r.clone())
+}
+
+/// returns a `FocusHandle` that persists across renders.
+pub fn use_focus_handle() -> FocusHandle {
+    use_hook(FocusHandle::new, |h: &mut FocusHandle| h.clone())
+}
+
+/// returns a `TextEditHandle` that persists across renders.
+pub fn use_text_edit_handle() -> TextEditHandle {
+    use_hook(TextEditHandle::new, |h: &mut TextEditHandle| h.clone())
+}
+
+/// returns the value of the nearest ancestor `Provider<T>`, if any.
+pub fn use_context<T>() -> Option<Arc<T>>
+where
+    T: Send + Sync + 'static,
+{
+    find_context::<T>()
+}
+
+/// registers a callback called once the native node of the element is created and mounted.
+pub fn on_mount<F>(callback: F)
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    register_lifecycle(|l| l.on_mount.push(Arc::new(callback)))
+}
+
+/// registers a callback called once the native node of the element is removed.
+/// The callback registered in the last render before removal is used.
+pub fn on_unmount<F>(callback: F)
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    register_lifecycle(|l| l.on_unmount.push(Arc::new(callback)))
+}
+
+/// registers a callback called when the mounted native node is updated by a render.
+pub fn on_update<F>(callback: F)
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    register_lifecycle(|l| l.on_update.push(Arc::new(callback)))
+}
+
+/// A mutable value returned by `use_ref`.
+pub struct Ref<T> {
+    inner: Arc<Mutex<T>>,
+}
+
+impl<T> Clone for Ref<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Ref<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(value)),
+        }
+    }
+
+    /// acquire a lock to the value
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        self.inner.lock()
+    }
+
+    /// replace the value
+    pub fn set(&self, value: T) {
+        *self.inner.lock() = value;
+    }
+}
+
+impl<T: Clone> Ref<T> {
+    /// returns a copy of the value
+    pub fn get(&self) -> T {
+        self.inner.lock().clone()
+    }
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_state_hooks() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{Button, ElementExt, Text, View};
+    use crate::{Element, ElementLike};
+
+    /// a counter showing its count and the number of times it was rendered
+    struct Counter;
+
+    impl Element for Counter {
+        fn render(&self) -> ElementLike {
+            let count = use_state(|| 0);
+            let renders = use_ref(|| 0);
+            let c = count.clone();
+            let r = renders.clone();
+
+            *renders.lock() += 1;
+
+            Box::new(
+                View::new()
+                    .with_child(
+                        Text::new(format!("{} {}", count.get(), renders.get())).test_id("label"),
+                    )
+                    .with_child(
+                        Button::new()
+                            .with_on_click(move || c.update(|c| *c += 1))
+                            .test_id("increment"),
+                    )
+                    .with_child(
+                        Button::new()
+                            .with_on_click(move || r.set(0))
+                            .test_id("reset"),
+                    ),
+            )
+        }
+    }
+
+    let shown = State::new(true);
+    let s = shown.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let mut view = View::new();
+
+        if s.get() {
+            view.add_child(Counter);
+        }
+
+        view
+    });
+
+    let label = |renderer: &TestRenderer| {
+        renderer
+            .find_by_test_id("label")
+            .and_then(|l| l.text())
+            .unwrap_or_default()
+    };
+
+    assert_eq!(label(&renderer), "0 1");
+
+    // the state persists across renders
+    renderer.find_by_test_id("increment").unwrap().click();
+    assert!(renderer.step());
+    assert_eq!(label(&renderer), "1 2");
+
+    renderer.render();
+    assert_eq!(label(&renderer), "1 3");
+
+    // mutating a ref does not render
+    renderer.find_by_test_id("reset").unwrap().click();
+    assert!(!renderer.step());
+    assert_eq!(label(&renderer), "1 3");
+
+    renderer.render();
+    assert_eq!(label(&renderer), "1 1");
+
+    // the hooks are discarded with the element
+    shown.set(false);
+    renderer.step();
+    assert!(renderer.find_by_test_id("label").is_none());
+
+    shown.set(true);
+    renderer.step();
+    assert_eq!(label(&renderer), "0 1");
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_lifecycle_hooks() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{Text, View};
+    use crate::{Element, ElementLike};
+
+    static LOG: Mutex<Vec<String>> = parking_lot::const_mutex(Vec::new());
+
+    /// logs its lifecycle events with its name
+    struct Logger {
+        name: &'static str,
+        child: Option<&'static str>,
+    }
+
+    impl Element for Logger {
+        fn render(&self) -> ElementLike {
+            let name = self.name;
+
+            on_mount(move || LOG.lock().push(format!("mount {}", name)));
+            on_update(move || LOG.lock().push(format!("update {}", name)));
+            on_unmount(move || LOG.lock().push(format!("unmount {}", name)));
+
+            let mut view = View::new().with_child(Text::new(name));
+
+            if let Some(child) = self.child {
+                view.add_child(Logger {
+                    name: child,
+                    child: None,
+                });
+            }
+
+            Box::new(view)
+        }
+    }
+
+    let shown = State::new(true);
+    let s = shown.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let mut view = View::new();
+
+        if s.get() {
+            view.add_child(Logger {
+                name: "parent",
+                child: Some("child"),
+            });
+        }
+
+        view
+    });
+
+    let take = || std::mem::take(&mut *LOG.lock());
+
+    // children are mounted before their parent
+    assert_eq!(take(), ["mount child", "mount parent"]);
+
+    renderer.render();
+    assert_eq!(take(), ["update child", "update parent"]);
+
+    // children are unmounted before their parent
+    shown.set(false);
+    renderer.step();
+    assert_eq!(take(), ["unmount child", "unmount parent"]);
+
+    shown.set(true);
+    renderer.step();
+    assert_eq!(take(), ["mount child", "mount parent"]);
+}
@@ -1,31 +1,51 @@
 mod app;
 pub mod custom;
+pub mod event;
+pub mod focus;
+pub mod hooks;
 pub mod image;
 mod native_tree;
+pub mod router;
 mod shadow_tree;
+pub mod state;
 pub mod style;
+#[cfg(feature = "headless")]
+pub mod testing;
 pub mod util;
 pub mod widget;
 
-#[cfg(target_os = "linux")]
+#[cfg(all(target_os = "linux", not(any(feature = "gtk", feature = "headless"))))]
+compile_error!("either the `gtk` or the `headless` feature must be enabled on linux");
+
+#[cfg(all(target_os = "linux", not(feature = "headless")))]
 pub mod linux;
 
-#[cfg(target_os = "linux")]
+#[cfg(all(target_os = "linux", not(feature = "headless")))]
 use linux as imp;
 
-#[cfg(target_family = "wasm")]
+#[cfg(feature = "headless")]
+pub mod headless;
+
+#[cfg(feature = "headless")]
+use headless as imp;
+
+#[cfg(all(target_family = "wasm", not(feature = "headless")))]
 pub mod web;
 
-#[cfg(target_family = "wasm")]
+#[cfg(all(target_family = "wasm", not(feature = "headless")))]
 use web as imp;
 
-#[cfg(target_os = "android")]
+#[cfg(all(target_os = "android", feature = "headless"))]
+compile_error!("the `headless` feature is not supported on android");
+
+#[cfg(all(target_os = "android", not(feature = "headless")))]
 pub mod android;
 
-#[cfg(target_os = "android")]
+#[cfg(all(target_os = "android", not(feature = "headless")))]
 use android as imp;
 
 pub use app::{App, AppBuilder, Application};
+pub use state::State;
 
 pub type ElementLike = Box<dyn private::ElementLike>;
 
@@ -49,6 +69,10 @@ mod private {
     pub trait ElementLike: 'static {
         fn as_native(&mut self) -> Option<&mut dyn NativeElement>;
         fn as_element(&mut self) -> Option<&mut dyn Element>;
+        /// type of the element, used to identify the element across renders
+        fn element_type_id(&self) -> core::any::TypeId {
+            core::any::TypeId::of::<Self>()
+        }
         fn render(&mut self) -> Result<CoreComponent, Box<dyn ElementLike>> {
             if let Some(e) = self.as_native() {
                 e.render();
@@ -4,22 +4,41 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use crossbeam_channel::{Receiver, Sender};
-use gtk4::prelude::{ApplicationExt, ApplicationExtManual, GtkWindowExt, WidgetExt};
+use gtk4::prelude::{
+    AdjustmentExt, ApplicationExt, ApplicationExtManual, EventControllerExt, FileExt,
+    GestureSingleExt, GtkWindowExt, WidgetExt,
+};
 use gtk4::ApplicationWindow;
 use parking_lot::RwLock;
 
 use crate::app::Application;
+use crate::event::{
+    trigger_accelerators, Accelerator, AcceleratorCallback, KeyEvent, KeyEventKind, Modifiers,
+    PointerButton, PointerEventKind,
+};
 use crate::native_tree::context::Context;
+use crate::native_tree::node::NativeComponent;
 use crate::native_tree::NativeTree;
+use crate::router::Router;
 use crate::shadow_tree::command::Command;
 use crate::shadow_tree::commit::commit_tree;
 use crate::shadow_tree::component::CoreComponent;
+use crate::shadow_tree::render::render_app;
+use crate::shadow_tree::NodeID;
+use crate::state::DirtyFlag;
+
+/// the native tree shared by the render loop and the event handlers of the main thread.
+/// event handlers are ignored while the render loop mutates the tree
+type SharedTree = Rc<RefCell<&'static mut NativeTree<'static>>>;
 
 pub struct GtkApp {
     app: gtk4::Application,
     title: String,
     width: u32,
     height: u32,
+    accelerators: Arc<Vec<(Accelerator, AcceleratorCallback)>>,
+    router: Option<Router>,
+    url: Option<String>,
 
     inner: Arc<AppInner>,
 }
@@ -47,9 +66,16 @@ impl GtkApp {
         // initialise gtk
         gtk4::init().expect("failed to initialise GTK");
 
+        // urls given on the command line or on activation are opened by the router
+        let flags = match builder.router {
+            Some(_) => gtk4::gio::ApplicationFlags::HANDLES_OPEN,
+            None => gtk4::gio::ApplicationFlags::empty(),
+        };
+
         // create a new gtk app
         let app = gtk4::Application::builder()
             .application_id("com.ui.native")
+            .flags(flags)
             .build();
 
         Self {
@@ -57,6 +83,9 @@ impl GtkApp {
             title: builder.title.unwrap_or_default(),
             width: builder.width.unwrap_or(320),
             height: builder.width.unwrap_or(320),
+            accelerators: Arc::new(builder.accelerators),
+            router: builder.router,
+            url: builder.url,
 
             inner: Default::default(),
         }
@@ -74,8 +103,25 @@ impl GtkApp {
         root_view.set_hexpand(true);
         root_view.set_vexpand(true);
 
-        // clone reference to root view
-        let cloned_root_view = root_view.clone();
+        // modals are presented in layers above the root view
+        let overlay = gtk4::Overlay::new();
+        overlay.set_child(Some(&root_view));
+
+        // just a dummy context
+        let mut ctx = Context::<'static>::dummy();
+
+        // create a new native tree
+        let native_tree: SharedTree = Rc::new(RefCell::new(NativeTree::get(&mut ctx)));
+
+        // pointer events are hit tested against the native tree
+        connect_pointer_events(&overlay, &root_view, native_tree.clone());
+
+        // key events are delivered to the focused node
+        let key_tree = native_tree.clone();
+        let accelerators = self.accelerators.clone();
+
+        // clone reference to the overlay
+        let cloned_overlay = overlay.clone();
 
         // create cell to store the window
         let window = Rc::new(RefCell::new(None));
@@ -91,8 +137,10 @@ impl GtkApp {
                 .default_height(height as _)
                 .build();
 
-            // set content as root view
-            window.set_child(Some(&cloned_root_view));
+            // set content as the overlay of the root view
+            window.set_child(Some(&cloned_overlay));
+
+            connect_key_events(&window, key_tree.clone(), accelerators.clone());
 
             // present the window
             window.present();
@@ -101,38 +149,55 @@ impl GtkApp {
             cloned_window.borrow_mut().replace(window);
         });
 
+        if let Some(router) = self.router.clone() {
+            // deep links on the command line are opened by the open signal
+            let deep_link = std::env::args()
+                .skip(1)
+                .any(|arg| router.is_deep_link(&arg));
+
+            if !deep_link {
+                if let Some(url) = &self.url {
+                    router.open(url);
+                }
+            }
+
+            let opened_window = window.clone();
+
+            self.app.connect_open(move |app, files, _hint| {
+                for file in files {
+                    router.open(&file.uri());
+                }
+
+                // the window is only created on the first activation
+                let window = opened_window.borrow().clone();
+
+                match window {
+                    Some(window) => window.present(),
+                    None => app.activate(),
+                }
+            });
+        }
+
         // clone app inner
         let app_inner = self.inner.clone();
 
+        // marked when a state rendered by the app is mutated
+        let dirty = DirtyFlag::new();
+
         // create new thread for rendering
         std::thread::spawn(move || {
             // indefinite loop for rendering
             loop {
-                // call on active
-                let mut widget = app.render();
-
-                // render the widget
-                let mut elem = widget.render();
-
-                // get the native component from the rendered element
-                let mut comp = loop {
-                    match elem {
-                        // element is not native
-                        Err(mut e) => {
-                            // keep rendering the element
-                            elem = e.render();
-                        }
-                        // element is native
-                        Ok(c) => {
-                            // return the native component
-                            break c;
-                        }
-                    }
-                };
+                // wait until some state is mutated
+                dirty.wait();
 
                 // acquire read lock to the old tree
                 let mut old = app_inner.comitted_tree.upgradable_read();
 
+                // render the app until native component is reached,
+                // element states are restored from the old tree
+                let mut comp = render_app(&mut app, old.as_ref(), &dirty);
+
                 // encode the new tree into commands by comparing to the old tree
                 let commands = commit_tree(&mut comp, old.as_ref());
 
@@ -149,27 +214,23 @@ impl GtkApp {
                     .command_sender
                     .send(commands)
                     .expect("app closed unexpectedly");
-
-                // sleep 18ms
-                std::thread::sleep(Duration::from_millis(20));
             }
         });
 
         let app_inner = self.inner.clone();
 
-        // just a dummy context
-        let mut ctx = Context::<'static>::dummy();
-
-        // create a new native tree
-        let native_tree = NativeTree::get(&mut ctx);
-
         let mut last_child: Option<gtk4::Widget> = None;
+        let mut last_layers: Vec<gtk4::Widget> = Vec::new();
+        let mut last_size = (0, 0);
 
         // force callback every 18ms
         glib::timeout_add_local(Duration::from_millis(20), move || {
             // has commands been made to tree
             let mut commands_recieved = false;
 
+            // events emitted while the tree is updated are ignored
+            let mut native_tree = native_tree.borrow_mut();
+
             // try to fetch commands
             while let Ok(commands) = app_inner.command_receiver.try_recv() {
                 commands_recieved = true;
@@ -177,14 +238,26 @@ impl GtkApp {
                 native_tree.execute_commands(&mut Context::dummy(), commands);
             }
 
-            // only need to check root if any command has been made
-            if commands_recieved {
-                let window = window.borrow();
-                let window = window.as_ref().unwrap();
+            // check update
+            native_tree.check_update();
 
-                // check update
-                native_tree.check_update();
+            let window = window.borrow();
+            let window = match window.as_ref() {
+                Some(w) => w,
+                None => return glib::ControlFlow::Continue,
+            };
+
+            // layout must be recomputed if the window is resized
+            let size = (window.width(), window.height());
+            let resized = size != last_size;
+            last_size = size;
+
+            if resized && !commands_recieved {
+                native_tree.compute_layout(&mut Context::dummy(), size.0 as _, size.1 as _);
+            }
 
+            // only need to check root if any command has been made
+            if commands_recieved {
                 // recalculate layout
                 native_tree.compute_layout(
                     &mut Context::dummy(),
@@ -213,6 +286,30 @@ impl GtkApp {
                 } else {
                     root_view.set_child_visible(false);
                 }
+
+                // present the layers of the modals, later modals above
+                let layers: Vec<gtk4::Widget> = native_tree
+                    .get_modal_nodes()
+                    .iter()
+                    .filter_map(|m| match m.as_ref() {
+                        NativeComponent::Modal(m) => Some(m.layer().clone()),
+                        _ => None,
+                    })
+                    .collect();
+
+                for layer in &last_layers {
+                    if !layers.contains(layer) {
+                        overlay.remove_overlay(layer);
+                    }
+                }
+
+                for layer in &layers {
+                    if !last_layers.contains(layer) {
+                        overlay.add_overlay(layer);
+                    }
+                }
+
+                last_layers = layers;
             }
 
             // indefinite loop
@@ -223,3 +320,173 @@ impl GtkApp {
         self.app.run();
     }
 }
+
+/// deliver the pointer events of the window content to the native tree.
+/// events are observed in the capture phase and never claimed,
+/// native widgets such as buttons still receive them.
+fn connect_pointer_events(
+    overlay: &gtk4::Overlay,
+    root_view: &gtk4::ScrolledWindow,
+    tree: SharedTree,
+) {
+    let click = gtk4::GestureClick::new();
+    // listen to all buttons
+    click.set_button(0);
+    click.set_propagation_phase(gtk4::PropagationPhase::Capture);
+
+    let view = root_view.clone();
+    let t = tree.clone();
+    click.connect_pressed(move |gesture, _n_press, x, y| {
+        if let Ok(tree) = t.try_borrow() {
+            let (x, y) = content_position(&view, &tree, x, y);
+            let button = pointer_button(gesture.current_button());
+            tree.dispatch_pointer(PointerEventKind::Down, Some(button), x, y);
+        }
+    });
+
+    let view = root_view.clone();
+    let t = tree.clone();
+    click.connect_released(move |gesture, _n_press, x, y| {
+        if let Ok(tree) = t.try_borrow() {
+            let (x, y) = content_position(&view, &tree, x, y);
+            let button = pointer_button(gesture.current_button());
+            tree.dispatch_pointer(PointerEventKind::Up, Some(button), x, y);
+        }
+    });
+
+    overlay.add_controller(click);
+
+    let motion = gtk4::EventControllerMotion::new();
+    motion.set_propagation_phase(gtk4::PropagationPhase::Capture);
+
+    let view = root_view.clone();
+    let t = tree.clone();
+    motion.connect_enter(move |_, x, y| {
+        if let Ok(tree) = t.try_borrow() {
+            let (x, y) = content_position(&view, &tree, x, y);
+            tree.dispatch_pointer(PointerEventKind::Enter, None, x, y);
+        }
+    });
+
+    let view = root_view.clone();
+    let t = tree.clone();
+    motion.connect_motion(move |_, x, y| {
+        if let Ok(tree) = t.try_borrow() {
+            let (x, y) = content_position(&view, &tree, x, y);
+            tree.dispatch_pointer(PointerEventKind::Move, None, x, y);
+        }
+    });
+
+    motion.connect_leave(move |_| {
+        if let Ok(tree) = tree.try_borrow() {
+            tree.dispatch_pointer(PointerEventKind::Leave, None, 0.0, 0.0);
+        }
+    });
+
+    overlay.add_controller(motion);
+}
+
+/// deliver the key events of the window to the accelerators and the focused node.
+/// the native widget does not receive an event stopped by a handler,
+/// Tab and Shift+Tab move the focus in tab order.
+fn connect_key_events(
+    window: &ApplicationWindow,
+    tree: SharedTree,
+    accelerators: Arc<Vec<(Accelerator, AcceleratorCallback)>>,
+) {
+    let key = gtk4::EventControllerKey::new();
+    key.set_propagation_phase(gtk4::PropagationPhase::Capture);
+
+    let t = tree.clone();
+    key.connect_key_pressed(move |_, keyval, keycode, state| {
+        let event = key_event(KeyEventKind::Down, keyval, keycode, state);
+
+        if trigger_accelerators(&accelerators, &event) {
+            return glib::Propagation::Stop;
+        }
+
+        let handled = match t.try_borrow() {
+            Ok(tree) => tree.handle_key(&event),
+            Err(_) => false,
+        };
+
+        match handled {
+            true => glib::Propagation::Stop,
+            false => glib::Propagation::Proceed,
+        }
+    });
+
+    let t = tree.clone();
+    key.connect_key_released(move |_, keyval, keycode, state| {
+        let event = key_event(KeyEventKind::Up, keyval, keycode, state);
+
+        if let Ok(tree) = t.try_borrow() {
+            tree.handle_key(&event);
+        }
+    });
+
+    window.add_controller(key);
+
+    // the native tree follows the focus of the window,
+    // nodes removed by the render loop forget their focus
+    window.connect_focus_widget_notify(move |window| {
+        if let Ok(tree) = tree.try_borrow() {
+            tree.focus_changed(focused_node(window, &tree));
+        }
+    });
+}
+
+fn key_event(
+    kind: KeyEventKind,
+    keyval: gtk4::gdk::Key,
+    keycode: u32,
+    state: gtk4::gdk::ModifierType,
+) -> KeyEvent {
+    let modifiers = Modifiers {
+        shift: state.contains(gtk4::gdk::ModifierType::SHIFT_MASK),
+        control: state.contains(gtk4::gdk::ModifierType::CONTROL_MASK),
+        alt: state.contains(gtk4::gdk::ModifierType::ALT_MASK),
+        meta: state.contains(gtk4::gdk::ModifierType::SUPER_MASK)
+            || state.contains(gtk4::gdk::ModifierType::META_MASK),
+    };
+
+    let name = keyval.name().map(|n| n.to_string()).unwrap_or_default();
+
+    return KeyEvent::new(kind, name, keyval.to_unicode(), keycode, modifiers);
+}
+
+/// the innermost node containing the focused widget of the window
+fn focused_node(window: &ApplicationWindow, tree: &NativeTree) -> Option<NodeID> {
+    let mut widget = window.focus();
+
+    while let Some(w) = widget {
+        if let Some(id) = tree.find_node(|c| c.widget().as_gtk4_widget() == &w) {
+            return Some(id);
+        }
+
+        widget = w.parent();
+    }
+
+    return None;
+}
+
+/// position relative to the scrolled content of the root view,
+/// modals are not scrolled with the content
+fn content_position(view: &gtk4::ScrolledWindow, tree: &NativeTree, x: f64, y: f64) -> (f32, f32) {
+    if tree.top_modal().is_some() {
+        return (x as f32, y as f32);
+    }
+
+    let x = x + view.hadjustment().value();
+    let y = y + view.vadjustment().value();
+    return (x as f32, y as f32);
+}
+
+fn pointer_button(button: u32) -> PointerButton {
+    match button {
+        1 => PointerButton::Primary,
+        2 => PointerButton::Middle,
+        3 => PointerButton::Secondary,
+        b => PointerButton::Other(b),
+    }
+}
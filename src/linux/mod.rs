@@ -4,13 +4,20 @@ mod widget;
 pub use gtk4;
 
 pub(super) use app::GtkApp as NativeApp;
+pub(super) use widget::NativeActivityIndicator;
 pub(super) use widget::NativeButton;
 pub use widget::NativeElement;
 pub(super) use widget::NativeImageView;
 pub(super) use widget::NativeListView;
+pub(super) use widget::NativeModal;
+pub(crate) use widget::NativeNavigator;
+pub(super) use widget::NativePicker;
+pub(super) use widget::NativeProgressBar;
 pub(super) use widget::NativeScrollView;
+pub(super) use widget::NativeSlider;
 pub(crate) use widget::NativeStackNavigator;
 pub(super) use widget::NativeText;
 pub(crate) use widget::NativeTextEdit;
 pub(crate) use widget::NativeTextInput;
+pub(super) use widget::NativeToggle;
 pub(super) use widget::NativeView;
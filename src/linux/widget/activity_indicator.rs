@@ -0,0 +1,118 @@
+use gtk4::prelude::*;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{
+    AvalableSpace, MeasuredSize, NativeActivityIndicatorImp, NativeStyledElement,
+};
+use crate::style::{
+    BorderStyle, Colour, FontStyle, FontWeight, PointEvents, TextAlign, TextDecorationLine,
+    TextTransform,
+};
+
+use super::{css_colour, NativeElement, WidgetCss};
+
+pub struct NativeActivityIndicator {
+    spinner: gtk4::Spinner,
+    css: WidgetCss,
+}
+
+impl NativeActivityIndicatorImp for NativeActivityIndicator {
+    fn new(_ctx: &mut Context) -> Self {
+        let spinner = gtk4::Spinner::new();
+        let css = WidgetCss::new(spinner.as_ref());
+
+        Self { spinner, css }
+    }
+
+    fn set_animating(&self, _ctx: &mut Context, animating: bool) {
+        self.spinner.set_spinning(animating)
+    }
+}
+
+impl NativeElement for NativeActivityIndicator {
+    fn as_gtk4_widget(&self) -> &gtk4::Widget {
+        self.spinner.as_ref()
+    }
+}
+
+impl NativeStyledElement for NativeActivityIndicator {
+    fn measure(
+        &self,
+        _ctx: &mut Context,
+        known_width: AvalableSpace,
+        known_height: AvalableSpace,
+    ) -> anyhow::Result<MeasuredSize> {
+        // measure width
+        let (min_width, natural_width, _, _) = self.spinner.measure(
+            gtk4::Orientation::Horizontal,
+            match known_height {
+                AvalableSpace::AtMost(f) => f as i32,
+                AvalableSpace::Exact(f) => f as i32,
+                AvalableSpace::Unknown => -1,
+            },
+        );
+        // measure height
+        let (min_height, natural_height, _, _) = self.spinner.measure(
+            gtk4::Orientation::Vertical,
+            match known_width {
+                AvalableSpace::AtMost(f) => f as i32,
+                AvalableSpace::Exact(f) => f as i32,
+                AvalableSpace::Unknown => -1,
+            },
+        );
+
+        return Ok(MeasuredSize {
+            min_width: min_width as f32,
+            natural_width: natural_width as f32,
+            min_height: min_height as f32,
+            natural_height: natural_height as f32,
+        });
+    }
+    fn set_visible(&self, _ctx: &mut Context, visible: bool) {
+        self.spinner.set_visible(visible)
+    }
+    fn set_backface_visible(&self, _visible: bool) {}
+
+    fn set_colour(&self, colour: Colour) {
+        // the spinner is drawn with the foreground colour
+        self.css
+            .set_rules(&format!("& {{ color: {}; }}", css_colour(colour)));
+    }
+    fn set_background_colour(&self, _colour: Colour) {}
+
+    fn set_border_top_width(&self, _width: f32) {}
+    fn set_border_bottom_width(&self, _width: f32) {}
+    fn set_border_left_width(&self, _width: f32) {}
+    fn set_border_right_width(&self, _width: f32) {}
+
+    fn set_border_top_left_radius(&self, _radius: f32) {}
+    fn set_border_top_right_radius(&self, _radius: f32) {}
+    fn set_border_bottom_left_radius(&self, _radius: f32) {}
+    fn set_border_bottom_right_radius(&self, _radius: f32) {}
+
+    fn set_border_top_colour(&self, _colour: Colour) {}
+    fn set_border_bottom_colour(&self, _colour: Colour) {}
+    fn set_border_left_colour(&self, _colour: Colour) {}
+    fn set_border_right_colour(&self, _colour: Colour) {}
+
+    fn set_border_style(&self, _style: BorderStyle) {}
+
+    fn set_opacity(&self, opacity: f32) {
+        self.spinner.set_opacity(opacity as f64);
+    }
+    fn set_points_event(&self, _event: PointEvents) {}
+
+    fn set_font_size(&self, _size: f32) {}
+    fn set_font_style(&self, _style: FontStyle) {}
+    fn set_font_weight(&self, _weight: FontWeight) {}
+
+    fn set_letter_spacing(&self, _spacing: f32) {}
+    fn set_line_height(&self, _height: f32) {}
+
+    fn set_text_align(&self, _align: TextAlign) {}
+    fn set_text_decloration_line(&self, _line: TextDecorationLine) {}
+    fn set_text_decloration_colour(&self, _colour: Colour) {}
+    fn set_text_shadow_colour(&self, _colour: Colour) {}
+    fn set_text_shadow_radius(&self, _radius: f32) {}
+    fn set_text_transform(&self, _transform: TextTransform) {}
+}
@@ -6,14 +6,14 @@ use parking_lot::RwLock;
 use crate::native_tree::context::Context;
 use crate::native_tree::AvalableSpace;
 use crate::native_tree::{
-    traits::NativeButtonImp, MeasuredSize, NativeStyledElement, NativeTextImp,
+    traits::NativeButtonImp, MeasuredSize, NativeFocusableImp, NativeStyledElement, NativeTextImp,
 };
 use crate::style::{
     BorderStyle, Colour, FontStyle, FontWeight, PointEvents, TextAlign, TextDecorationLine,
     TextTransform,
 };
 
-use super::{NativeElement, NativeText};
+use super::{blur_widget, NativeElement, NativeText};
 
 pub struct NativeButton {
     label: NativeText,
@@ -63,6 +63,15 @@ impl NativeButtonImp for NativeButton {
     }
 }
 
+impl NativeFocusableImp for NativeButton {
+    fn focus(&self, _ctx: &mut Context) -> bool {
+        self.button.grab_focus()
+    }
+    fn blur(&self, _ctx: &mut Context) {
+        blur_widget(self.button.as_ref())
+    }
+}
+
 impl super::NativeElement for NativeButton {
     fn as_gtk4_widget(&self) -> &gtk4::Widget {
         self.button.as_ref()
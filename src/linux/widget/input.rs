@@ -1,19 +1,27 @@
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::sync::Arc;
 
 use gtk4::prelude::*;
 use parking_lot::RwLock;
 
 use crate::native_tree::context::Context;
-use crate::native_tree::{AvalableSpace, MeasuredSize, NativeStyledElement, NativeTextInputImp};
+use crate::native_tree::{
+    AvalableSpace, MeasuredSize, NativeFocusableImp, NativeStyledElement, NativeTextInputImp,
+};
 use crate::style::{
     BorderStyle, Colour, FontStyle, FontWeight, PointEvents, TextAlign, TextDecorationLine,
     TextTransform,
 };
+use crate::widget::input::{InputFormatter, InputRules, InputType};
 
-use super::NativeElement;
+use super::{blur_widget, NativeElement};
 
 pub struct NativeTextInput {
     input: gtk4::Entry,
+    /// the handler of the changed signal, blocked while the text is set programmatically
+    changed_handler: Rc<RefCell<Option<gtk4::glib::SignalHandlerId>>>,
+    rules: Arc<RwLock<InputRules>>,
     text_changed_callback: Arc<RwLock<Option<Arc<dyn Fn(&str) + Sync + Send>>>>,
     enter_callback: Arc<RwLock<Option<Arc<dyn Fn(&str) + Sync + Send>>>>,
 }
@@ -43,15 +51,40 @@ impl NativeTextInputImp for NativeTextInput {
         let text_changed_callback: Arc<RwLock<Option<Arc<dyn Fn(&str) + Sync + Send>>>> =
             Arc::new(RwLock::new(None));
         let cb = text_changed_callback.clone();
+        let rules: Arc<RwLock<InputRules>> = Arc::new(RwLock::new(InputRules::default()));
+        let r = rules.clone();
+        let changed_handler: Rc<RefCell<Option<gtk4::glib::SignalHandlerId>>> = Rc::default();
+        let handler = changed_handler.clone();
 
-        entry.connect_changed(move |entry| {
-            let lock = cb.read();
+        let id = entry.connect_changed(move |entry| {
+            let text = entry.text();
+            let formatted = r.read().apply(text.as_str());
 
-            if let Some(cb) = lock.as_ref() {
-                (cb)(entry.text().as_str())
+            // the cursor is only moved once the edit is complete,
+            // the text is formatted after the signal
+            if formatted != text.as_str() {
+                let entry = entry.downgrade();
+                let (rules, handler) = (r.clone(), handler.clone());
+
+                gtk4::glib::idle_add_local_once(move || {
+                    if let Some(entry) = entry.upgrade() {
+                        format_text(&entry, &rules.read(), &handler);
+                    }
+                });
+            }
+
+            let callback = cb.read().clone();
+
+            if let Some(cb) = callback {
+                (cb)(&formatted)
             }
+
+            // a controlled input is reset to its value if the callback rejected the text
+            crate::state::mark_dirty();
         });
 
+        *changed_handler.borrow_mut() = Some(id);
+
         let enter_callback: Arc<RwLock<Option<Arc<dyn Fn(&str) + Sync + Send>>>> =
             Arc::new(RwLock::new(None));
         let cb = enter_callback.clone();
@@ -66,6 +99,8 @@ impl NativeTextInputImp for NativeTextInput {
 
         Self {
             input: entry,
+            changed_handler,
+            rules,
             text_changed_callback,
             enter_callback,
         }
@@ -74,11 +109,110 @@ impl NativeTextInputImp for NativeTextInput {
     fn set_background_text(&self, _ctx: &mut Context, text: &str) {
         self.input.set_placeholder_text(Some(text))
     }
-    fn set_on_text_changed(&self, _ctx: &mut Context, callback: Arc<dyn Fn(&str) + Sync + Send>) {
-        *self.text_changed_callback.write() = Some(callback)
+    fn set_input_type(&self, _ctx: &mut Context, input_type: InputType) {
+        self.rules.write().input_type = input_type;
+
+        self.input.set_visibility(input_type != InputType::Password);
+        self.input.set_input_purpose(match input_type {
+            InputType::Text => gtk4::InputPurpose::FreeForm,
+            InputType::Password => gtk4::InputPurpose::Password,
+            InputType::Number => gtk4::InputPurpose::Number,
+            InputType::Email => gtk4::InputPurpose::Email,
+        });
+    }
+    fn set_max_length(&self, _ctx: &mut Context, max_length: Option<usize>) {
+        self.rules.write().max_length = max_length;
+
+        // zero means no limit
+        self.input
+            .set_max_length(max_length.map_or(0, |max| max.min(i32::MAX as usize) as i32));
+    }
+    fn set_formatter(&self, _ctx: &mut Context, formatter: Option<InputFormatter>) {
+        self.rules.write().formatter = formatter;
+    }
+    fn set_text(&self, _ctx: &mut Context, text: &str) {
+        let current = self.input.text();
+
+        // setting the same text moves the cursor to the end,
+        // an edit waiting to be formatted is formatted into the same text
+        if current == text || self.rules.read().apply(current.as_str()) == text {
+            return;
+        }
+
+        let handler = self.changed_handler.borrow();
+
+        if let Some(handler) = handler.as_ref() {
+            self.input.block_signal(handler);
+        }
+
+        self.input.set_text(text);
+
+        if let Some(handler) = handler.as_ref() {
+            self.input.unblock_signal(handler);
+        }
+    }
+    fn set_on_text_changed(
+        &self,
+        _ctx: &mut Context,
+        callback: Option<Arc<dyn Fn(&str) + Sync + Send>>,
+    ) {
+        *self.text_changed_callback.write() = callback
+    }
+    fn set_on_enter_pressed(
+        &self,
+        _ctx: &mut Context,
+        callback: Option<Arc<dyn Fn(&str) + Sync + Send>>,
+    ) {
+        *self.enter_callback.write() = callback
+    }
+}
+
+/// replace the text of the entry by the formatted text,
+/// the cursor is moved by the characters inserted or removed before it.
+fn format_text(
+    entry: &gtk4::Entry,
+    rules: &InputRules,
+    handler: &RefCell<Option<gtk4::glib::SignalHandlerId>>,
+) {
+    let text = entry.text();
+    let formatted = rules.apply(text.as_str());
+
+    if formatted == text.as_str() {
+        return;
+    }
+
+    let before: String = text
+        .chars()
+        .take(entry.position().max(0) as usize)
+        .collect();
+    let position = rules
+        .apply(&before)
+        .chars()
+        .count()
+        .min(formatted.chars().count());
+
+    // the formatted text is not formatted again
+    let handler = handler.borrow();
+
+    if let Some(handler) = handler.as_ref() {
+        entry.block_signal(handler);
+    }
+
+    entry.set_text(&formatted);
+
+    if let Some(handler) = handler.as_ref() {
+        entry.unblock_signal(handler);
+    }
+
+    entry.set_position(position as i32);
+}
+
+impl NativeFocusableImp for NativeTextInput {
+    fn focus(&self, _ctx: &mut Context) -> bool {
+        self.input.grab_focus()
     }
-    fn set_on_enter_pressed(&self, _ctx: &mut Context, callback: Arc<dyn Fn(&str) + Sync + Send>) {
-        *self.enter_callback.write() = Some(callback)
+    fn blur(&self, _ctx: &mut Context) {
+        blur_widget(self.input.as_ref())
     }
 }
 
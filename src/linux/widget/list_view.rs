@@ -5,6 +5,7 @@ use gtk4::prelude::*;
 
 use crate::native_tree::context::Context;
 use crate::native_tree::{AvalableSpace, MeasuredSize, NativeListViewImp, NativeStyledElement};
+use crate::shadow_tree::render::render_root;
 use crate::style::{
     BorderStyle, Colour, FontStyle, FontWeight, PointEvents, TextAlign, TextDecorationLine,
     TextTransform,
@@ -57,14 +58,6 @@ impl NativeListViewImp for NativeListView {
                     // render the components using factory
                     let mut tree = render.render_item(pos as _, data);
 
-                    // render the component tree
-                    let mut component = loop {
-                        match tree.render() {
-                            Ok(comp) => break comp,
-                            Err(r) => tree = r,
-                        }
-                    };
-
                     let mut ctx = Context::dummy();
 
                     // get the native tree
@@ -80,6 +73,9 @@ impl NativeListViewImp for NativeListView {
                         // get the old shadow tree
                         let old_comp = list_item_widget.take_core_component();
 
+                        // render the component tree
+                        let mut component = render_root(&mut tree, old_comp.as_ref());
+
                         // encode tree into commands
                         let commands = crate::shadow_tree::commit::commit_tree(
                             &mut component,
@@ -108,6 +104,9 @@ impl NativeListViewImp for NativeListView {
                         list_item_widget.set_or_replace_child(widget.widget().as_gtk4_widget());
                         list_item_widget.set_core_component(component);
                     } else {
+                        // render the component tree
+                        let mut component = render_root(&mut tree, None);
+
                         // encode tree into commands
                         let commands =
                             crate::shadow_tree::commit::commit_tree(&mut component, None);
@@ -1,30 +1,119 @@
+pub mod activity_indicator;
 pub mod button;
 pub mod image_view;
 pub mod input;
 pub mod navigator;
+pub mod picker;
+pub mod progress_bar;
 pub mod scroll_view;
+pub mod slider;
 pub mod stack_navigator;
 pub mod text;
 pub mod text_edit;
+pub mod toggle;
 pub mod view;
 
 pub mod list_item_widget;
 pub mod list_model;
 pub mod list_view;
+pub mod modal;
 pub mod paintable;
 
 use std::any::Any;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+use gtk4::prelude::{RootExt, WidgetExt};
+
+use crate::style::Colour;
+
+pub use activity_indicator::NativeActivityIndicator;
 pub use button::NativeButton;
 pub use image_view::NativeImageView;
 pub use input::NativeTextInput;
 pub use list_view::NativeListView;
+pub use modal::NativeModal;
+pub use navigator::NativeNavigator;
+pub use picker::NativePicker;
+pub use progress_bar::NativeProgressBar;
 pub use scroll_view::NativeScrollView;
+pub use slider::NativeSlider;
 pub use stack_navigator::NativeStackNavigator;
 pub use text::NativeText;
 pub use text_edit::NativeTextEdit;
+pub use toggle::NativeToggle;
 pub use view::NativeView;
 
 pub trait NativeElement: Any {
     fn as_gtk4_widget(&self) -> &gtk4::Widget;
 }
+
+/// remove the keyboard focus from the window if it is inside the widget
+pub(crate) fn blur_widget(widget: &gtk4::Widget) {
+    let root = match widget.root() {
+        Some(r) => r,
+        None => return,
+    };
+
+    let focused = root
+        .focus()
+        .map_or(false, |f| &f == widget || f.is_ancestor(widget));
+
+    if focused {
+        root.set_focus(None::<&gtk4::Widget>);
+    }
+}
+
+/// css rules applied to a single widget.
+/// the widget is given a unique css class to scope the rules of the provider.
+pub(crate) struct WidgetCss {
+    provider: gtk4::CssProvider,
+    display: gtk4::gdk::Display,
+    class: String,
+}
+
+impl WidgetCss {
+    pub fn new(widget: &gtk4::Widget) -> Self {
+        static NEXT_CLASS: AtomicUsize = AtomicUsize::new(0);
+
+        let class = format!("ui-native-{}", NEXT_CLASS.fetch_add(1, Ordering::Relaxed));
+        widget.add_css_class(&class);
+
+        let provider = gtk4::CssProvider::new();
+        let display = widget.display();
+
+        gtk4::style_context_add_provider_for_display(
+            &display,
+            &provider,
+            gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION,
+        );
+
+        Self {
+            provider,
+            display,
+            class,
+        }
+    }
+
+    /// replace the rules, `&` in a rule stands for the widget
+    pub fn set_rules(&self, rules: &str) {
+        let selector = format!(".{}", self.class);
+        self.provider.load_from_data(&rules.replace('&', &selector));
+    }
+}
+
+impl Drop for WidgetCss {
+    fn drop(&mut self) {
+        gtk4::style_context_remove_provider_for_display(&self.display, &self.provider);
+    }
+}
+
+/// format a colour as a css value
+pub(crate) fn css_colour(colour: Colour) -> String {
+    format!(
+        "rgba({}, {}, {}, {})",
+        colour.r,
+        colour.g,
+        colour.b,
+        colour.a as f32 / 255.0
+    )
+}
@@ -0,0 +1,186 @@
+use std::cell::Cell;
+use std::sync::Arc;
+
+use gtk4::prelude::*;
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{AvalableSpace, MeasuredSize, NativeModalImp, NativeStyledElement};
+use crate::style::{
+    BorderStyle, Colour, FontStyle, FontWeight, PointEvents, TextAlign, TextDecorationLine,
+    TextTransform,
+};
+
+use super::{css_colour, NativeElement, WidgetCss};
+
+/// colour of the backdrop dimming the window content
+const BACKDROP_COLOUR: &str = "rgba(0, 0, 0, 0.4)";
+
+pub struct NativeModal {
+    /// empty widget holding the place of the modal in its parent
+    placeholder: gtk4::Fixed,
+    /// the layer presented above the window content by the app
+    layer: gtk4::Fixed,
+    css: WidgetCss,
+    transparent: Cell<bool>,
+    background_colour: Cell<Option<Colour>>,
+    on_request_close: Arc<RwLock<Option<Arc<dyn Fn() + Send + Sync>>>>,
+}
+
+impl NativeModal {
+    /// the layer holding the child, shown in an overlay of the window
+    pub fn layer(&self) -> &gtk4::Widget {
+        self.layer.as_ref()
+    }
+
+    fn update_css(&self) {
+        let background = match self.background_colour.get() {
+            Some(colour) => css_colour(colour),
+            None if self.transparent.get() => String::from("transparent"),
+            None => String::from(BACKDROP_COLOUR),
+        };
+
+        self.css
+            .set_rules(&format!("& {{ background-color: {}; }}", background));
+    }
+}
+
+impl NativeModalImp for NativeModal {
+    fn new(_ctx: &mut Context) -> Self {
+        let layer = gtk4::Fixed::new();
+        layer.set_visible(false);
+
+        let css = WidgetCss::new(layer.as_ref());
+
+        let modal = Self {
+            placeholder: gtk4::Fixed::new(),
+            layer,
+            css,
+            transparent: Cell::new(false),
+            background_colour: Cell::new(None),
+            on_request_close: Arc::new(RwLock::new(None)),
+        };
+        modal.update_css();
+
+        return modal;
+    }
+    fn set_visible(&self, _ctx: &mut Context, visible: bool) {
+        self.layer.set_visible(visible);
+    }
+    fn is_visible(&self, _ctx: &mut Context) -> bool {
+        self.layer.is_visible()
+    }
+    fn set_transparent(&self, _ctx: &mut Context, transparent: bool) {
+        self.transparent.set(transparent);
+        self.update_css();
+    }
+    fn set_child(&self, _ctx: &mut Context, child: &dyn NativeElement) {
+        // a layer holds a single child
+        while let Some(c) = self.layer.first_child() {
+            self.layer.remove(&c);
+        }
+
+        self.layer.put(child.as_gtk4_widget(), 0.0, 0.0);
+    }
+    fn remove_child(&self, _ctx: &mut Context) {
+        while let Some(c) = self.layer.first_child() {
+            self.layer.remove(&c);
+        }
+    }
+    fn layout_child(
+        &self,
+        _ctx: &mut Context,
+        child: &dyn NativeElement,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    ) {
+        let w = child.as_gtk4_widget();
+
+        self.layer.move_(w, x as f64, y as f64);
+        w.set_width_request(width as i32);
+        w.set_height_request(height as i32);
+    }
+    fn set_on_request_close(
+        &self,
+        _ctx: &mut Context,
+        on_request_close: Option<Arc<dyn Fn() + Send + Sync>>,
+    ) {
+        *self.on_request_close.write() = on_request_close;
+    }
+    fn request_close(&self, _ctx: &mut Context) {
+        let callback = self.on_request_close.read().clone();
+
+        if let Some(cb) = callback {
+            cb();
+        }
+    }
+}
+
+impl NativeElement for NativeModal {
+    fn as_gtk4_widget(&self) -> &gtk4::Widget {
+        self.placeholder.as_ref()
+    }
+}
+
+impl NativeStyledElement for NativeModal {
+    fn measure(
+        &self,
+        _ctx: &mut Context,
+        _known_width: AvalableSpace,
+        _known_height: AvalableSpace,
+    ) -> anyhow::Result<MeasuredSize> {
+        // the placeholder takes no space in the parent
+        return Ok(MeasuredSize {
+            min_width: 0.0,
+            natural_width: 0.0,
+            min_height: 0.0,
+            natural_height: 0.0,
+        });
+    }
+    fn set_visible(&self, _ctx: &mut Context, _visible: bool) {}
+    fn set_backface_visible(&self, _visible: bool) {}
+
+    fn set_colour(&self, _colour: Colour) {}
+    fn set_background_colour(&self, colour: Colour) {
+        self.background_colour.set(Some(colour));
+        self.update_css();
+    }
+
+    fn set_border_top_width(&self, _width: f32) {}
+    fn set_border_bottom_width(&self, _width: f32) {}
+    fn set_border_left_width(&self, _width: f32) {}
+    fn set_border_right_width(&self, _width: f32) {}
+
+    fn set_border_top_left_radius(&self, _radius: f32) {}
+    fn set_border_top_right_radius(&self, _radius: f32) {}
+    fn set_border_bottom_left_radius(&self, _radius: f32) {}
+    fn set_border_bottom_right_radius(&self, _radius: f32) {}
+
+    fn set_border_top_colour(&self, _colour: Colour) {}
+    fn set_border_bottom_colour(&self, _colour: Colour) {}
+    fn set_border_left_colour(&self, _colour: Colour) {}
+    fn set_border_right_colour(&self, _colour: Colour) {}
+
+    fn set_border_style(&self, _style: BorderStyle) {}
+
+    fn set_opacity(&self, opacity: f32) {
+        self.layer.set_opacity(opacity as f64);
+    }
+    fn set_points_event(&self, _event: PointEvents) {}
+
+    fn set_font_size(&self, _size: f32) {}
+    fn set_font_style(&self, _style: FontStyle) {}
+    fn set_font_weight(&self, _weight: FontWeight) {}
+
+    fn set_letter_spacing(&self, _spacing: f32) {}
+    fn set_line_height(&self, _height: f32) {}
+
+    fn set_text_align(&self, _align: TextAlign) {}
+    fn set_text_decloration_line(&self, _line: TextDecorationLine) {}
+    fn set_text_decloration_colour(&self, _colour: Colour) {}
+    fn set_text_shadow_colour(&self, _colour: Colour) {}
+    fn set_text_shadow_radius(&self, _radius: f32) {}
+    fn set_text_transform(&self, _transform: TextTransform) {}
+}
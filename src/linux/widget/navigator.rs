@@ -1,5 +1,6 @@
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crossbeam_channel::{Receiver, TryRecvError};
@@ -11,22 +12,103 @@ use crate::native_tree::{AvalableSpace, NativeNavigatorImp, NativeStyledElement}
 use crate::shadow_tree::component::NavigatorCommand;
 use crate::shadow_tree::NodeID;
 use crate::style::*;
+use crate::widget::tab_navigator::{Tab, TabBarPosition};
 
 use super::NativeElement;
 
 struct NavigatorState {
     dropped: bool,
+    tabs: Vec<Tab>,
+    tab_bar_position: TabBarPosition,
+    /// none until a tab is selected, the first tab is shown
+    selected: Option<String>,
+    /// previously selected tabs, the last is returned to on goback
+    history: Vec<String>,
     children: Vec<(String, NodeID)>,
+    /// a toggle button for each tab
+    buttons: Vec<gtk4::ToggleButton>,
+    on_tab_change: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+}
+
+impl NavigatorState {
+    fn selected(&self) -> Option<String> {
+        if let Some(name) = &self.selected {
+            return Some(name.clone());
+        }
+
+        return self.tabs.first().map(|t| t.name.clone());
+    }
+
+    /// select the tab, returns true if the selected tab changed
+    fn select(&mut self, name: &str) -> bool {
+        if !self.tabs.iter().any(|t| t.name == name) {
+            return false;
+        }
+
+        let previous = self.selected();
+
+        if previous.as_deref() == Some(name) {
+            return false;
+        }
+
+        if let Some(previous) = previous {
+            self.history.push(previous);
+        }
+
+        self.selected = Some(name.to_string());
+
+        return true;
+    }
+
+    /// select the previous tab, returns its name
+    fn back(&mut self) -> Option<String> {
+        let name = self.history.pop()?;
+
+        self.selected = Some(name.clone());
+
+        return Some(name);
+    }
+
+    /// show the page of the selected tab and activate its button
+    fn show_selected(&self, stack: &gtk4::Stack) {
+        let selected = match self.selected() {
+            Some(name) => name,
+            None => return,
+        };
+
+        if self.children.iter().any(|(n, _)| n == &selected) {
+            stack.set_visible_child_name(&selected);
+        }
+
+        for (tab, button) in self.tabs.iter().zip(self.buttons.iter()) {
+            button.set_active(tab.name == selected);
+        }
+    }
+}
+
+/// the selected tab changed, called without the state borrowed
+fn tab_changed(state: &Rc<RefCell<NavigatorState>>, name: &str) {
+    // visible page changes, layout must be recomputed
+    crate::state::mark_dirty();
+
+    let callback = state.borrow().on_tab_change.clone();
+
+    if let Some(cb) = callback {
+        cb(name);
+    }
 }
 
 pub struct NativeNavigator {
+    /// the tab bar and the stack of pages
+    root: gtk4::Box,
+    bar: gtk4::Box,
     stack: gtk4::Stack,
     state: Rc<RefCell<NavigatorState>>,
 }
 
 impl NativeElement for NativeNavigator {
     fn as_gtk4_widget(&self) -> &gtk4::Widget {
-        self.stack.as_ref()
+        self.root.as_ref()
     }
 }
 
@@ -35,7 +117,13 @@ impl NativeNavigatorImp for NativeNavigator {
         // constuct the state
         let state = Rc::new(RefCell::new(NavigatorState {
             dropped: false,
+            tabs: Vec::new(),
+            tab_bar_position: TabBarPosition::default(),
+            selected: None,
+            history: Vec::new(),
             children: Vec::new(),
+            buttons: Vec::new(),
+            on_tab_change: None,
         }));
         let state1 = state.clone();
 
@@ -45,38 +133,71 @@ impl NativeNavigatorImp for NativeNavigator {
         stack.set_overflow(gtk4::Overflow::Hidden);
         stack.set_hhomogeneous(true);
         stack.set_vhomogeneous(true);
+        stack.set_vexpand(true);
 
         stack.set_transition_type(gtk4::StackTransitionType::SlideLeftRight);
 
+        let bar = gtk4::Box::new(gtk4::Orientation::Horizontal, 0);
+        bar.set_homogeneous(true);
+        bar.add_css_class("toolbar");
+
+        // the bar is at the bottom by default
+        let root = gtk4::Box::new(gtk4::Orientation::Vertical, 0);
+        root.append(&stack);
+        root.append(&bar);
+
         // check navigator actions at regular intervals
         glib::timeout_add_local(Duration::from_millis(5), move || {
-            let mut state = state1.borrow_mut();
-
-            loop {
-                match command_recv.try_recv() {
-                    Ok(cmd) => match cmd {
-                        NavigatorCommand::Goto(name) => {
-                            // find the id of page
-                            if let Some(_) = state.children.iter().find(|(n, _p)| n == &name) {
-                                // set the page
-                                stack1.set_visible_child_name(&name);
+            let mut changes = Vec::new();
+            let mut dropped = false;
+
+            {
+                let mut state = state1.borrow_mut();
+
+                loop {
+                    match command_recv.try_recv() {
+                        Ok(NavigatorCommand::Goback) => {
+                            if let Some(name) = state.back() {
+                                changes.push(name);
+                            }
+                        }
+                        // tabs are not stacked, pushing a tab selects it
+                        Ok(NavigatorCommand::Push(name))
+                        | Ok(NavigatorCommand::PushWithTransition(name, _))
+                        | Ok(NavigatorCommand::Goto(name)) => {
+                            if state.select(&name) {
+                                changes.push(name);
                             }
                         }
-                        _ => unimplemented!(),
-                    },
-                    Err(TryRecvError::Empty) => break,
-                    Err(TryRecvError::Disconnected) => {
-                        state.dropped = true;
-                        return glib::ControlFlow::Break;
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => {
+                            state.dropped = true;
+                            dropped = true;
+                            break;
+                        }
                     }
                 }
+
+                if !changes.is_empty() {
+                    state.show_selected(&stack1);
+                }
+            }
+
+            for name in changes {
+                tab_changed(&state1, &name);
+            }
+
+            if dropped {
+                return glib::ControlFlow::Break;
             }
 
             return glib::ControlFlow::Continue;
         });
 
         Self {
-            stack: stack,
+            root,
+            bar,
+            stack,
             state,
         }
     }
@@ -91,13 +212,10 @@ impl NativeNavigatorImp for NativeNavigator {
         // push page
         state.children.push((name.to_string(), id));
 
-        // if no child is visible, set as the first child
-        if self.stack.visible_child().is_none() {
-            if let Some((name, _)) = state.children.first() {
-                // set the page
-                self.stack
-                    .set_visible_child_full(name, gtk4::StackTransitionType::None);
-            }
+        // the page may belong to the selected tab
+        if state.selected().as_deref() == Some(name) {
+            self.stack
+                .set_visible_child_full(name, gtk4::StackTransitionType::None);
         }
     }
 
@@ -107,19 +225,14 @@ impl NativeNavigatorImp for NativeNavigator {
 
             let mut state = self.state.borrow_mut();
 
-            for (i, (n, _id)) in state.children.iter().enumerate() {
-                if &name == n {
-                    state.children.remove(i);
-                    break;
-                }
-            }
+            state.children.retain(|(n, _)| n != name);
         }
     }
 
     fn visible_child(&self, _ctx: &mut Context) -> Option<NodeID> {
         let state = self.state.borrow();
 
-        let name = self.stack.visible_child_name()?;
+        let name = state.selected()?;
 
         if let Some((_, id)) = state.children.iter().find(|(n, _)| n == &name) {
             return Some(*id);
@@ -147,6 +260,90 @@ impl NativeNavigatorImp for NativeNavigator {
         w.set_width_request(width as i32);
         w.set_height_request(height as i32);
     }
+
+    fn set_tabs(&self, _ctx: &mut Context, tabs: &[Tab]) {
+        let mut state = self.state.borrow_mut();
+
+        // rebuild the tab bar
+        for button in state.buttons.drain(..) {
+            self.bar.remove(&button);
+        }
+
+        for tab in tabs {
+            let content = gtk4::Box::new(gtk4::Orientation::Vertical, 2);
+
+            if let Some(icon) = &tab.icon {
+                content.append(&gtk4::Image::from_icon_name(icon));
+            }
+
+            content.append(&gtk4::Label::new(Some(&tab.label)));
+
+            let button = gtk4::ToggleButton::new();
+            button.set_child(Some(&content));
+            button.add_css_class("flat");
+
+            // only one tab is active at a time
+            if let Some(first) = state.buttons.first() {
+                button.set_group(Some(first));
+            }
+
+            let state1 = self.state.clone();
+            let stack = self.stack.clone();
+            let name = tab.name.clone();
+
+            button.connect_clicked(move |_| {
+                let changed = {
+                    let mut state = state1.borrow_mut();
+                    let changed = state.select(&name);
+                    state.show_selected(&stack);
+                    changed
+                };
+
+                if changed {
+                    tab_changed(&state1, &name);
+                }
+            });
+
+            self.bar.append(&button);
+            state.buttons.push(button);
+        }
+
+        state.tabs = tabs.to_vec();
+        state.show_selected(&self.stack);
+    }
+
+    fn set_tab_bar_position(&self, _ctx: &mut Context, position: TabBarPosition) {
+        match position {
+            TabBarPosition::Top => self
+                .root
+                .reorder_child_after(&self.bar, None::<&gtk4::Widget>),
+            TabBarPosition::Bottom => self.root.reorder_child_after(&self.bar, Some(&self.stack)),
+        }
+
+        self.state.borrow_mut().tab_bar_position = position;
+    }
+
+    fn tab_bar_position(&self, _ctx: &mut Context) -> TabBarPosition {
+        self.state.borrow().tab_bar_position
+    }
+
+    fn tab_bar_height(&self, _ctx: &mut Context) -> f32 {
+        let (_, natural_height, _, _) = self.bar.measure(gtk4::Orientation::Vertical, -1);
+
+        return natural_height as f32;
+    }
+
+    fn set_on_tab_change(
+        &self,
+        _ctx: &mut Context,
+        on_tab_change: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+    ) {
+        self.state.borrow_mut().on_tab_change = on_tab_change;
+    }
+
+    fn check_update(&self) {
+        // commands are polled by the glib timeout
+    }
 }
 
 impl NativeStyledElement for NativeNavigator {
@@ -157,7 +354,7 @@ impl NativeStyledElement for NativeNavigator {
         known_height: AvalableSpace,
     ) -> anyhow::Result<MeasuredSize> {
         // measure width
-        let (min_width, natural_width, _, _) = self.stack.measure(
+        let (min_width, natural_width, _, _) = self.root.measure(
             gtk4::Orientation::Horizontal,
             match known_height {
                 AvalableSpace::AtMost(f) => f as i32,
@@ -166,7 +363,7 @@ impl NativeStyledElement for NativeNavigator {
             },
         );
         // measure height
-        let (min_height, natural_height, _, _) = self.stack.measure(
+        let (min_height, natural_height, _, _) = self.root.measure(
             gtk4::Orientation::Vertical,
             match known_width {
                 AvalableSpace::AtMost(f) => f as i32,
@@ -183,7 +380,7 @@ impl NativeStyledElement for NativeNavigator {
         });
     }
     fn set_visible(&self, _ctx: &mut Context, visible: bool) {
-        self.stack.set_visible(visible)
+        self.root.set_visible(visible)
     }
     fn set_backface_visible(&self, _visible: bool) {}
 
@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use gtk4::prelude::*;
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{
+    AvalableSpace, MeasuredSize, NativeFocusableImp, NativePickerImp, NativeStyledElement,
+};
+use crate::style::{
+    BorderStyle, Colour, FontStyle, FontWeight, PointEvents, TextAlign, TextDecorationLine,
+    TextTransform,
+};
+
+use super::{blur_widget, NativeElement};
+
+pub struct NativePicker {
+    dropdown: gtk4::DropDown,
+    options: gtk4::StringList,
+    /// the handler of the selected notify signal, blocked while the selection is set programmatically
+    selected_handler: gtk4::glib::SignalHandlerId,
+    callback: Arc<RwLock<Option<Arc<dyn Fn(usize) + Send + Sync>>>>,
+}
+
+impl NativePickerImp for NativePicker {
+    fn new(_ctx: &mut Context) -> Self {
+        let options = gtk4::StringList::new(&[]);
+        let dropdown = gtk4::DropDown::new(Some(options.clone()), None::<gtk4::Expression>);
+
+        let callback: Arc<RwLock<Option<Arc<dyn Fn(usize) + Send + Sync>>>> =
+            Arc::new(RwLock::new(None));
+        let cb = callback.clone();
+
+        let selected_handler = dropdown.connect_selected_notify(move |dropdown| {
+            let selected = dropdown.selected();
+
+            if selected == gtk4::INVALID_LIST_POSITION {
+                return;
+            }
+
+            let callback = cb.read().clone();
+
+            if let Some(cb) = callback {
+                cb(selected as usize);
+            }
+
+            // the picker is reset to the selected prop if the callback rejected the option
+            crate::state::mark_dirty();
+        });
+
+        Self {
+            dropdown,
+            options,
+            selected_handler,
+            callback,
+        }
+    }
+
+    fn splice_options(
+        &self,
+        _ctx: &mut Context,
+        position: usize,
+        removed: usize,
+        added: &[String],
+    ) {
+        let added: Vec<&str> = added.iter().map(|s| s.as_str()).collect();
+
+        // the dropdown moves the selection when the options change
+        self.dropdown.block_signal(&self.selected_handler);
+        self.options.splice(position as u32, removed as u32, &added);
+        self.dropdown.unblock_signal(&self.selected_handler);
+    }
+
+    fn set_selected(&self, _ctx: &mut Context, selected: Option<usize>) {
+        let selected = match selected {
+            Some(i) if (i as u32) < self.options.n_items() => i as u32,
+            _ => gtk4::INVALID_LIST_POSITION,
+        };
+
+        if self.dropdown.selected() == selected {
+            return;
+        }
+
+        self.dropdown.block_signal(&self.selected_handler);
+        self.dropdown.set_selected(selected);
+        self.dropdown.unblock_signal(&self.selected_handler);
+    }
+
+    fn set_on_select(
+        &self,
+        _ctx: &mut Context,
+        on_select: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    ) {
+        *self.callback.write() = on_select;
+    }
+}
+
+impl NativeFocusableImp for NativePicker {
+    fn focus(&self, _ctx: &mut Context) -> bool {
+        self.dropdown.grab_focus()
+    }
+    fn blur(&self, _ctx: &mut Context) {
+        blur_widget(self.dropdown.as_ref())
+    }
+}
+
+impl NativeElement for NativePicker {
+    fn as_gtk4_widget(&self) -> &gtk4::Widget {
+        self.dropdown.as_ref()
+    }
+}
+
+impl NativeStyledElement for NativePicker {
+    fn measure(
+        &self,
+        _ctx: &mut Context,
+        known_width: AvalableSpace,
+        known_height: AvalableSpace,
+    ) -> anyhow::Result<MeasuredSize> {
+        // measure width
+        let (min_width, natural_width, _, _) = self.dropdown.measure(
+            gtk4::Orientation::Horizontal,
+            match known_height {
+                AvalableSpace::AtMost(f) => f as i32,
+                AvalableSpace::Exact(f) => f as i32,
+                AvalableSpace::Unknown => -1,
+            },
+        );
+        // measure height
+        let (min_height, natural_height, _, _) = self.dropdown.measure(
+            gtk4::Orientation::Vertical,
+            match known_width {
+                AvalableSpace::AtMost(f) => f as i32,
+                AvalableSpace::Exact(f) => f as i32,
+                AvalableSpace::Unknown => -1,
+            },
+        );
+
+        return Ok(MeasuredSize {
+            min_width: min_width as f32,
+            natural_width: natural_width as f32,
+            min_height: min_height as f32,
+            natural_height: natural_height as f32,
+        });
+    }
+    fn set_visible(&self, _ctx: &mut Context, visible: bool) {
+        self.dropdown.set_visible(visible)
+    }
+    fn set_backface_visible(&self, _visible: bool) {}
+
+    fn set_colour(&self, _colour: Colour) {}
+    fn set_background_colour(&self, _colour: Colour) {}
+
+    fn set_border_top_width(&self, _width: f32) {}
+    fn set_border_bottom_width(&self, _width: f32) {}
+    fn set_border_left_width(&self, _width: f32) {}
+    fn set_border_right_width(&self, _width: f32) {}
+
+    fn set_border_top_left_radius(&self, _radius: f32) {}
+    fn set_border_top_right_radius(&self, _radius: f32) {}
+    fn set_border_bottom_left_radius(&self, _radius: f32) {}
+    fn set_border_bottom_right_radius(&self, _radius: f32) {}
+
+    fn set_border_top_colour(&self, _colour: Colour) {}
+    fn set_border_bottom_colour(&self, _colour: Colour) {}
+    fn set_border_left_colour(&self, _colour: Colour) {}
+    fn set_border_right_colour(&self, _colour: Colour) {}
+
+    fn set_border_style(&self, _style: BorderStyle) {}
+
+    fn set_opacity(&self, opacity: f32) {
+        self.dropdown.set_opacity(opacity as f64);
+    }
+    fn set_points_event(&self, _event: PointEvents) {}
+
+    fn set_font_size(&self, _size: f32) {}
+    fn set_font_style(&self, _style: FontStyle) {}
+    fn set_font_weight(&self, _weight: FontWeight) {}
+
+    fn set_letter_spacing(&self, _spacing: f32) {}
+    fn set_line_height(&self, _height: f32) {}
+
+    fn set_text_align(&self, _align: TextAlign) {}
+    fn set_text_decloration_line(&self, _line: TextDecorationLine) {}
+    fn set_text_decloration_colour(&self, _colour: Colour) {}
+    fn set_text_shadow_colour(&self, _colour: Colour) {}
+    fn set_text_shadow_radius(&self, _radius: f32) {}
+    fn set_text_transform(&self, _transform: TextTransform) {}
+}
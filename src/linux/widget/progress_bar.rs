@@ -0,0 +1,210 @@
+use std::cell::{Cell, RefCell};
+use std::time::Duration;
+
+use gtk4::prelude::*;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{AvalableSpace, MeasuredSize, NativeProgressBarImp, NativeStyledElement};
+use crate::style::{
+    BorderStyle, Colour, FontStyle, FontWeight, PointEvents, TextAlign, TextDecorationLine,
+    TextTransform,
+};
+
+use super::{css_colour, NativeElement, WidgetCss};
+
+/// interval between two pulses of an indeterminate bar
+const PULSE_INTERVAL: Duration = Duration::from_millis(100);
+
+pub struct NativeProgressBar {
+    bar: gtk4::ProgressBar,
+    css: WidgetCss,
+    /// the fraction shown once the bar is no longer indeterminate
+    fraction: Cell<f64>,
+    colour: Cell<Option<Colour>>,
+    background_colour: Cell<Option<Colour>>,
+    /// the timer pulsing the bar while indeterminate
+    pulse: RefCell<Option<gtk4::glib::SourceId>>,
+}
+
+impl NativeProgressBar {
+    fn update_css(&self) {
+        let mut rules = String::new();
+
+        if let Some(colour) = self.colour.get() {
+            let colour = css_colour(colour);
+            rules += &format!(
+                "& > trough > progress {{ background-color: {0}; border-color: {0}; }}\n",
+                colour
+            );
+            rules += &format!("& > text {{ color: {}; }}\n", colour);
+        }
+
+        if let Some(colour) = self.background_colour.get() {
+            rules += &format!(
+                "& > trough {{ background-color: {}; }}\n",
+                css_colour(colour)
+            );
+        }
+
+        self.css.set_rules(&rules);
+    }
+
+    fn stop_pulse(&self) {
+        if let Some(source) = self.pulse.borrow_mut().take() {
+            source.remove();
+        }
+    }
+}
+
+impl Drop for NativeProgressBar {
+    fn drop(&mut self) {
+        self.stop_pulse();
+    }
+}
+
+impl NativeProgressBarImp for NativeProgressBar {
+    fn new(_ctx: &mut Context) -> Self {
+        let bar = gtk4::ProgressBar::new();
+        bar.set_valign(gtk4::Align::Center);
+
+        let css = WidgetCss::new(bar.as_ref());
+
+        Self {
+            bar,
+            css,
+            fraction: Cell::new(0.0),
+            colour: Cell::new(None),
+            background_colour: Cell::new(None),
+            pulse: RefCell::new(None),
+        }
+    }
+
+    fn set_fraction(&self, _ctx: &mut Context, fraction: f64) {
+        self.fraction.set(fraction);
+
+        // an indeterminate bar keeps pulsing
+        if self.pulse.borrow().is_none() {
+            self.bar.set_fraction(fraction);
+        }
+    }
+
+    fn set_indeterminate(&self, _ctx: &mut Context, indeterminate: bool) {
+        if indeterminate == self.pulse.borrow().is_some() {
+            return;
+        }
+
+        if !indeterminate {
+            self.stop_pulse();
+            // pulsing has moved the bar
+            self.bar.set_fraction(self.fraction.get());
+            return;
+        }
+
+        // the timer stops once the bar is dropped
+        let bar = self.bar.downgrade();
+        let source = gtk4::glib::timeout_add_local(PULSE_INTERVAL, move || match bar.upgrade() {
+            Some(bar) => {
+                bar.pulse();
+                gtk4::glib::ControlFlow::Continue
+            }
+            None => gtk4::glib::ControlFlow::Break,
+        });
+
+        *self.pulse.borrow_mut() = Some(source);
+    }
+
+    fn set_text(&self, _ctx: &mut Context, text: Option<&str>) {
+        self.bar.set_text(text);
+        self.bar.set_show_text(text.is_some());
+    }
+}
+
+impl NativeElement for NativeProgressBar {
+    fn as_gtk4_widget(&self) -> &gtk4::Widget {
+        self.bar.as_ref()
+    }
+}
+
+impl NativeStyledElement for NativeProgressBar {
+    fn measure(
+        &self,
+        _ctx: &mut Context,
+        known_width: AvalableSpace,
+        known_height: AvalableSpace,
+    ) -> anyhow::Result<MeasuredSize> {
+        // measure width
+        let (min_width, natural_width, _, _) = self.bar.measure(
+            gtk4::Orientation::Horizontal,
+            match known_height {
+                AvalableSpace::AtMost(f) => f as i32,
+                AvalableSpace::Exact(f) => f as i32,
+                AvalableSpace::Unknown => -1,
+            },
+        );
+        // measure height
+        let (min_height, natural_height, _, _) = self.bar.measure(
+            gtk4::Orientation::Vertical,
+            match known_width {
+                AvalableSpace::AtMost(f) => f as i32,
+                AvalableSpace::Exact(f) => f as i32,
+                AvalableSpace::Unknown => -1,
+            },
+        );
+
+        return Ok(MeasuredSize {
+            min_width: min_width as f32,
+            natural_width: natural_width as f32,
+            min_height: min_height as f32,
+            natural_height: natural_height as f32,
+        });
+    }
+    fn set_visible(&self, _ctx: &mut Context, visible: bool) {
+        self.bar.set_visible(visible)
+    }
+    fn set_backface_visible(&self, _visible: bool) {}
+
+    fn set_colour(&self, colour: Colour) {
+        self.colour.set(Some(colour));
+        self.update_css();
+    }
+    fn set_background_colour(&self, colour: Colour) {
+        self.background_colour.set(Some(colour));
+        self.update_css();
+    }
+
+    fn set_border_top_width(&self, _width: f32) {}
+    fn set_border_bottom_width(&self, _width: f32) {}
+    fn set_border_left_width(&self, _width: f32) {}
+    fn set_border_right_width(&self, _width: f32) {}
+
+    fn set_border_top_left_radius(&self, _radius: f32) {}
+    fn set_border_top_right_radius(&self, _radius: f32) {}
+    fn set_border_bottom_left_radius(&self, _radius: f32) {}
+    fn set_border_bottom_right_radius(&self, _radius: f32) {}
+
+    fn set_border_top_colour(&self, _colour: Colour) {}
+    fn set_border_bottom_colour(&self, _colour: Colour) {}
+    fn set_border_left_colour(&self, _colour: Colour) {}
+    fn set_border_right_colour(&self, _colour: Colour) {}
+
+    fn set_border_style(&self, _style: BorderStyle) {}
+
+    fn set_opacity(&self, opacity: f32) {
+        self.bar.set_opacity(opacity as f64);
+    }
+    fn set_points_event(&self, _event: PointEvents) {}
+
+    fn set_font_size(&self, _size: f32) {}
+    fn set_font_style(&self, _style: FontStyle) {}
+    fn set_font_weight(&self, _weight: FontWeight) {}
+
+    fn set_letter_spacing(&self, _spacing: f32) {}
+    fn set_line_height(&self, _height: f32) {}
+
+    fn set_text_align(&self, _align: TextAlign) {}
+    fn set_text_decloration_line(&self, _line: TextDecorationLine) {}
+    fn set_text_decloration_colour(&self, _colour: Colour) {}
+    fn set_text_shadow_colour(&self, _colour: Colour) {}
+    fn set_text_shadow_radius(&self, _radius: f32) {}
+    fn set_text_transform(&self, _transform: TextTransform) {}
+}
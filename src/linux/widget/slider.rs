@@ -0,0 +1,270 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use gtk4::prelude::*;
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{
+    AvalableSpace, MeasuredSize, NativeFocusableImp, NativeSliderImp, NativeStyledElement,
+};
+use crate::style::{
+    BorderStyle, Colour, FontStyle, FontWeight, PointEvents, TextAlign, TextDecorationLine,
+    TextTransform,
+};
+use crate::widget::slider::{snap_value, Orientation};
+
+use super::{blur_widget, NativeElement};
+
+pub struct NativeSlider {
+    scale: gtk4::Scale,
+    /// zero for a continuous slider
+    step: Rc<Cell<f64>>,
+    /// the handler of the value changed signal, blocked while the value is set programmatically
+    value_handler: gtk4::glib::SignalHandlerId,
+    on_change: Arc<RwLock<Option<Arc<dyn Fn(f64) + Send + Sync>>>>,
+    on_release: Arc<RwLock<Option<Arc<dyn Fn(f64) + Send + Sync>>>>,
+}
+
+impl NativeSliderImp for NativeSlider {
+    fn new(_ctx: &mut Context) -> Self {
+        let adjustment = gtk4::Adjustment::new(0.0, 0.0, 1.0, 0.01, 0.1, 0.0);
+        let scale = gtk4::Scale::new(gtk4::Orientation::Horizontal, Some(&adjustment));
+        scale.set_draw_value(false);
+
+        let step = Rc::new(Cell::new(0.0));
+
+        let on_change: Arc<RwLock<Option<Arc<dyn Fn(f64) + Send + Sync>>>> =
+            Arc::new(RwLock::new(None));
+        let on_release: Arc<RwLock<Option<Arc<dyn Fn(f64) + Send + Sync>>>> =
+            Arc::new(RwLock::new(None));
+
+        // true if the user has moved the slider since it was last released
+        let moved = Rc::new(Cell::new(false));
+
+        let s = step.clone();
+        let m = moved.clone();
+        let cb = on_change.clone();
+        let value_handler = scale.connect_value_changed(move |scale| {
+            let adjustment = scale.adjustment();
+            let value = scale.value();
+            let snapped = snap_value(value, adjustment.lower(), adjustment.upper(), s.get());
+
+            // the nested emission reports the snapped value
+            if snapped != value {
+                scale.set_value(snapped);
+                return;
+            }
+
+            m.set(true);
+
+            let callback = cb.read().clone();
+
+            if let Some(cb) = callback {
+                cb(value);
+            }
+
+            // the slider is reset to the value prop if the callback rejected the value
+            crate::state::mark_dirty();
+        });
+
+        // the release is seen before the scale handles the event
+        let legacy = gtk4::EventControllerLegacy::new();
+        legacy.set_propagation_phase(gtk4::PropagationPhase::Capture);
+
+        let sc = scale.clone();
+        let cb = on_release.clone();
+        legacy.connect_event(move |_, event| {
+            let released = match event.event_type() {
+                gtk4::gdk::EventType::ButtonRelease | gtk4::gdk::EventType::TouchEnd => true,
+                // keyboard changes are released once the key is up
+                gtk4::gdk::EventType::KeyRelease => moved.get(),
+                _ => false,
+            };
+
+            if released {
+                moved.set(false);
+
+                let r = cb.read();
+
+                if let Some(cb) = r.as_ref() {
+                    cb(sc.value());
+                }
+            }
+
+            return gtk4::glib::Propagation::Proceed;
+        });
+        scale.add_controller(legacy);
+
+        Self {
+            scale,
+            step,
+            value_handler,
+            on_change,
+            on_release,
+        }
+    }
+
+    fn set_range(&self, _ctx: &mut Context, min: f64, max: f64) {
+        let (low, high) = if min <= max { (min, max) } else { (max, min) };
+
+        // the value is clamped to the new range without notifying
+        self.scale.block_signal(&self.value_handler);
+        self.scale.set_range(low, high);
+        self.scale.unblock_signal(&self.value_handler);
+
+        self.update_increments();
+    }
+
+    fn set_step(&self, _ctx: &mut Context, step: f64) {
+        self.step.set(step);
+        self.update_increments();
+    }
+
+    fn set_value(&self, _ctx: &mut Context, value: f64) {
+        let adjustment = self.scale.adjustment();
+        let value = snap_value(
+            value,
+            adjustment.lower(),
+            adjustment.upper(),
+            self.step.get(),
+        );
+
+        if self.scale.value() == value {
+            return;
+        }
+
+        self.scale.block_signal(&self.value_handler);
+        self.scale.set_value(value);
+        self.scale.unblock_signal(&self.value_handler);
+    }
+
+    fn set_orientation(&self, _ctx: &mut Context, orientation: Orientation) {
+        self.scale.set_orientation(match orientation {
+            Orientation::Horizontal => gtk4::Orientation::Horizontal,
+            Orientation::Vertical => gtk4::Orientation::Vertical,
+        });
+        // vertical sliders have the minimum at the bottom
+        self.scale
+            .set_inverted(orientation == Orientation::Vertical);
+    }
+
+    fn set_on_change(&self, _ctx: &mut Context, on_change: Option<Arc<dyn Fn(f64) + Send + Sync>>) {
+        *self.on_change.write() = on_change;
+    }
+
+    fn set_on_release(
+        &self,
+        _ctx: &mut Context,
+        on_release: Option<Arc<dyn Fn(f64) + Send + Sync>>,
+    ) {
+        *self.on_release.write() = on_release;
+    }
+}
+
+impl NativeSlider {
+    /// the arrow keys move by a step, or by a hundredth of the range if continuous
+    fn update_increments(&self) {
+        let adjustment = self.scale.adjustment();
+        let step = match self.step.get() {
+            s if s > 0.0 => s,
+            _ => (adjustment.upper() - adjustment.lower()) / 100.0,
+        };
+
+        self.scale.set_increments(step, step * 10.0);
+    }
+}
+
+impl NativeFocusableImp for NativeSlider {
+    fn focus(&self, _ctx: &mut Context) -> bool {
+        self.scale.grab_focus()
+    }
+    fn blur(&self, _ctx: &mut Context) {
+        blur_widget(self.scale.as_ref())
+    }
+}
+
+impl NativeElement for NativeSlider {
+    fn as_gtk4_widget(&self) -> &gtk4::Widget {
+        self.scale.as_ref()
+    }
+}
+
+impl NativeStyledElement for NativeSlider {
+    fn measure(
+        &self,
+        _ctx: &mut Context,
+        known_width: AvalableSpace,
+        known_height: AvalableSpace,
+    ) -> anyhow::Result<MeasuredSize> {
+        // measure width
+        let (min_width, natural_width, _, _) = self.scale.measure(
+            gtk4::Orientation::Horizontal,
+            match known_height {
+                AvalableSpace::AtMost(f) => f as i32,
+                AvalableSpace::Exact(f) => f as i32,
+                AvalableSpace::Unknown => -1,
+            },
+        );
+        // measure height
+        let (min_height, natural_height, _, _) = self.scale.measure(
+            gtk4::Orientation::Vertical,
+            match known_width {
+                AvalableSpace::AtMost(f) => f as i32,
+                AvalableSpace::Exact(f) => f as i32,
+                AvalableSpace::Unknown => -1,
+            },
+        );
+
+        return Ok(MeasuredSize {
+            min_width: min_width as f32,
+            natural_width: natural_width as f32,
+            min_height: min_height as f32,
+            natural_height: natural_height as f32,
+        });
+    }
+    fn set_visible(&self, _ctx: &mut Context, visible: bool) {
+        self.scale.set_visible(visible)
+    }
+    fn set_backface_visible(&self, _visible: bool) {}
+
+    fn set_colour(&self, _colour: Colour) {}
+    fn set_background_colour(&self, _colour: Colour) {}
+
+    fn set_border_top_width(&self, _width: f32) {}
+    fn set_border_bottom_width(&self, _width: f32) {}
+    fn set_border_left_width(&self, _width: f32) {}
+    fn set_border_right_width(&self, _width: f32) {}
+
+    fn set_border_top_left_radius(&self, _radius: f32) {}
+    fn set_border_top_right_radius(&self, _radius: f32) {}
+    fn set_border_bottom_left_radius(&self, _radius: f32) {}
+    fn set_border_bottom_right_radius(&self, _radius: f32) {}
+
+    fn set_border_top_colour(&self, _colour: Colour) {}
+    fn set_border_bottom_colour(&self, _colour: Colour) {}
+    fn set_border_left_colour(&self, _colour: Colour) {}
+    fn set_border_right_colour(&self, _colour: Colour) {}
+
+    fn set_border_style(&self, _style: BorderStyle) {}
+
+    fn set_opacity(&self, opacity: f32) {
+        self.scale.set_opacity(opacity as f64);
+    }
+    fn set_points_event(&self, _event: PointEvents) {}
+
+    fn set_font_size(&self, _size: f32) {}
+    fn set_font_style(&self, _style: FontStyle) {}
+    fn set_font_weight(&self, _weight: FontWeight) {}
+
+    fn set_letter_spacing(&self, _spacing: f32) {}
+    fn set_line_height(&self, _height: f32) {}
+
+    fn set_text_align(&self, _align: TextAlign) {}
+    fn set_text_decloration_line(&self, _line: TextDecorationLine) {}
+    fn set_text_decloration_colour(&self, _colour: Colour) {}
+    fn set_text_shadow_colour(&self, _colour: Colour) {}
+    fn set_text_shadow_radius(&self, _radius: f32) {}
+    fn set_text_transform(&self, _transform: TextTransform) {}
+}
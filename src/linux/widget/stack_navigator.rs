@@ -1,9 +1,12 @@
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crossbeam_channel::{Receiver, TryRecvError};
 use gtk4::prelude::*;
+use parking_lot::RwLock;
 
 use crate::custom::MeasuredSize;
 use crate::native_tree::context::Context;
@@ -11,13 +14,109 @@ use crate::native_tree::{AvalableSpace, NativeStackNavigatorImp, NativeStyledEle
 use crate::shadow_tree::component::NavigatorCommand;
 use crate::shadow_tree::NodeID;
 use crate::style::*;
+use crate::widget::stack_navigator::{PageEvents, StackNavigatorTransition};
 
 use super::NativeElement;
 
 struct StackNavigatorState {
     dropped: bool,
-    history: Vec<NodeID>,
+    /// default transition of the navigator
+    transition: StackNavigatorTransition,
+    /// pages in the stack with the transition they were shown with
+    history: Vec<(NodeID, StackNavigatorTransition)>,
     children: Vec<(String, NodeID)>,
+    /// page pushed before it was added, an instance is pushed before it is rendered
+    pending: Option<(String, StackNavigatorTransition)>,
+    events: HashMap<String, PageEvents>,
+    /// the page focus was last given to
+    focused: Option<NodeID>,
+    /// the history read by the `StackNavigator` handles
+    published: Arc<RwLock<Vec<String>>>,
+}
+
+impl StackNavigatorState {
+    fn find(&self, name: &str) -> Option<NodeID> {
+        self.children
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, id)| *id)
+    }
+
+    fn name(&self, page: NodeID) -> Option<&str> {
+        self.children
+            .iter()
+            .find(|(_, id)| *id == page)
+            .map(|(n, _)| n.as_str())
+    }
+
+    fn events(&self, page: NodeID) -> PageEvents {
+        self.name(page)
+            .and_then(|name| self.events.get(name))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// the guard of the visible page
+    fn before_leave(&self) -> Option<Arc<dyn Fn() -> bool + Send + Sync>> {
+        let (page, _) = self.history.last()?;
+        return self.events(*page).on_before_leave;
+    }
+
+    /// write the names of the pages in history for the handles
+    fn publish(&self) {
+        *self.published.write() = self
+            .history
+            .iter()
+            .filter_map(|(page, _)| self.name(*page).map(|n| n.to_string()))
+            .collect();
+    }
+}
+
+/// call the guard of the visible page, leaving is cancelled if it returns false
+fn may_leave(state: &Rc<RefCell<StackNavigatorState>>) -> bool {
+    // the state is not borrowed while calling
+    let guard = state.borrow().before_leave();
+
+    match guard {
+        Some(guard) => guard(),
+        None => true,
+    }
+}
+
+/// give focus to the visible page, blurring the page focused before
+fn update_focus(state: &Rc<RefCell<StackNavigatorState>>) {
+    let (blurred, focused) = {
+        let mut state = state.borrow_mut();
+        let visible = state.history.last().map(|(id, _)| *id);
+
+        if visible == state.focused {
+            return;
+        }
+
+        let blurred = state.focused.map(|page| state.events(page));
+        let focused = visible.map(|page| state.events(page));
+        state.focused = visible;
+
+        (blurred, focused)
+    };
+
+    if let Some(cb) = blurred.and_then(|e| e.on_blur) {
+        cb();
+    }
+    if let Some(cb) = focused.and_then(|e| e.on_focus) {
+        cb();
+    }
+}
+
+/// the gtk transition showing a page, going back reverses the direction
+fn stack_transition(transition: StackNavigatorTransition, back: bool) -> gtk4::StackTransitionType {
+    match transition {
+        StackNavigatorTransition::None => gtk4::StackTransitionType::None,
+        StackNavigatorTransition::Fade => gtk4::StackTransitionType::Crossfade,
+        // a pushed page slides in from the right and back out to the right
+        StackNavigatorTransition::Slide if back => gtk4::StackTransitionType::SlideRight,
+        StackNavigatorTransition::Slide => gtk4::StackTransitionType::SlideLeft,
+    }
 }
 
 pub struct NativeStackNavigator {
@@ -32,12 +131,21 @@ impl NativeElement for NativeStackNavigator {
 }
 
 impl NativeStackNavigatorImp for NativeStackNavigator {
-    fn new(_ctx: &mut Context, command_recv: Receiver<NavigatorCommand>) -> Self {
+    fn new(
+        _ctx: &mut Context,
+        command_recv: Receiver<NavigatorCommand>,
+        history: Arc<RwLock<Vec<String>>>,
+    ) -> Self {
         // constuct the state
         let state = Rc::new(RefCell::new(StackNavigatorState {
             dropped: false,
+            transition: StackNavigatorTransition::default(),
             history: Vec::new(),
             children: Vec::new(),
+            pending: None,
+            events: HashMap::new(),
+            focused: None,
+            published: history,
         }));
         let state1 = state.clone();
 
@@ -48,81 +156,102 @@ impl NativeStackNavigatorImp for NativeStackNavigator {
         stack.set_hhomogeneous(true);
         stack.set_vhomogeneous(true);
 
-        stack.set_transition_type(gtk4::StackTransitionType::SlideLeftRight);
-
         glib::timeout_add_local(Duration::from_millis(5), move || {
             let mut state = state1.borrow_mut();
 
-            'outer: loop {
-                match command_recv.try_recv() {
-                    Ok(cmd) => match cmd {
-                        NavigatorCommand::Goback => {
-                            if state.history.len() > 1 {
-                                // pop the last page
-                                state.history.pop();
-                                // get the page to jump to
-                                let page = state.history.last().unwrap();
-                                for (name, id) in &state.children {
-                                    if id == page {
-                                        stack1.set_visible_child_name(name);
-                                        break;
-                                    }
-                                }
+            loop {
+                let cmd = match command_recv.try_recv() {
+                    Ok(cmd) => cmd,
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        state.dropped = true;
+                        return glib::ControlFlow::Break;
+                    }
+                };
+
+                // visible page changes, layout must be recomputed
+                crate::state::mark_dirty();
+
+                let (name, transition) = match cmd {
+                    NavigatorCommand::Goback => {
+                        if state.history.len() > 1 {
+                            drop(state);
+                            let leave = may_leave(&state1);
+                            state = state1.borrow_mut();
+
+                            if !leave {
+                                continue;
                             }
-                        }
-                        NavigatorCommand::Push(name) => {
-                            for (n, id) in &state.children {
-                                if n == &name {
-                                    // set the page
-                                    stack1.set_visible_child_name(n);
-                                    // copy the id
-                                    let id = *id;
-                                    // push page to history
-                                    state.history.push(id);
-
-                                    break;
-                                }
+
+                            // pop the last page, it leaves the way it came in
+                            let (_, transition) = state.history.pop().unwrap();
+                            let (page, _) = *state.history.last().unwrap();
+
+                            if let Some(name) = state.name(page) {
+                                stack1.set_visible_child_full(
+                                    name,
+                                    stack_transition(transition, true),
+                                );
                             }
                         }
-                        NavigatorCommand::Goto(name) => {
-                            // find the id of page
-                            if let Some((_, page)) =
-                                state.children.iter().find(|(n, _p)| n == &name)
-                            {
-                                // find the page in history
-                                for (i, id) in state.history.iter().rev().enumerate() {
-                                    if id == page {
-                                        // set the page
-                                        stack1.set_visible_child_name(&name);
-
-                                        // remove all the stack behind
-                                        for _ in 0..i {
-                                            state.children.pop();
-                                        }
-
-                                        continue 'outer;
-                                    }
+                        continue;
+                    }
+                    NavigatorCommand::Push(name) => {
+                        let transition = state.transition;
+                        (name, transition)
+                    }
+                    NavigatorCommand::PushWithTransition(name, transition) => (name, transition),
+                    NavigatorCommand::Goto(name) => {
+                        let page = match state.find(&name) {
+                            Some(page) => page,
+                            None => continue,
+                        };
+
+                        // go back to the page if it is in history
+                        if let Some(i) = state.history.iter().rposition(|(id, _)| *id == page) {
+                            if i + 1 < state.history.len() {
+                                drop(state);
+                                let leave = may_leave(&state1);
+                                state = state1.borrow_mut();
+
+                                if !leave {
+                                    continue;
                                 }
 
-                                // no page in history found, push page
+                                let (_, transition) = *state.history.last().unwrap();
+                                state.history.truncate(i + 1);
 
-                                // set the page
-                                stack1.set_visible_child_name(&name);
-                                // copy the id
-                                let page = *page;
-                                // push page to history
-                                state.history.push(page);
+                                stack1.set_visible_child_full(
+                                    &name,
+                                    stack_transition(transition, true),
+                                );
                             }
+                            continue;
                         }
-                    },
-                    Err(TryRecvError::Empty) => break,
-                    Err(TryRecvError::Disconnected) => {
-                        state.dropped = true;
-                        return glib::ControlFlow::Break;
+
+                        // no page in history found, push page
+                        let transition = state.transition;
+                        (name, transition)
                     }
+                };
+
+                if let Some(page) = state.find(&name) {
+                    // set the page
+                    stack1.set_visible_child_full(&name, stack_transition(transition, false));
+                    // push page to history
+                    state.history.push((page, transition));
+                } else {
+                    // the instance is added once rendered
+                    state.pending = Some((name, transition));
                 }
             }
 
+            state.publish();
+            drop(state);
+
+            // focus follows the visible page
+            update_focus(&state1);
+
             return glib::ControlFlow::Continue;
         });
 
@@ -151,9 +280,18 @@ impl NativeStackNavigatorImp for NativeStackNavigator {
                 // copy the id
                 let id = *id;
                 state.history.clear();
-                state.history.push(id);
+                state.history.push((id, StackNavigatorTransition::None));
             }
         }
+
+        // show the page if it was pushed before it was added
+        if state.pending.as_ref().map(|(n, _)| n.as_str()) == Some(name) {
+            let (_, transition) = state.pending.take().unwrap();
+
+            self.stack
+                .set_visible_child_full(name, stack_transition(transition, false));
+            state.history.push((id, transition));
+        }
     }
 
     fn remove_child(&self, _ctx: &mut Context, name: &str) {
@@ -172,14 +310,14 @@ impl NativeStackNavigatorImp for NativeStackNavigator {
                 }
             }
 
-            state.history.retain(|p| p != &page);
+            state.history.retain(|(p, _)| p != &page);
         }
     }
 
     fn visible_child(&self, _ctx: &mut Context) -> Option<NodeID> {
         let state = self.state.borrow();
 
-        state.history.last().map(|id| *id)
+        state.history.last().map(|(id, _)| *id)
     }
 
     fn should_retain(&self, _ctx: &mut Context) -> bool {
@@ -188,6 +326,25 @@ impl NativeStackNavigatorImp for NativeStackNavigator {
         return !state.dropped;
     }
 
+    fn set_transition(
+        &self,
+        _ctx: &mut Context,
+        transition: StackNavigatorTransition,
+        duration: Duration,
+    ) {
+        self.stack
+            .set_transition_duration(duration.as_millis() as u32);
+        self.state.borrow_mut().transition = transition;
+    }
+
+    fn set_page_events(&self, _ctx: &mut Context, events: Vec<(String, PageEvents)>) {
+        self.state.borrow_mut().events = events.into_iter().collect();
+    }
+
+    fn check_update(&self) {
+        // commands are polled by the glib timeout
+    }
+
     fn layout_child(
         &self,
         _ctx: &mut Context,
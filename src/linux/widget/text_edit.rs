@@ -1,22 +1,30 @@
+use std::cell::Cell;
+use std::ops::Range;
 use std::sync::Arc;
 
 use gtk4::prelude::*;
 use parking_lot::RwLock;
 
 use crate::native_tree::context::Context;
-use crate::native_tree::{AvalableSpace, MeasuredSize, NativeStyledElement, NativeTextEditImp};
+use crate::native_tree::{
+    AvalableSpace, MeasuredSize, NativeFocusableImp, NativeStyledElement, NativeTextEditImp,
+};
 use crate::style::{
     BorderStyle, Colour, FontStyle, FontWeight, PointEvents, TextAlign, TextDecorationLine,
     TextTransform,
 };
+use crate::widget::text_edit::WrapMode;
 
-use super::NativeElement;
+use super::{blur_widget, NativeElement};
 
 pub struct NativeTextEdit {
     edit: gtk4::TextView,
     tag: gtk4::TextTag,
     scroll: gtk4::ScrolledWindow,
+    /// the handler of the changed signal, blocked while the text is set programmatically
+    changed_handler: gtk4::glib::SignalHandlerId,
     text_changed_callback: Arc<RwLock<Option<Arc<dyn Fn(&str) + Sync + Send>>>>,
+    selection_changed_callback: Arc<RwLock<Option<Arc<dyn Fn(Range<usize>) + Sync + Send>>>>,
 }
 
 impl NativeElement for NativeTextEdit {
@@ -25,6 +33,14 @@ impl NativeElement for NativeTextEdit {
     }
 }
 
+/// the selected range of the buffer in characters
+fn buffer_selection(buffer: &gtk4::TextBuffer) -> Range<usize> {
+    let bound = buffer.iter_at_mark(&buffer.selection_bound()).offset() as usize;
+    let insert = buffer.iter_at_mark(&buffer.get_insert()).offset() as usize;
+
+    return bound.min(insert)..bound.max(insert);
+}
+
 impl NativeTextEditImp for NativeTextEdit {
     fn new(_ctx: &mut Context) -> Self {
         let buffer = gtk4::TextBuffer::new(None);
@@ -36,17 +52,42 @@ impl NativeTextEditImp for NativeTextEdit {
             Arc::new(RwLock::new(None));
         let cb = text_changed_callback.clone();
 
-        view.connect_buffer_notify(move |view| {
+        let changed_handler = buffer.connect_changed(move |buffer| {
             let lock = cb.read();
 
             if let Some(cb) = lock.as_ref() {
-                let buffer = view.buffer();
                 let start = buffer.start_iter();
                 let end = buffer.end_iter();
                 (cb)(buffer.slice(&start, &end, false).as_str())
             }
         });
 
+        let selection_changed_callback: Arc<
+            RwLock<Option<Arc<dyn Fn(Range<usize>) + Sync + Send>>>,
+        > = Arc::new(RwLock::new(None));
+        let cb = selection_changed_callback.clone();
+        let last_selection = Cell::new(0..0);
+
+        // the insert and selection bound marks are moved with the cursor
+        buffer.connect_mark_set(move |buffer, _iter, mark| {
+            if *mark != buffer.get_insert() && *mark != buffer.selection_bound() {
+                return;
+            }
+
+            let selection = buffer_selection(buffer);
+            let old = last_selection.replace(selection.clone());
+
+            if old == selection {
+                return;
+            }
+
+            let lock = cb.read();
+
+            if let Some(cb) = lock.as_ref() {
+                (cb)(selection)
+            }
+        });
+
         view.set_height_request(100);
         view.set_width_request(100);
         view.set_hexpand(true);
@@ -59,11 +100,67 @@ impl NativeTextEditImp for NativeTextEdit {
             edit: view,
             tag: tag,
             scroll: scroll,
+            changed_handler,
             text_changed_callback,
+            selection_changed_callback,
+        }
+    }
+    fn set_text(&self, _ctx: &mut Context, text: &str) {
+        let buffer = self.edit.buffer();
+        let current = buffer.slice(&buffer.start_iter(), &buffer.end_iter(), false);
+
+        if current == text {
+            return;
         }
+
+        buffer.block_signal(&self.changed_handler);
+        buffer.set_text(text);
+        buffer.unblock_signal(&self.changed_handler);
+
+        // the style tag only covers the text it was applied to
+        buffer.apply_tag(&self.tag, &buffer.start_iter(), &buffer.end_iter());
+    }
+    fn set_selection(&self, _ctx: &mut Context, selection: Range<usize>) {
+        let buffer = self.edit.buffer();
+        let start = buffer.iter_at_offset(selection.start as i32);
+        let end = buffer.iter_at_offset(selection.end as i32);
+
+        buffer.select_range(&end, &start);
+    }
+    fn set_read_only(&self, _ctx: &mut Context, read_only: bool) {
+        self.edit.set_editable(!read_only);
+        self.edit.set_cursor_visible(!read_only);
+    }
+    fn set_wrap_mode(&self, _ctx: &mut Context, mode: WrapMode) {
+        self.edit.set_wrap_mode(match mode {
+            WrapMode::None => gtk4::WrapMode::None,
+            WrapMode::Char => gtk4::WrapMode::Char,
+            WrapMode::Word => gtk4::WrapMode::Word,
+            WrapMode::WordChar => gtk4::WrapMode::WordChar,
+        })
+    }
+    fn set_on_text_changed(
+        &self,
+        _ctx: &mut Context,
+        callback: Option<Arc<dyn Fn(&str) + Sync + Send>>,
+    ) {
+        *self.text_changed_callback.write() = callback
+    }
+    fn set_on_selection_changed(
+        &self,
+        _ctx: &mut Context,
+        callback: Option<Arc<dyn Fn(Range<usize>) + Sync + Send>>,
+    ) {
+        *self.selection_changed_callback.write() = callback
+    }
+}
+
+impl NativeFocusableImp for NativeTextEdit {
+    fn focus(&self, _ctx: &mut Context) -> bool {
+        self.edit.grab_focus()
     }
-    fn set_on_text_changed(&self, _ctx: &mut Context, callback: Arc<dyn Fn(&str) + Sync + Send>) {
-        *self.text_changed_callback.write() = Some(callback)
+    fn blur(&self, _ctx: &mut Context) {
+        blur_widget(self.scroll.as_ref())
     }
 }
 
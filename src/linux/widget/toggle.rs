@@ -0,0 +1,268 @@
+use std::sync::Arc;
+
+use gtk4::prelude::*;
+use parking_lot::RwLock;
+
+use crate::native_tree::context::Context;
+use crate::native_tree::{
+    AvalableSpace, MeasuredSize, NativeFocusableImp, NativeStyledElement, NativeTextImp,
+    NativeToggleImp,
+};
+use crate::shadow_tree::component::ToggleKind;
+use crate::style::{
+    BorderStyle, Colour, FontStyle, FontWeight, PointEvents, TextAlign, TextDecorationLine,
+    TextTransform,
+};
+
+use super::{blur_widget, NativeElement, NativeText};
+
+/// space between the indicator and the label
+const SPACING: i32 = 6;
+
+/// the gtk widget showing the state of a checkbox or a switch
+#[derive(Clone)]
+enum Indicator {
+    Check(gtk4::CheckButton),
+    Switch(gtk4::Switch),
+}
+
+impl Indicator {
+    fn widget(&self) -> &gtk4::Widget {
+        match self {
+            Self::Check(c) => c.as_ref(),
+            Self::Switch(s) => s.as_ref(),
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        match self {
+            Self::Check(c) => c.is_active(),
+            Self::Switch(s) => s.is_active(),
+        }
+    }
+
+    fn set_active(&self, active: bool) {
+        match self {
+            Self::Check(c) => c.set_active(active),
+            Self::Switch(s) => s.set_active(active),
+        }
+    }
+}
+
+pub struct NativeToggle {
+    container: gtk4::Box,
+    indicator: Indicator,
+    label: NativeText,
+    /// the handler of the toggled signal, blocked while the state is set programmatically
+    toggled_handler: gtk4::glib::SignalHandlerId,
+    callback: Arc<RwLock<Option<Arc<dyn Fn(bool) + Send + Sync>>>>,
+}
+
+impl NativeToggleImp for NativeToggle {
+    fn new(ctx: &mut Context, kind: ToggleKind) -> Self {
+        let label = NativeText::new(ctx, "");
+        label.as_gtk4_widget().set_visible(false);
+
+        let callback: Arc<RwLock<Option<Arc<dyn Fn(bool) + Send + Sync>>>> =
+            Arc::new(RwLock::new(None));
+        let cb = callback.clone();
+
+        let on_toggled = move |active: bool| {
+            let callback = cb.read().clone();
+
+            if let Some(cb) = callback {
+                cb(active);
+            }
+
+            // the toggle is reset to the checked prop if the callback rejected the toggle
+            crate::state::mark_dirty();
+        };
+
+        let (indicator, toggled_handler) = match kind {
+            ToggleKind::Checkbox => {
+                let check = gtk4::CheckButton::new();
+                let handler = check.connect_toggled(move |check| on_toggled(check.is_active()));
+
+                (Indicator::Check(check), handler)
+            }
+            ToggleKind::Switch => {
+                let switch = gtk4::Switch::new();
+                switch.set_valign(gtk4::Align::Center);
+                let handler =
+                    switch.connect_active_notify(move |switch| on_toggled(switch.is_active()));
+
+                (Indicator::Switch(switch), handler)
+            }
+        };
+
+        // clicking the label toggles the indicator
+        let gesture = gtk4::GestureClick::new();
+        let i = indicator.clone();
+        gesture.connect_released(move |_, _, _, _| {
+            if i.widget().is_sensitive() {
+                i.set_active(!i.is_active());
+            }
+        });
+        label.as_gtk4_widget().add_controller(gesture);
+
+        let container = gtk4::Box::new(gtk4::Orientation::Horizontal, SPACING);
+        container.append(indicator.widget());
+        container.append(label.as_gtk4_widget());
+
+        Self {
+            container,
+            indicator,
+            label,
+            toggled_handler,
+            callback,
+        }
+    }
+
+    fn set_label(&self, ctx: &mut Context, label: &str) {
+        self.label.set_text(ctx, label);
+        self.label.as_gtk4_widget().set_visible(!label.is_empty());
+    }
+
+    fn set_checked(&self, _ctx: &mut Context, checked: bool) {
+        if self.indicator.is_active() == checked {
+            return;
+        }
+
+        let widget = self.indicator.widget();
+
+        widget.block_signal(&self.toggled_handler);
+        self.indicator.set_active(checked);
+        widget.unblock_signal(&self.toggled_handler);
+    }
+
+    fn set_disabled(&self, _ctx: &mut Context, disabled: bool) {
+        self.container.set_sensitive(!disabled)
+    }
+
+    fn set_on_toggle(
+        &self,
+        _ctx: &mut Context,
+        on_toggle: Option<Arc<dyn Fn(bool) + Send + Sync>>,
+    ) {
+        *self.callback.write() = on_toggle;
+    }
+}
+
+impl NativeFocusableImp for NativeToggle {
+    fn focus(&self, _ctx: &mut Context) -> bool {
+        self.indicator.widget().grab_focus()
+    }
+    fn blur(&self, _ctx: &mut Context) {
+        blur_widget(self.container.as_ref())
+    }
+}
+
+impl NativeElement for NativeToggle {
+    fn as_gtk4_widget(&self) -> &gtk4::Widget {
+        self.container.as_ref()
+    }
+}
+
+impl NativeStyledElement for NativeToggle {
+    fn measure(
+        &self,
+        _ctx: &mut Context,
+        known_width: AvalableSpace,
+        known_height: AvalableSpace,
+    ) -> anyhow::Result<MeasuredSize> {
+        // measure width
+        let (min_width, natural_width, _, _) = self.container.measure(
+            gtk4::Orientation::Horizontal,
+            match known_height {
+                AvalableSpace::AtMost(f) => f as i32,
+                AvalableSpace::Exact(f) => f as i32,
+                AvalableSpace::Unknown => -1,
+            },
+        );
+        // measure height
+        let (min_height, natural_height, _, _) = self.container.measure(
+            gtk4::Orientation::Vertical,
+            match known_width {
+                AvalableSpace::AtMost(f) => f as i32,
+                AvalableSpace::Exact(f) => f as i32,
+                AvalableSpace::Unknown => -1,
+            },
+        );
+
+        return Ok(MeasuredSize {
+            min_width: min_width as f32,
+            natural_width: natural_width as f32,
+            min_height: min_height as f32,
+            natural_height: natural_height as f32,
+        });
+    }
+    fn set_visible(&self, _ctx: &mut Context, visible: bool) {
+        self.container.set_visible(visible)
+    }
+    fn set_backface_visible(&self, _visible: bool) {}
+
+    fn set_colour(&self, colour: Colour) {
+        self.label.set_colour(colour)
+    }
+    fn set_background_colour(&self, colour: Colour) {
+        self.label.set_background_colour(colour)
+    }
+
+    fn set_border_top_width(&self, _width: f32) {}
+    fn set_border_bottom_width(&self, _width: f32) {}
+    fn set_border_left_width(&self, _width: f32) {}
+    fn set_border_right_width(&self, _width: f32) {}
+
+    fn set_border_top_left_radius(&self, _radius: f32) {}
+    fn set_border_top_right_radius(&self, _radius: f32) {}
+    fn set_border_bottom_left_radius(&self, _radius: f32) {}
+    fn set_border_bottom_right_radius(&self, _radius: f32) {}
+
+    fn set_border_top_colour(&self, _colour: Colour) {}
+    fn set_border_bottom_colour(&self, _colour: Colour) {}
+    fn set_border_left_colour(&self, _colour: Colour) {}
+    fn set_border_right_colour(&self, _colour: Colour) {}
+
+    fn set_border_style(&self, _style: BorderStyle) {}
+
+    fn set_opacity(&self, opacity: f32) {
+        self.container.set_opacity(opacity as f64);
+    }
+    fn set_points_event(&self, _event: PointEvents) {}
+
+    fn set_font_size(&self, size: f32) {
+        self.label.set_font_size(size)
+    }
+    fn set_font_style(&self, style: FontStyle) {
+        self.label.set_font_style(style)
+    }
+    fn set_font_weight(&self, weight: FontWeight) {
+        self.label.set_font_weight(weight)
+    }
+
+    fn set_letter_spacing(&self, spacing: f32) {
+        self.label.set_letter_spacing(spacing)
+    }
+    fn set_line_height(&self, height: f32) {
+        self.label.set_line_height(height)
+    }
+
+    fn set_text_align(&self, align: TextAlign) {
+        self.label.set_text_align(align)
+    }
+    fn set_text_decloration_line(&self, line: TextDecorationLine) {
+        self.label.set_text_decloration_line(line)
+    }
+    fn set_text_decloration_colour(&self, colour: Colour) {
+        self.label.set_text_decloration_colour(colour)
+    }
+    fn set_text_shadow_colour(&self, colour: Colour) {
+        self.label.set_text_shadow_colour(colour)
+    }
+    fn set_text_shadow_radius(&self, radius: f32) {
+        self.label.set_text_shadow_radius(radius)
+    }
+    fn set_text_transform(&self, transform: TextTransform) {
+        self.label.set_text_transform(transform)
+    }
+}
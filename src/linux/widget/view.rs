@@ -38,6 +38,27 @@ impl NativeViewImp for NativeView {
         self.fixed.remove(child.as_gtk4_widget());
     }
 
+    fn move_child(&self, _ctx: &mut Context, index: usize, child: &dyn NativeElement) {
+        let widget = child.as_gtk4_widget();
+
+        // find the sibling at index, not counting the child itself
+        let mut sibling = self.fixed.first_child();
+        let mut i = 0;
+
+        while let Some(s) = &sibling {
+            if s != widget {
+                if i == index {
+                    break;
+                }
+                i += 1;
+            }
+            sibling = s.next_sibling();
+        }
+
+        // reordering does not unparent the widget, focus and scroll state is kept
+        widget.insert_before(&self.fixed, sibling.as_ref());
+    }
+
     fn layout_child(
         &self,
         _ctx: &mut Context,
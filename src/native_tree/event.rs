@@ -0,0 +1,474 @@
+use crate::event::{KeyEvent, KeyEventKind, PointerButton, PointerEvent, PointerEventKind};
+use crate::shadow_tree::NodeID;
+use crate::style::PointEvents;
+
+use super::context::Context;
+use super::traits::*;
+use super::{NativeComponent, NativeTree};
+
+impl<'a> NativeTree<'a> {
+    /// deliver a pointer event at a position relative to the window.
+    ///
+    /// `Enter` and `Move` update the nodes under the pointer,
+    /// `Leave` means the pointer has left the window.
+    /// while a modal is visible, only the topmost modal receives events
+    /// and releasing the pointer on its backdrop requests to close it.
+    pub(crate) fn dispatch_pointer(
+        &self,
+        kind: PointerEventKind,
+        button: Option<PointerButton>,
+        x: f32,
+        y: f32,
+    ) {
+        let modal = self.top_modal();
+
+        let target = match (kind, modal) {
+            (PointerEventKind::Leave, _) => None,
+            (_, Some(modal)) => self.hit_test(modal, x, y, 0.0, 0.0),
+            (_, None) => self
+                .root
+                .and_then(|root| self.hit_test(root, x, y, 0.0, 0.0)),
+        };
+
+        // the target and its ancestors, target first
+        let mut path = Vec::new();
+        let mut next = target;
+
+        while let Some(id) = next {
+            path.push(id);
+            next = self.nodes.get(&id).and_then(|n| n.parent);
+        }
+
+        self.update_hovered(&path, x, y);
+
+        if kind == PointerEventKind::Enter || kind == PointerEventKind::Leave {
+            return;
+        }
+
+        // bubble from the target to the root
+        let mut event = PointerEvent::new(kind, button, x, y);
+
+        // the backdrop is the modal itself, its child is above it
+        let backdrop = kind == PointerEventKind::Up && modal.is_some() && target == modal;
+
+        for id in path {
+            let node = match self.nodes.get(&id) {
+                Some(n) => n,
+                None => continue,
+            };
+
+            if let Some(callback) = node.pointer.get(kind) {
+                let (abs_x, abs_y) = self.absolute_location(id);
+                event.local_x = x - abs_x;
+                event.local_y = y - abs_y;
+
+                callback(&event);
+
+                if event.is_propagation_stopped() {
+                    return;
+                }
+            }
+        }
+
+        if backdrop {
+            self.request_close(modal.unwrap());
+        }
+    }
+
+    /// deliver a key event to the focused node.
+    /// Tab and Shift+Tab move the focus and Escape requests to close the topmost modal
+    /// unless the event is stopped.
+    /// returns true if the event is handled.
+    pub(crate) fn handle_key(&self, event: &KeyEvent) -> bool {
+        if let Some(id) = self.focused() {
+            if self.dispatch_key(id, event) {
+                return true;
+            }
+        }
+
+        let modifiers = event.modifiers;
+        let is_tab = event.key == "Tab" || event.key == "ISO_Left_Tab";
+
+        if event.kind == KeyEventKind::Down
+            && is_tab
+            && !modifiers.control
+            && !modifiers.alt
+            && !modifiers.meta
+        {
+            return self.focus_next(modifiers.shift);
+        }
+
+        if event.kind == KeyEventKind::Down && event.key == "Escape" {
+            if let Some(modal) = self.top_modal() {
+                self.request_close(modal);
+                return true;
+            }
+        }
+
+        return false;
+    }
+
+    /// ask the user of a modal to close it
+    fn request_close(&self, id: NodeID) {
+        if let Some(NativeComponent::Modal(m)) = self.nodes.get(&id).map(|n| n.component.as_ref()) {
+            m.request_close(self.context());
+        }
+    }
+
+    /// deliver a key event to a node, the event bubbles to the root.
+    /// returns true if a handler has stopped the propagation.
+    pub(crate) fn dispatch_key(&self, target: NodeID, event: &KeyEvent) -> bool {
+        let mut next = Some(target);
+
+        while let Some(id) = next {
+            let node = match self.nodes.get(&id) {
+                Some(n) => n,
+                None => break,
+            };
+
+            if let Some(callback) = node.keyboard.get(event.kind) {
+                callback(event);
+
+                if event.is_propagation_stopped() {
+                    return true;
+                }
+            }
+
+            next = node.parent;
+        }
+
+        return false;
+    }
+
+    /// find the node of a native component
+    #[cfg(all(target_os = "linux", not(feature = "headless")))]
+    pub(crate) fn find_node<F>(&self, predicate: F) -> Option<NodeID>
+    where
+        F: Fn(&NativeComponent) -> bool,
+    {
+        self.nodes
+            .iter()
+            .find(|(_, node)| predicate(&node.component))
+            .map(|(id, _)| *id)
+    }
+
+    /// send enter and leave events to the nodes whose hovered state has changed
+    fn update_hovered(&self, path: &[NodeID], x: f32, y: f32) {
+        let old = core::mem::replace(&mut *self.hovered.lock(), path.to_vec());
+
+        // the innermost node is left first
+        for id in &old {
+            if !path.contains(id) {
+                self.send_pointer(*id, PointerEventKind::Leave, x, y);
+            }
+        }
+
+        // the outermost node is entered first
+        for id in path.iter().rev() {
+            if !old.contains(id) {
+                self.send_pointer(*id, PointerEventKind::Enter, x, y);
+            }
+        }
+    }
+
+    /// send an event to a single node, the event does not bubble
+    fn send_pointer(&self, id: NodeID, kind: PointerEventKind, x: f32, y: f32) {
+        // the node may have been removed
+        let callback = match self.nodes.get(&id).and_then(|n| n.pointer.get(kind)) {
+            Some(c) => c,
+            None => return,
+        };
+
+        let (abs_x, abs_y) = self.absolute_location(id);
+
+        let mut event = PointerEvent::new(kind, None, x, y);
+        event.local_x = x - abs_x;
+        event.local_y = y - abs_y;
+
+        callback(&event);
+    }
+
+    /// find the deepest node at the position that can be the target of pointer events.
+    /// `offset_x` and `offset_y` is the position of the parent relative to the window.
+    fn hit_test(&self, id: NodeID, x: f32, y: f32, offset_x: f32, offset_y: f32) -> Option<NodeID> {
+        let node = self.nodes.get(&id)?;
+        let layout = &node.computed_layout;
+
+        let left = offset_x + layout.location.x;
+        let top = offset_y + layout.location.y;
+
+        if x < left || y < top || x >= left + layout.size.width || y >= top + layout.size.height {
+            return None;
+        }
+
+        let point_events = node.style.get_point_events();
+
+        if point_events == PointEvents::None {
+            return None;
+        }
+
+        if point_events != PointEvents::BoxOnly {
+            let context = self.context();
+            let (scroll_x, scroll_y) = self.scroll_offset(context, &node.component);
+
+            // later children are drawn above earlier children
+            for child in self.visible_children(context, id).iter().rev() {
+                if let Some(target) = self.hit_test(*child, x, y, left - scroll_x, top - scroll_y) {
+                    return Some(target);
+                }
+            }
+        }
+
+        if point_events == PointEvents::BoxNone {
+            return None;
+        }
+
+        return Some(id);
+    }
+
+    /// the children shown on screen, only the current page of a navigator is visible
+    pub(super) fn visible_children(&self, context: &mut Context, id: NodeID) -> Vec<NodeID> {
+        let node = self.nodes.get(&id).expect("invalid node id");
+
+        match node.component.as_ref() {
+            NativeComponent::StackNavigator(n) => n.visible_child(context).into_iter().collect(),
+            NativeComponent::TabNavigator(n) => n.visible_child(context).into_iter().collect(),
+            // modals are shown in their own layer
+            _ => node
+                .children
+                .iter()
+                .copied()
+                .filter(|c| !self.is_modal(*c))
+                .collect(),
+        }
+    }
+
+    /// scroll location of a scroll view, zero for other components
+    fn scroll_offset(&self, context: &mut Context, component: &NativeComponent) -> (f32, f32) {
+        match component {
+            NativeComponent::ScrollView(s) => (
+                s.horizontal_scroll_location(context) as f32,
+                s.vertical_scroll_location(context) as f32,
+            ),
+            _ => (0.0, 0.0),
+        }
+    }
+
+    /// position of a node relative to the window
+    fn absolute_location(&self, id: NodeID) -> (f32, f32) {
+        let context = self.context();
+
+        let mut x = 0.0;
+        let mut y = 0.0;
+        let mut next = Some(id);
+
+        while let Some(id) = next {
+            let node = match self.nodes.get(&id) {
+                Some(n) => n,
+                None => break,
+            };
+
+            x += node.computed_layout.location.x;
+            y += node.computed_layout.location.y;
+
+            // a modal is positioned relative to the window
+            if self.is_modal(id) {
+                break;
+            }
+
+            next = node.parent;
+
+            if let Some(parent) = next.and_then(|p| self.nodes.get(&p)) {
+                let (scroll_x, scroll_y) = self.scroll_offset(context, &parent.component);
+                x -= scroll_x;
+                y -= scroll_y;
+            }
+        }
+
+        return (x, y);
+    }
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_pointer_bubbling() {
+    use std::sync::Arc;
+
+    use parking_lot::Mutex;
+
+    use crate::style::{Dimension, Style};
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Text, View};
+
+    static SQUARE: Style = Style {
+        width: Dimension::Points(50.0),
+        height: Dimension::Points(50.0),
+        ..Style::DEFAULT
+    };
+
+    let log = Arc::new(Mutex::new(Vec::<String>::new()));
+    let l = log.clone();
+
+    let renderer = TestRenderer::new(move || {
+        let (l1, l2, l3) = (l.clone(), l.clone(), l.clone());
+
+        View::new()
+            .with_on_pointer_down(move |e| l1.lock().push(format!("view down {}", e.local_x)))
+            .with_child(
+                Text::new("stop")
+                    .with_style(&SQUARE)
+                    .with_on_pointer_down(move |e| {
+                        l2.lock().push(format!("stop down {}", e.local_x));
+                        e.stop_propagation();
+                    }),
+            )
+            .with_child(
+                Text::new("bubble")
+                    .with_style(&SQUARE)
+                    .with_on_pointer_up(move |_| l3.lock().push("bubble up".into())),
+            )
+    });
+
+    renderer.find_by_text("stop").unwrap().press();
+
+    // the second text does not handle pointer down, the event bubbles to the view
+    let bubble = renderer.find_by_text("bubble").unwrap().absolute_layout();
+    renderer.pointer_down(bubble.x + 1.0, bubble.y + 1.0);
+    renderer.pointer_up(bubble.x + 1.0, bubble.y + 1.0);
+
+    assert_eq!(
+        *log.lock(),
+        [
+            "stop down 25".to_string(),
+            format!("view down {}", bubble.x + 1.0),
+            "bubble up".to_string(),
+        ]
+    );
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_pointer_pass_through() {
+    use crate::style::{Dimension, PointEvents, Style};
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Text, View};
+    use crate::State;
+
+    static PASS_THROUGH: Style = Style {
+        width: Dimension::Points(50.0),
+        height: Dimension::Points(50.0),
+        point_events: PointEvents::None,
+        ..Style::DEFAULT
+    };
+
+    let pressed = State::new(Vec::<&'static str>::new());
+    let p = pressed.clone();
+
+    let renderer = TestRenderer::new(move || {
+        let (p1, p2) = (p.clone(), p.clone());
+
+        View::new()
+            .with_on_pointer_down(move |_| p1.update(|p| p.push("view")))
+            .with_child(
+                Text::new("ignored")
+                    .with_style(&PASS_THROUGH)
+                    .with_on_pointer_down(move |_| p2.update(|p| p.push("text"))),
+            )
+    });
+
+    renderer.find_by_text("ignored").unwrap().press();
+    assert_eq!(pressed.get(), ["view"]);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_pointer_enter_leave() {
+    use crate::style::{Dimension, Style};
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, View};
+    use crate::State;
+
+    static STYLE: Style = Style {
+        width: Dimension::Points(200.0),
+        height: Dimension::Points(100.0),
+        ..Style::DEFAULT
+    };
+
+    let events = State::new(Vec::<&'static str>::new());
+    let e = events.clone();
+
+    let renderer = TestRenderer::new(move || {
+        let (e1, e2) = (e.clone(), e.clone());
+
+        View::new()
+            .with_style(&STYLE)
+            .with_on_pointer_enter(move |_| e1.update(|e| e.push("enter")))
+            .with_on_pointer_leave(move |_| e2.update(|e| e.push("leave")))
+    });
+
+    renderer.pointer_move(1.0, 1.0);
+    // moving inside the view does not enter it again
+    renderer.pointer_move(2.0, 2.0);
+    renderer.pointer_move(300.0, 300.0);
+    assert_eq!(events.get(), ["enter", "leave"]);
+
+    renderer.pointer_move(1.0, 1.0);
+    renderer.pointer_leave();
+    assert_eq!(events.get(), ["enter", "leave", "enter", "leave"]);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_pointer_handler_update() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Text};
+    use crate::State;
+
+    let count = State::new(0);
+    let c = count.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let (c1, value) = (c.clone(), c.get());
+
+        // the handler captures the value of the render
+        Text::new("add").with_on_pointer_down(move |_| c1.set(value + 1))
+    });
+
+    renderer.find_by_text("add").unwrap().press();
+    renderer.step();
+    renderer.find_by_text("add").unwrap().press();
+    renderer.step();
+
+    // the handler of the latest render is called
+    assert_eq!(count.get(), 2);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_key_handlers() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, TextInput, View};
+    use crate::State;
+
+    let keys = State::new(Vec::<String>::new());
+    let k = keys.clone();
+
+    let renderer = TestRenderer::new(move || {
+        let (k1, k2) = (k.clone(), k.clone());
+
+        View::new().with_child(
+            TextInput::new()
+                .with_on_key_down(move |e| k1.update(|k| k.push(format!("down {}", e.key))))
+                .with_on_key_up(move |e| k2.update(|k| k.push(format!("up {}", e.key))))
+                .test_id("input"),
+        )
+    });
+
+    let input = renderer.find_by_test_id("input").unwrap();
+    input.key_down("a");
+    input.key_up("a");
+
+    input.key_down("Shift+B");
+
+    assert_eq!(keys.get(), ["down a", "up a", "down B"]);
+}
@@ -0,0 +1,265 @@
+use crate::shadow_tree::NodeID;
+
+use super::node::NativeNode;
+use super::NativeTree;
+
+impl<'a> NativeTree<'a> {
+    /// the node with the keyboard focus
+    pub(crate) fn focused(&self) -> Option<NodeID> {
+        *self.focused.lock()
+    }
+
+    /// move the keyboard focus to a node, returns true if the node is focused
+    pub(crate) fn focus_node(&self, id: NodeID) -> bool {
+        let widget = match self.nodes.get(&id).and_then(|n| n.component.focusable()) {
+            Some(w) => w,
+            None => return false,
+        };
+
+        if !widget.focus(self.context()) {
+            return false;
+        }
+
+        self.focus_changed(Some(id));
+
+        return true;
+    }
+
+    /// remove the keyboard focus from a node if it is focused
+    pub(crate) fn blur_node(&self, id: NodeID) {
+        if self.focused() != Some(id) {
+            return;
+        }
+
+        if let Some(widget) = self.nodes.get(&id).and_then(|n| n.component.focusable()) {
+            widget.blur(self.context());
+        }
+
+        self.focus_changed(None);
+    }
+
+    /// called once the keyboard focus has moved to a node or has left all nodes.
+    /// the focus handles are updated and the focus callbacks are invoked.
+    pub(crate) fn focus_changed(&self, id: Option<NodeID>) {
+        // only focusable nodes are tracked
+        let id = id.filter(|id| {
+            self.nodes
+                .get(id)
+                .is_some_and(|n| n.component.focusable().is_some())
+        });
+
+        let old = core::mem::replace(&mut *self.focused.lock(), id);
+
+        if old == id {
+            return;
+        }
+
+        if let Some(node) = old.and_then(|id| self.nodes.get(&id)) {
+            if let Some(handle) = &node.focus.handle {
+                handle.set_focused(false);
+            }
+            if let Some(on_blur) = &node.focus.on_blur {
+                on_blur();
+            }
+        }
+
+        if let Some(node) = id.and_then(|id| self.nodes.get(&id)) {
+            if let Some(handle) = &node.focus.handle {
+                handle.set_focused(true);
+            }
+            if let Some(on_focus) = &node.focus.on_focus {
+                on_focus();
+            }
+        }
+    }
+
+    /// move the focus to the next node in tab order, or to the previous node if `backwards`.
+    /// returns false if no node can be focused.
+    pub(crate) fn focus_next(&self, backwards: bool) -> bool {
+        let order = self.tab_order();
+        let len = order.len();
+
+        let current = self
+            .focused()
+            .and_then(|f| order.iter().position(|id| *id == f));
+
+        // nodes such as disabled buttons refuse the focus, try every node once
+        for i in 1..=len {
+            let index = match (current, backwards) {
+                (Some(c), false) => (c + i) % len,
+                (Some(c), true) => (c + len - i) % len,
+                (None, false) => i - 1,
+                (None, true) => len - i,
+            };
+
+            if self.focus_node(order[index]) {
+                return true;
+            }
+        }
+
+        return false;
+    }
+
+    /// the nodes reachable with Tab, in tab order.
+    /// the focus is kept within the topmost visible modal
+    fn tab_order(&self) -> Vec<NodeID> {
+        let mut nodes = Vec::new();
+
+        if let Some(root) = self.top_modal().or(self.root) {
+            self.collect_focusable(root, &mut nodes);
+        }
+
+        // negative tab indices are skipped
+        nodes.retain(|(_, index)| *index >= 0);
+
+        // positive tab indices come first, the sort is stable and keeps the tree order
+        nodes.sort_by_key(|(_, index)| match *index {
+            0 => i32::MAX,
+            i => i,
+        });
+
+        return nodes.into_iter().map(|(id, _)| id).collect();
+    }
+
+    fn collect_focusable(&self, id: NodeID, nodes: &mut Vec<(NodeID, i32)>) {
+        let node = self.nodes.get(&id).expect("invalid node id");
+
+        if node.component.focusable().is_some() {
+            nodes.push((id, node.focus.tab_index.unwrap_or(0)));
+        }
+
+        // hidden navigator pages can not be focused
+        for child in self.visible_children(self.context(), id) {
+            self.collect_focusable(child, nodes);
+        }
+    }
+
+    /// apply the requests made through focus handles
+    pub(crate) fn apply_focus_requests(&self) {
+        for (id, node) in &self.nodes {
+            let request = node.focus.handle.as_ref().and_then(|h| h.take_request());
+
+            match request {
+                Some(true) => {
+                    self.focus_node(*id);
+                }
+                Some(false) => self.blur_node(*id),
+                None => {}
+            }
+        }
+    }
+
+    /// clear the focus of a removed node, the blur callback is not invoked
+    pub(crate) fn forget_focus(&self, id: NodeID, node: &NativeNode) {
+        let mut focused = self.focused.lock();
+
+        if *focused == Some(id) {
+            *focused = None;
+
+            if let Some(handle) = &node.focus.handle {
+                handle.set_focused(false);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_tab_order() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{Button, ElementExt, TextEdit, TextInput, View};
+
+    let renderer = TestRenderer::new(|| {
+        View::new()
+            .with_child(TextInput::new().test_id("name"))
+            .with_child(Button::new().with_disabled(true).test_id("disabled"))
+            .with_child(TextEdit::new().with_tab_index(1).test_id("notes"))
+            .with_child(Button::new().with_tab_index(-1).test_id("skipped"))
+            .with_child(Button::new().test_id("ok"))
+    });
+
+    let focused = |r: &TestRenderer| r.focused().and_then(|n| n.test_id()).map(String::from);
+
+    // positive tab indices first, then tree order, disabled and negative indices are skipped
+    renderer.key_down("Tab");
+    assert_eq!(focused(&renderer).as_deref(), Some("notes"));
+    renderer.key_down("Tab");
+    assert_eq!(focused(&renderer).as_deref(), Some("name"));
+    renderer.key_down("Tab");
+    assert_eq!(focused(&renderer).as_deref(), Some("ok"));
+    renderer.key_down("Shift+Tab");
+    assert_eq!(focused(&renderer).as_deref(), Some("name"));
+
+    // a negative index can still be focused programmatically, a disabled node can not
+    assert!(!renderer.find_by_test_id("disabled").unwrap().focus());
+    assert!(renderer.find_by_test_id("skipped").unwrap().focus());
+    assert_eq!(focused(&renderer).as_deref(), Some("skipped"));
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_focus_handle() {
+    use crate::focus::FocusHandle;
+    use crate::testing::TestRenderer;
+    use crate::widget::{Button, ElementExt, TextInput, View};
+
+    let handle = FocusHandle::new();
+    let h = handle.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        View::new()
+            .with_child(TextInput::new().with_focus_handle(&h).test_id("name"))
+            .with_child(Button::new().test_id("ok"))
+    });
+
+    renderer.key_down("Tab");
+    assert!(handle.is_focused());
+    renderer.key_down("Tab");
+    assert!(!handle.is_focused());
+
+    // the focus is moved through the handle on the next frame
+    handle.focus();
+    assert!(!handle.is_focused());
+    renderer.step();
+    assert!(handle.is_focused());
+    assert!(renderer.find_by_test_id("name").unwrap().is_focused());
+
+    handle.blur();
+    renderer.step();
+    assert!(!handle.is_focused());
+    assert!(renderer.focused().is_none());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_focus_callbacks() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{Button, ElementExt, TextInput, View};
+    use crate::State;
+
+    let events = State::new(Vec::<&'static str>::new());
+    let e = events.clone();
+
+    let renderer = TestRenderer::new(move || {
+        let (e1, e2) = (e.clone(), e.clone());
+
+        View::new()
+            .with_child(
+                TextInput::new()
+                    .with_on_focus(move || e1.update(|e| e.push("focus")))
+                    .with_on_blur(move || e2.update(|e| e.push("blur")))
+                    .test_id("name"),
+            )
+            .with_child(Button::new().test_id("ok"))
+    });
+
+    let name = renderer.find_by_test_id("name").unwrap();
+    assert!(name.focus());
+    // focusing the focused node does nothing
+    assert!(name.focus());
+    renderer.key_down("Tab");
+    name.focus();
+    name.blur();
+
+    assert_eq!(events.get(), ["focus", "blur", "focus", "blur"]);
+}
@@ -1,8 +1,9 @@
-use taffy::Size;
+use taffy::{LayoutPartialTree, ResolveOrZero, Size};
 
 use crate::custom::NativeCustomElement;
 use crate::imp::{
-    NativeButton, NativeImageView, NativeListView, NativeText, NativeTextEdit, NativeTextInput,
+    NativeActivityIndicator, NativeButton, NativeImageView, NativeListView, NativePicker,
+    NativeProgressBar, NativeSlider, NativeText, NativeTextEdit, NativeTextInput, NativeToggle,
 };
 use crate::shadow_tree::NodeID;
 use crate::style::StyleRef;
@@ -19,6 +20,12 @@ impl<'a> NativeTree<'a> {
             None => return,
         };
 
+        // nodes are not marked dirty when their style, content or visible page changes,
+        // the cache is only reused within a pass
+        for node in self.nodes.values_mut() {
+            node.cache.clear();
+        }
+
         taffy::compute_root_layout(
             self,
             taffy::NodeId::new(root.0),
@@ -30,6 +37,53 @@ impl<'a> NativeTree<'a> {
 
         // assign layout to nodes
         self.assign_layout(context, root);
+
+        // each visible modal fills the window in its own layer
+        for modal in self.modals.clone() {
+            let visible = match self.nodes.get(&modal).map(|n| n.component.as_ref()) {
+                Some(NativeComponent::Modal(m)) => m.is_visible(context),
+                _ => false,
+            };
+
+            if visible {
+                self.compute_modal_layout(modal, width as f32, height as f32);
+                self.assign_layout(context, modal);
+            }
+        }
+    }
+
+    /// layout a modal as the root of its layer, the modal has the size of the window
+    fn compute_modal_layout(&mut self, id: NodeID, width: f32, height: f32) {
+        let node_id = taffy::NodeId::new(id.0);
+        let window = taffy::Size {
+            width: Some(width),
+            height: Some(height),
+        };
+
+        let output = self.compute_child_layout(
+            node_id,
+            taffy::LayoutInput {
+                run_mode: taffy::RunMode::PerformLayout,
+                sizing_mode: taffy::SizingMode::InherentSize,
+                axis: taffy::RequestedAxis::Both,
+                known_dimensions: window,
+                parent_size: window,
+                available_space: taffy::Size {
+                    width: taffy::AvailableSpace::Definite(width),
+                    height: taffy::AvailableSpace::Definite(height),
+                },
+                vertical_margins_are_collapsible: taffy::Line::FALSE,
+            },
+        );
+
+        let style = &self.nodes.get(&id).expect("invalid id").layout_style;
+
+        let mut layout = taffy::Layout::with_order(0);
+        layout.size = output.size;
+        layout.padding = style.padding.resolve_or_zero(Some(width));
+        layout.border = style.border.resolve_or_zero(Some(width));
+
+        self.set_unrounded_layout(node_id, &layout);
     }
 
     fn assign_layout(&self, context: &mut Context, id: NodeID) -> &taffy::Layout {
@@ -37,6 +91,11 @@ impl<'a> NativeTree<'a> {
         let node = self.nodes.get(&id).expect("invalid id");
 
         for child in &node.children {
+            // modals are laid out in their own layer
+            if self.is_modal(*child) {
+                continue;
+            }
+
             let child_layout = self.assign_layout(context, *child);
             let child_node = self.nodes.get(child).expect("invalid id");
 
@@ -56,8 +115,17 @@ impl<'a> NativeTree<'a> {
 
 // workaround for the child iterator
 pub enum ChildIdIter<'a> {
-    Normal { iter: core::slice::Iter<'a, NodeID> },
-    Navigator { gotten: bool, page: NodeID },
+    Normal {
+        iter: core::slice::Iter<'a, NodeID>,
+    },
+    /// children without the modals
+    Filtered {
+        iter: std::vec::IntoIter<NodeID>,
+    },
+    Navigator {
+        gotten: bool,
+        page: NodeID,
+    },
     None,
 }
 
@@ -69,6 +137,7 @@ impl<'a> Iterator for ChildIdIter<'a> {
                 Some(id) => Some(taffy::NodeId::new(id.0)),
                 None => None,
             },
+            Self::Filtered { iter } => iter.next().map(|id| taffy::NodeId::new(id.0)),
             Self::Navigator { gotten, page } => {
                 if !*gotten {
                     *gotten = true;
@@ -83,7 +152,10 @@ impl<'a> Iterator for ChildIdIter<'a> {
 }
 
 impl<'a> taffy::traits::TraversePartialTree for NativeTree<'a> {
-    type ChildIter<'b> = ChildIdIter<'b> where Self: 'b;
+    type ChildIter<'b>
+        = ChildIdIter<'b>
+    where
+        Self: 'b;
 
     fn child_ids(&self, parent_node_id: taffy::NodeId) -> Self::ChildIter<'_> {
         let parent_id: u64 = unsafe { core::mem::transmute(parent_node_id) };
@@ -103,6 +175,19 @@ impl<'a> taffy::traits::TraversePartialTree for NativeTree<'a> {
                     ChildIdIter::None
                 }
             }
+            NativeComponent::TabNavigator(n) => {
+                if let Some(p) = n.visible_child(context) {
+                    ChildIdIter::Navigator {
+                        gotten: false,
+                        page: p,
+                    }
+                } else {
+                    ChildIdIter::None
+                }
+            }
+            _ if parent.children.iter().any(|c| self.is_modal(*c)) => ChildIdIter::Filtered {
+                iter: self.layout_children(&parent.children).into_iter(),
+            },
             _ => ChildIdIter::Normal {
                 iter: parent.children.iter(),
             },
@@ -122,20 +207,43 @@ impl<'a> taffy::traits::TraversePartialTree for NativeTree<'a> {
                 }
                 return 0;
             }
-            _ => parent.children.len(),
+            NativeComponent::TabNavigator(n) => {
+                if n.visible_child(context).is_some() {
+                    return 1;
+                }
+                return 0;
+            }
+            _ => parent
+                .children
+                .iter()
+                .filter(|c| !self.is_modal(**c))
+                .count(),
         }
     }
     fn get_child_id(&self, parent_node_id: taffy::NodeId, child_index: usize) -> taffy::NodeId {
         let parent_id: u64 = unsafe { core::mem::transmute(parent_node_id) };
         let parent = self.nodes.get(&NodeID(parent_id)).expect("invalid id");
 
-        match parent.children.get(child_index) {
+        let mut children = parent.children.iter().filter(|c| !self.is_modal(**c));
+
+        match children.nth(child_index) {
             Some(id) => taffy::NodeId::new(id.0),
             None => panic!("invalid child index"),
         }
     }
 }
 
+impl<'a> NativeTree<'a> {
+    /// the children laid out in the parent, modals are excluded
+    fn layout_children(&self, children: &[NodeID]) -> Vec<NodeID> {
+        children
+            .iter()
+            .copied()
+            .filter(|c| !self.is_modal(*c))
+            .collect()
+    }
+}
+
 impl<'a> taffy::traits::TraverseTree for NativeTree<'a> {}
 
 impl<'a> taffy::LayoutPartialTree for NativeTree<'a> {
@@ -218,12 +326,19 @@ impl<'a> taffy::traits::PrintTree for NativeTree<'a> {
 
         match node.component.as_ref() {
             NativeComponent::Button(_) => "button",
+            NativeComponent::Toggle(_) => "toggle",
+            NativeComponent::Slider(_) => "slider",
+            NativeComponent::Picker(_) => "picker",
+            NativeComponent::ProgressBar(_) => "progress bar",
+            NativeComponent::ActivityIndicator(_) => "activity indicator",
             NativeComponent::Custom(_) => "custom",
             NativeComponent::View(_) => "view",
             NativeComponent::ImageView(_) => "image view",
             NativeComponent::ListView(_) => "list view",
+            NativeComponent::Modal(_) => "modal",
             NativeComponent::ScrollView(_) => "scroll view",
             NativeComponent::StackNavigator(_) => "stack navigator",
+            NativeComponent::TabNavigator(_) => "tab navigator",
             NativeComponent::Text(_) => "text",
             NativeComponent::TextInput(_) => "text input",
             NativeComponent::TextEdit(_) => "text edit",
@@ -308,6 +423,25 @@ fn measuring_function(
         NativeComponent::Button(b) => {
             button_measuring_function(context, b, style, known_dimensions, available_space)
         }
+        NativeComponent::Toggle(t) => {
+            toggle_measuring_function(context, t, style, known_dimensions, available_space)
+        }
+        NativeComponent::Slider(s) => {
+            slider_measuring_function(context, s, style, known_dimensions, available_space)
+        }
+        NativeComponent::Picker(p) => {
+            picker_measuring_function(context, p, style, known_dimensions, available_space)
+        }
+        NativeComponent::ProgressBar(p) => {
+            progress_bar_measuring_function(context, p, style, known_dimensions, available_space)
+        }
+        NativeComponent::ActivityIndicator(a) => activity_indicator_measuring_function(
+            context,
+            a,
+            style,
+            known_dimensions,
+            available_space,
+        ),
         NativeComponent::Custom(c) => {
             custom_measuring_function(c.as_ref(), style, known_dimensions, available_space)
         }
@@ -318,8 +452,10 @@ fn measuring_function(
             list_view_measuring_function(context, l, style, known_dimensions, available_space)
         }
         NativeComponent::ScrollView(_) => Size::ZERO,
+        NativeComponent::Modal(_) => Size::ZERO,
         NativeComponent::View(_) => Size::ZERO,
         NativeComponent::StackNavigator(_) => Size::ZERO,
+        NativeComponent::TabNavigator(_) => Size::ZERO,
         NativeComponent::Text(t) => {
             text_measuring_function(context, t, style, known_dimensions, available_space)
         }
@@ -390,6 +526,300 @@ fn button_measuring_function(
     return Size { width, height };
 }
 
+fn toggle_measuring_function(
+    context: &mut Context,
+    toggle: &NativeToggle,
+    _style: &StyleRef,
+    known_dimensions: Size<Option<f32>>,
+    available_space: Size<taffy::AvailableSpace>,
+) -> Size<f32> {
+    let known_width = known_dimensions.width;
+    let known_height = known_dimensions.height;
+
+    if let (Some(w), Some(h)) = (known_width, known_height) {
+        return Size {
+            width: w,
+            height: h,
+        };
+    }
+
+    let known_width = match known_dimensions.width {
+        Some(f) => AvalableSpace::Exact(f),
+        None => match available_space.width {
+            taffy::AvailableSpace::Definite(f) => AvalableSpace::AtMost(f),
+            taffy::AvailableSpace::MaxContent => AvalableSpace::Unknown,
+            taffy::AvailableSpace::MinContent => AvalableSpace::Unknown,
+        },
+    };
+
+    let known_height = match known_dimensions.height {
+        Some(f) => AvalableSpace::Exact(f),
+        None => match available_space.height {
+            taffy::AvailableSpace::Definite(f) => AvalableSpace::AtMost(f),
+            taffy::AvailableSpace::MaxContent => AvalableSpace::Unknown,
+            taffy::AvailableSpace::MinContent => AvalableSpace::Unknown,
+        },
+    };
+
+    let measured = toggle.measure(context, known_width, known_height).unwrap();
+
+    let width = match known_dimensions.width {
+        Some(w) => w,
+        None => match available_space.width {
+            taffy::AvailableSpace::MinContent => measured.min_width,
+            taffy::AvailableSpace::MaxContent => measured.natural_width,
+            taffy::AvailableSpace::Definite(d) => d,
+        },
+    };
+
+    let height = match known_dimensions.height {
+        Some(h) => h,
+        None => match available_space.height {
+            taffy::AvailableSpace::MinContent => measured.min_height,
+            taffy::AvailableSpace::MaxContent => measured.natural_height,
+            taffy::AvailableSpace::Definite(d) => d,
+        },
+    };
+
+    return Size { width, height };
+}
+
+fn slider_measuring_function(
+    context: &mut Context,
+    slider: &NativeSlider,
+    _style: &StyleRef,
+    known_dimensions: Size<Option<f32>>,
+    available_space: Size<taffy::AvailableSpace>,
+) -> Size<f32> {
+    let known_width = known_dimensions.width;
+    let known_height = known_dimensions.height;
+
+    if let (Some(w), Some(h)) = (known_width, known_height) {
+        return Size {
+            width: w,
+            height: h,
+        };
+    }
+
+    let known_width = match known_dimensions.width {
+        Some(f) => AvalableSpace::Exact(f),
+        None => match available_space.width {
+            taffy::AvailableSpace::Definite(f) => AvalableSpace::AtMost(f),
+            taffy::AvailableSpace::MaxContent => AvalableSpace::Unknown,
+            taffy::AvailableSpace::MinContent => AvalableSpace::Unknown,
+        },
+    };
+
+    let known_height = match known_dimensions.height {
+        Some(f) => AvalableSpace::Exact(f),
+        None => match available_space.height {
+            taffy::AvailableSpace::Definite(f) => AvalableSpace::AtMost(f),
+            taffy::AvailableSpace::MaxContent => AvalableSpace::Unknown,
+            taffy::AvailableSpace::MinContent => AvalableSpace::Unknown,
+        },
+    };
+
+    let measured = slider.measure(context, known_width, known_height).unwrap();
+
+    let width = match known_dimensions.width {
+        Some(w) => w,
+        None => match available_space.width {
+            taffy::AvailableSpace::MinContent => measured.min_width,
+            taffy::AvailableSpace::MaxContent => measured.natural_width,
+            taffy::AvailableSpace::Definite(d) => d,
+        },
+    };
+
+    let height = match known_dimensions.height {
+        Some(h) => h,
+        None => match available_space.height {
+            taffy::AvailableSpace::MinContent => measured.min_height,
+            taffy::AvailableSpace::MaxContent => measured.natural_height,
+            taffy::AvailableSpace::Definite(d) => d,
+        },
+    };
+
+    return Size { width, height };
+}
+
+fn picker_measuring_function(
+    context: &mut Context,
+    picker: &NativePicker,
+    _style: &StyleRef,
+    known_dimensions: Size<Option<f32>>,
+    available_space: Size<taffy::AvailableSpace>,
+) -> Size<f32> {
+    let known_width = known_dimensions.width;
+    let known_height = known_dimensions.height;
+
+    if let (Some(w), Some(h)) = (known_width, known_height) {
+        return Size {
+            width: w,
+            height: h,
+        };
+    }
+
+    let known_width = match known_dimensions.width {
+        Some(f) => AvalableSpace::Exact(f),
+        None => match available_space.width {
+            taffy::AvailableSpace::Definite(f) => AvalableSpace::AtMost(f),
+            taffy::AvailableSpace::MaxContent => AvalableSpace::Unknown,
+            taffy::AvailableSpace::MinContent => AvalableSpace::Unknown,
+        },
+    };
+
+    let known_height = match known_dimensions.height {
+        Some(f) => AvalableSpace::Exact(f),
+        None => match available_space.height {
+            taffy::AvailableSpace::Definite(f) => AvalableSpace::AtMost(f),
+            taffy::AvailableSpace::MaxContent => AvalableSpace::Unknown,
+            taffy::AvailableSpace::MinContent => AvalableSpace::Unknown,
+        },
+    };
+
+    let measured = picker.measure(context, known_width, known_height).unwrap();
+
+    let width = match known_dimensions.width {
+        Some(w) => w,
+        None => match available_space.width {
+            taffy::AvailableSpace::MinContent => measured.min_width,
+            taffy::AvailableSpace::MaxContent => measured.natural_width,
+            taffy::AvailableSpace::Definite(d) => d,
+        },
+    };
+
+    let height = match known_dimensions.height {
+        Some(h) => h,
+        None => match available_space.height {
+            taffy::AvailableSpace::MinContent => measured.min_height,
+            taffy::AvailableSpace::MaxContent => measured.natural_height,
+            taffy::AvailableSpace::Definite(d) => d,
+        },
+    };
+
+    return Size { width, height };
+}
+
+fn progress_bar_measuring_function(
+    context: &mut Context,
+    progress_bar: &NativeProgressBar,
+    _style: &StyleRef,
+    known_dimensions: Size<Option<f32>>,
+    available_space: Size<taffy::AvailableSpace>,
+) -> Size<f32> {
+    let known_width = known_dimensions.width;
+    let known_height = known_dimensions.height;
+
+    if let (Some(w), Some(h)) = (known_width, known_height) {
+        return Size {
+            width: w,
+            height: h,
+        };
+    }
+
+    let known_width = match known_dimensions.width {
+        Some(f) => AvalableSpace::Exact(f),
+        None => match available_space.width {
+            taffy::AvailableSpace::Definite(f) => AvalableSpace::AtMost(f),
+            taffy::AvailableSpace::MaxContent => AvalableSpace::Unknown,
+            taffy::AvailableSpace::MinContent => AvalableSpace::Unknown,
+        },
+    };
+
+    let known_height = match known_dimensions.height {
+        Some(f) => AvalableSpace::Exact(f),
+        None => match available_space.height {
+            taffy::AvailableSpace::Definite(f) => AvalableSpace::AtMost(f),
+            taffy::AvailableSpace::MaxContent => AvalableSpace::Unknown,
+            taffy::AvailableSpace::MinContent => AvalableSpace::Unknown,
+        },
+    };
+
+    let measured = progress_bar
+        .measure(context, known_width, known_height)
+        .unwrap();
+
+    let width = match known_dimensions.width {
+        Some(w) => w,
+        None => match available_space.width {
+            taffy::AvailableSpace::MinContent => measured.min_width,
+            taffy::AvailableSpace::MaxContent => measured.natural_width,
+            taffy::AvailableSpace::Definite(d) => d,
+        },
+    };
+
+    let height = match known_dimensions.height {
+        Some(h) => h,
+        None => match available_space.height {
+            taffy::AvailableSpace::MinContent => measured.min_height,
+            taffy::AvailableSpace::MaxContent => measured.natural_height,
+            taffy::AvailableSpace::Definite(d) => d,
+        },
+    };
+
+    return Size { width, height };
+}
+
+fn activity_indicator_measuring_function(
+    context: &mut Context,
+    activity_indicator: &NativeActivityIndicator,
+    _style: &StyleRef,
+    known_dimensions: Size<Option<f32>>,
+    available_space: Size<taffy::AvailableSpace>,
+) -> Size<f32> {
+    let known_width = known_dimensions.width;
+    let known_height = known_dimensions.height;
+
+    if let (Some(w), Some(h)) = (known_width, known_height) {
+        return Size {
+            width: w,
+            height: h,
+        };
+    }
+
+    let known_width = match known_dimensions.width {
+        Some(f) => AvalableSpace::Exact(f),
+        None => match available_space.width {
+            taffy::AvailableSpace::Definite(f) => AvalableSpace::AtMost(f),
+            taffy::AvailableSpace::MaxContent => AvalableSpace::Unknown,
+            taffy::AvailableSpace::MinContent => AvalableSpace::Unknown,
+        },
+    };
+
+    let known_height = match known_dimensions.height {
+        Some(f) => AvalableSpace::Exact(f),
+        None => match available_space.height {
+            taffy::AvailableSpace::Definite(f) => AvalableSpace::AtMost(f),
+            taffy::AvailableSpace::MaxContent => AvalableSpace::Unknown,
+            taffy::AvailableSpace::MinContent => AvalableSpace::Unknown,
+        },
+    };
+
+    let measured = activity_indicator
+        .measure(context, known_width, known_height)
+        .unwrap();
+
+    let width = match known_dimensions.width {
+        Some(w) => w,
+        None => match available_space.width {
+            taffy::AvailableSpace::MinContent => measured.min_width,
+            taffy::AvailableSpace::MaxContent => measured.natural_width,
+            taffy::AvailableSpace::Definite(d) => d,
+        },
+    };
+
+    let height = match known_dimensions.height {
+        Some(h) => h,
+        None => match available_space.height {
+            taffy::AvailableSpace::MinContent => measured.min_height,
+            taffy::AvailableSpace::MaxContent => measured.natural_height,
+            taffy::AvailableSpace::Definite(d) => d,
+        },
+    };
+
+    return Size { width, height };
+}
+
 fn image_view_measuring_function(
     context: &mut Context,
     image_view: &NativeImageView,
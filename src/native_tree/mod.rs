@@ -1,14 +1,20 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use parking_lot::Mutex;
+
 use crate::custom::NativeCustomElement;
 use crate::imp::{
-    NativeButton, NativeImageView, NativeListView, NativeScrollView, NativeStackNavigator,
-    NativeText, NativeTextEdit, NativeTextInput, NativeView,
+    NativeActivityIndicator, NativeButton, NativeImageView, NativeListView,
+    NativeModal, NativeNavigator, NativePicker, NativeProgressBar, NativeScrollView, NativeSlider, NativeStackNavigator,
+    NativeText, NativeTextEdit, NativeTextInput, NativeToggle, NativeView,
 };
 use crate::shadow_tree::{command::Command, NodeID};
+use crate::widget::tab_navigator::TabBarPosition;
 
 pub(crate) mod context;
+pub(crate) mod event;
+pub(crate) mod focus;
 pub(crate) mod layout;
 pub(crate) mod node;
 pub(crate) mod style;
@@ -25,6 +31,12 @@ pub struct NativeTree<'a> {
     nodes: HashMap<NodeID, NativeNode>,
     root: Option<NodeID>,
     context: Option<&'a mut Context<'a>>,
+    /// nodes under the pointer, innermost first
+    hovered: Mutex<Vec<NodeID>>,
+    /// node with the keyboard focus
+    focused: Mutex<Option<NodeID>>,
+    /// modal nodes in the order they are created, later modals are shown above
+    modals: Vec<NodeID>,
 }
 
 unsafe impl<'a> Sync for NativeTree<'a> {}
@@ -32,12 +44,16 @@ unsafe impl<'a> Send for NativeTree<'a> {}
 
 impl<'a> NativeTree<'a> {
     /// should only be called from the main thread
+    #[cfg(not(feature = "headless"))]
     pub(crate) fn get<'b>(ctx: *mut Context<'b>) -> &'static mut Self {
         lazy_static::lazy_static! {
             static ref NATIVE_TREE: NativeTree<'static> = NativeTree {
                 nodes: HashMap::new(),
                 root: None,
                 context: None,
+                hovered: Mutex::new(Vec::new()),
+                focused: Mutex::new(None),
+                modals: Vec::new(),
             };
         };
 
@@ -52,6 +68,19 @@ impl<'a> NativeTree<'a> {
         }
     }
 
+    /// creates a tree that is not shared with the main thread
+    #[cfg(feature = "headless")]
+    pub(crate) fn new(ctx: &'a mut Context<'a>) -> Self {
+        Self {
+            nodes: HashMap::new(),
+            root: None,
+            context: Some(ctx),
+            hovered: Mutex::new(Vec::new()),
+            focused: Mutex::new(None),
+            modals: Vec::new(),
+        }
+    }
+
     pub fn context(&self) -> &mut Context {
         unsafe {
             self.context
@@ -65,12 +94,42 @@ impl<'a> NativeTree<'a> {
         }
     }
 
+    #[cfg(not(feature = "headless"))]
     pub fn get_root_node(&self) -> Option<Arc<NativeComponent>> {
         let id = self.root?;
         let node = self.nodes.get(&id).expect("invalid node id");
         return Some(node.component.clone());
     }
 
+    /// the modals presented in their own layer, bottom first
+    #[cfg(not(feature = "headless"))]
+    pub fn get_modal_nodes(&self) -> Vec<Arc<NativeComponent>> {
+        self.modals
+            .iter()
+            .map(|id| self.nodes.get(id).expect("invalid node id").component.clone())
+            .collect()
+    }
+
+    /// the topmost visible modal, events are delivered to it instead of the root
+    pub(crate) fn top_modal(&self) -> Option<NodeID> {
+        self.modals.iter().rev().copied().find(|id| {
+            match self.nodes.get(id).map(|n| n.component.as_ref()) {
+                Some(NativeComponent::Modal(m)) => m.is_visible(self.context()),
+                _ => false,
+            }
+        })
+    }
+
+    /// returns true if the node is laid out in a modal layer instead of its parent
+    pub(crate) fn is_modal(&self, id: NodeID) -> bool {
+        self.modals.contains(&id)
+    }
+
+    #[cfg(feature = "headless")]
+    pub(crate) fn get_node(&self, id: NodeID) -> Option<&NativeNode> {
+        self.nodes.get(&id)
+    }
+
     /// aux function to get known button node
     fn get_button(&self, id: NodeID) -> (&NativeNode, &NativeButton) {
         match self.nodes.get(&id) {
@@ -85,6 +144,76 @@ impl<'a> NativeTree<'a> {
         }
     }
 
+    /// aux function to get known toggle node
+    fn get_toggle(&self, id: NodeID) -> (&NativeNode, &NativeToggle) {
+        match self.nodes.get(&id) {
+            Some(node) => {
+                if let NativeComponent::Toggle(t) = node.component.as_ref() {
+                    return (&node, &t);
+                } else {
+                    unreachable!()
+                }
+            }
+            None => unreachable!(),
+        }
+    }
+
+    /// aux function to get known slider node
+    fn get_slider(&self, id: NodeID) -> (&NativeNode, &NativeSlider) {
+        match self.nodes.get(&id) {
+            Some(node) => {
+                if let NativeComponent::Slider(s) = node.component.as_ref() {
+                    return (&node, &s);
+                } else {
+                    unreachable!()
+                }
+            }
+            None => unreachable!(),
+        }
+    }
+
+    /// aux function to get known picker node
+    fn get_picker(&self, id: NodeID) -> (&NativeNode, &NativePicker) {
+        match self.nodes.get(&id) {
+            Some(node) => {
+                if let NativeComponent::Picker(p) = node.component.as_ref() {
+                    return (&node, &p);
+                } else {
+                    unreachable!()
+                }
+            }
+            None => unreachable!(),
+        }
+    }
+
+    /// aux function to get known progress bar node
+    fn get_progress_bar(&self, id: NodeID) -> (&NativeNode, &NativeProgressBar) {
+        match self.nodes.get(&id) {
+            Some(node) => {
+                if let NativeComponent::ProgressBar(p) = node.component.as_ref() {
+                    return (&node, &p);
+                } else {
+                    unreachable!()
+                }
+            }
+            None => unreachable!(),
+        }
+    }
+
+    /// aux function to get known activity indicator node
+    fn get_activity_indicator(&self, id: NodeID) -> (&NativeNode, &NativeActivityIndicator) {
+        match self.nodes.get(&id) {
+            Some(node) => {
+                if let NativeComponent::ActivityIndicator(a) = node.component.as_ref() {
+                    return (&node, &a);
+                } else {
+                    unreachable!()
+                }
+            }
+            None => unreachable!(),
+        }
+    }
+
     /// aux function to get known view node
     fn get_view(&self, id: NodeID) -> (&NativeNode, &NativeView) {
         match self.nodes.get(&id) {
@@ -126,6 +255,20 @@ impl<'a> NativeTree<'a> {
         }
     }
 
+    /// aux function to get known modal node
+    fn get_modal(&self, id: NodeID) -> (&NativeNode, &NativeModal) {
+        match self.nodes.get(&id) {
+            Some(node) => {
+                if let NativeComponent::Modal(m) = node.component.as_ref() {
+                    return (&node, &m);
+                } else {
+                    unreachable!()
+                }
+            }
+            None => unreachable!(),
+        }
+    }
+
     /// aux function to get known text node
     fn get_text(&self, id: NodeID) -> (&NativeNode, &NativeText) {
         match self.nodes.get(&id) {
@@ -154,6 +297,20 @@ impl<'a> NativeTree<'a> {
         }
     }
 
+    /// aux function to get known text edit node
+    fn get_text_edit(&self, id: NodeID) -> (&NativeNode, &NativeTextEdit) {
+        match self.nodes.get(&id) {
+            Some(node) => {
+                if let NativeComponent::TextEdit(t) = node.component.as_ref() {
+                    return (&node, &t);
+                } else {
+                    unreachable!()
+                }
+            }
+            None => unreachable!(),
+        }
+    }
+
     /// aux function to get known stack
     fn get_stack_nav(&self, id: NodeID) -> (&NativeNode, &NativeStackNavigator) {
         match self.nodes.get(&id) {
@@ -168,6 +325,49 @@ impl<'a> NativeTree<'a> {
         }
     }
 
+    /// aux function to get known tab navigator
+    fn get_tab_nav(&self, id: NodeID) -> (&NativeNode, &NativeNavigator) {
+        match self.nodes.get(&id) {
+            Some(node) => {
+                if let NativeComponent::TabNavigator(t) = node.component.as_ref() {
+                    return (&node, &t);
+                } else {
+                    unreachable!()
+                }
+            }
+            None => unreachable!(),
+        }
+    }
+
+    /// the tab bar is not a node, the page is laid out within the padding
+    /// of the navigator with room left for the tab bar
+    fn inset_tab_bar(&mut self, context: &mut Context, id: NodeID) {
+        let node = self.nodes.get_mut(&id).unwrap();
+        let component = node.component.clone();
+
+        let nav = match component.as_ref() {
+            NativeComponent::TabNavigator(nav) => nav,
+            _ => return,
+        };
+
+        let height = nav.tab_bar_height(context);
+
+        let mut layout_style = node.style.to_taffy_style();
+
+        let padding = match nav.tab_bar_position(context) {
+            TabBarPosition::Top => &mut layout_style.padding.top,
+            TabBarPosition::Bottom => &mut layout_style.padding.bottom,
+        };
+
+        *padding = match *padding {
+            taffy::LengthPercentage::Length(l) => taffy::LengthPercentage::Length(l + height),
+            // percentage is resolved during layout, the bar height takes its place
+            taffy::LengthPercentage::Percent(_) => taffy::LengthPercentage::Length(height),
+        };
+
+        node.layout_style = layout_style;
+    }
+
     /// aux function to get known custom node
     fn get_custom(&self, id: NodeID) -> (&NativeNode, &dyn NativeCustomElement) {
         match self.nodes.get(&id) {
@@ -196,15 +396,38 @@ impl<'a> NativeTree<'a> {
                     // should retain if navigator or scroll view is valid
                     if node.component.should_retain(context) {
                         self.nodes.insert(id, node);
+                    } else {
+                        self.forget_focus(id, &node);
+                        self.modals.retain(|m| *m != id);
                     }
                 }
-                Command::SetStyle { node, style } => {
-                    let node = self.nodes.get_mut(&node).unwrap();
+                Command::SetPointerHandlers { node, handlers } => {
+                    let node = self.nodes.get_mut(&node).expect("invalid node id");
+                    node.pointer = handlers;
+                }
+                Command::SetKeyHandlers { node, handlers } => {
+                    let node = self.nodes.get_mut(&node).expect("invalid node id");
+                    node.keyboard = handlers;
+                }
+                Command::SetFocusProps { node: id, focus } => {
+                    // the new handle takes over the focus state of the node
+                    if let Some(handle) = &focus.handle {
+                        handle.set_focused(*self.focused.lock() == Some(id));
+                    }
+
+                    let node = self.nodes.get_mut(&id).expect("invalid node id");
+                    node.focus = focus;
+                }
+                Command::SetStyle { node: id, style } => {
+                    let node = self.nodes.get_mut(&id).unwrap();
 
                     // set the layout style
                     node.layout_style = style.to_taffy_style();
                     // set the style
                     node.style = style;
+
+                    self.inset_tab_bar(context, id);
+                    self.apply_colours(id);
                 }
                 // button commands
                 Command::ButtonCreate { id, style } => {
@@ -229,6 +452,137 @@ impl<'a> NativeTree<'a> {
                     b.set_on_click(context, on_click);
                 }
 
+                Command::ToggleCreate { id, style, kind } => {
+                    self.nodes.insert(
+                        id,
+                        NativeNode::new(
+                            Arc::new(NativeComponent::Toggle(NativeToggle::new(context, kind))),
+                            style,
+                        ),
+                    );
+                }
+                Command::ToggleSetLabel { id, label } => {
+                    let (_node, t) = self.get_toggle(id);
+                    t.set_label(context, &label);
+                }
+                Command::ToggleSetChecked { id, checked } => {
+                    let (_node, t) = self.get_toggle(id);
+                    t.set_checked(context, checked);
+                }
+                Command::ToggleSetDisabled { id, disabled } => {
+                    let (_node, t) = self.get_toggle(id);
+                    t.set_disabled(context, disabled);
+                }
+                Command::ToggleSetOnToggle { id, on_toggle } => {
+                    let (_node, t) = self.get_toggle(id);
+                    t.set_on_toggle(context, on_toggle);
+                }
+
+                Command::SliderCreate { id, style } => {
+                    self.nodes.insert(
+                        id,
+                        NativeNode::new(
+                            Arc::new(NativeComponent::Slider(NativeSlider::new(context))),
+                            style,
+                        ),
+                    );
+                }
+                Command::SliderSetRange { id, min, max } => {
+                    let (_node, s) = self.get_slider(id);
+                    s.set_range(context, min, max);
+                }
+                Command::SliderSetStep { id, step } => {
+                    let (_node, s) = self.get_slider(id);
+                    s.set_step(context, step);
+                }
+                Command::SliderSetValue { id, value } => {
+                    let (_node, s) = self.get_slider(id);
+                    s.set_value(context, value);
+                }
+                Command::SliderSetOrientation { id, orientation } => {
+                    let (_node, s) = self.get_slider(id);
+                    s.set_orientation(context, orientation);
+                }
+                Command::SliderSetOnChange { id, on_change } => {
+                    let (_node, s) = self.get_slider(id);
+                    s.set_on_change(context, on_change);
+                }
+                Command::SliderSetOnRelease { id, on_release } => {
+                    let (_node, s) = self.get_slider(id);
+                    s.set_on_release(context, on_release);
+                }
+
+                Command::PickerCreate { id, style } => {
+                    self.nodes.insert(
+                        id,
+                        NativeNode::new(
+                            Arc::new(NativeComponent::Picker(NativePicker::new(context))),
+                            style,
+                        ),
+                    );
+                }
+                Command::PickerSpliceOptions {
+                    id,
+                    position,
+                    removed,
+                    added,
+                } => {
+                    let (_node, p) = self.get_picker(id);
+                    p.splice_options(context, position, removed, &added);
+                }
+                Command::PickerSetSelected { id, selected } => {
+                    let (_node, p) = self.get_picker(id);
+                    p.set_selected(context, selected);
+                }
+                Command::PickerSetOnSelect { id, on_select } => {
+                    let (_node, p) = self.get_picker(id);
+                    p.set_on_select(context, on_select);
+                }
+
+                Command::ProgressBarCreate { id, style } => {
+                    self.nodes.insert(
+                        id,
+                        NativeNode::new(
+                            Arc::new(NativeComponent::ProgressBar(NativeProgressBar::new(
+                                context,
+                            ))),
+                            style,
+                        ),
+                    );
+
+                    self.apply_colours(id);
+                }
+                Command::ProgressBarSetFraction { id, fraction } => {
+                    let (_node, p) = self.get_progress_bar(id);
+                    p.set_fraction(context, fraction);
+                }
+                Command::ProgressBarSetIndeterminate { id, indeterminate } => {
+                    let (_node, p) = self.get_progress_bar(id);
+                    p.set_indeterminate(context, indeterminate);
+                }
+                Command::ProgressBarSetText { id, text } => {
+                    let (_node, p) = self.get_progress_bar(id);
+                    p.set_text(context, text.as_deref());
+                }
+
+                Command::ActivityIndicatorCreate { id, style } => {
+                    self.nodes.insert(
+                        id,
+                        NativeNode::new(
+                            Arc::new(NativeComponent::ActivityIndicator(
+                                NativeActivityIndicator::new(context),
+                            )),
+                            style,
+                        ),
+                    );
+
+                    self.apply_colours(id);
+                }
+                Command::ActivityIndicatorSetAnimating { id, animating } => {
+                    let (_node, a) = self.get_activity_indicator(id);
+                    a.set_animating(context, animating);
+                }
+
                 Command::ViewCreate { id, style } => {
                     // create view node
                     self.nodes.insert(
@@ -250,6 +604,23 @@ impl<'a> NativeTree<'a> {
                     let child_node = self.nodes.get_mut(&child).expect("invalid node id");
                     child_node.parent = None;
                 }
+                Command::ViewMoveChild {
+                    id,
+                    child,
+                    from,
+                    to,
+                } => {
+                    let (_node, view) = self.get_view(id);
+                    let child_node = self.nodes.get(&child).expect("invalid node id");
+
+                    // the widget is moved without being removed from the view
+                    view.move_child(context, to, child_node.component.widget());
+
+                    let node = self.nodes.get_mut(&id).expect("invalid node id");
+                    let moved = node.children.remove(from);
+                    debug_assert!(moved == child);
+                    node.children.insert(to, child);
+                }
                 Command::ViewSetChild { id, child, index } => {
                     let (_node, view) = self.get_view(id);
                     let child_node = self.nodes.get(&child).unwrap();
@@ -330,6 +701,57 @@ impl<'a> NativeTree<'a> {
                     }
                 }
 
+                Command::ModalCreate { id, style } => {
+                    self.nodes.insert(
+                        id,
+                        NativeNode::new(
+                            Arc::new(NativeComponent::Modal(NativeModal::new(context))),
+                            style,
+                        ),
+                    );
+                    self.modals.push(id);
+                }
+                Command::ModalSetVisible { id, visible } => {
+                    let (_node, modal) = self.get_modal(id);
+                    NativeModalImp::set_visible(modal, context, visible);
+                }
+                Command::ModalSetTransparent { id, transparent } => {
+                    let (_node, modal) = self.get_modal(id);
+                    modal.set_transparent(context, transparent);
+                }
+                Command::ModalSetOnRequestClose {
+                    id,
+                    on_request_close,
+                } => {
+                    let (_node, modal) = self.get_modal(id);
+                    modal.set_on_request_close(context, on_request_close);
+                }
+                Command::ModalSetChild { id, child } => {
+                    let (_node, modal) = self.get_modal(id);
+                    let child_node = self.nodes.get(&child).expect("invalid node id");
+
+                    modal.set_child(context, child_node.component.widget());
+
+                    let node = self.nodes.get_mut(&id).expect("invalid node id");
+                    node.children.clear();
+                    node.children.push(child);
+
+                    let child_node = self.nodes.get_mut(&child).expect("invalid node id");
+                    child_node.parent = Some(id);
+                }
+                Command::ModalRemoveChild { id } => {
+                    let (_node, modal) = self.get_modal(id);
+                    modal.remove_child(context);
+
+                    let node = self.nodes.get_mut(&id).expect("invalid node id");
+
+                    for child in core::mem::take(&mut node.children) {
+                        if let Some(child_node) = self.nodes.get_mut(&child) {
+                            child_node.parent = None;
+                        }
+                    }
+                }
+
                 Command::TextCreate { id, style, text } => {
                     // create text node
                     self.nodes.insert(
@@ -358,6 +780,30 @@ impl<'a> NativeTree<'a> {
                     let (_node, input) = self.get_text_input(id);
                     input.set_background_text(context, &text);
                 }
+                Command::TextInputSetInputType { id, input_type } => {
+                    let (_node, input) = self.get_text_input(id);
+                    input.set_input_type(context, input_type);
+                }
+                Command::TextInputSetMaxLength { id, max_length } => {
+                    let (_node, input) = self.get_text_input(id);
+                    input.set_max_length(context, max_length);
+                }
+                Command::TextInputSetFormatter { id, formatter } => {
+                    let (_node, input) = self.get_text_input(id);
+                    input.set_formatter(context, formatter);
+                }
+                Command::TextInputSetValue { id, value } => {
+                    let (_node, input) = self.get_text_input(id);
+                    input.set_text(context, &value);
+                }
+                Command::TextInputSetOnChange { id, on_change } => {
+                    let (_node, input) = self.get_text_input(id);
+                    input.set_on_text_changed(context, on_change);
+                }
+                Command::TextInputSetOnSubmit { id, on_submit } => {
+                    let (_node, input) = self.get_text_input(id);
+                    input.set_on_enter_pressed(context, on_submit);
+                }
 
                 Command::TextEditCreate { id, style } => {
                     self.nodes.insert(
@@ -368,6 +814,33 @@ impl<'a> NativeTree<'a> {
                         ),
                     );
                 }
+                Command::TextEditSetText { id, text } => {
+                    let (_node, edit) = self.get_text_edit(id);
+                    edit.set_text(context, &text);
+                }
+                Command::TextEditSetSelection { id, selection } => {
+                    let (_node, edit) = self.get_text_edit(id);
+                    edit.set_selection(context, selection);
+                }
+                Command::TextEditSetReadOnly { id, read_only } => {
+                    let (_node, edit) = self.get_text_edit(id);
+                    edit.set_read_only(context, read_only);
+                }
+                Command::TextEditSetWrapMode { id, mode } => {
+                    let (_node, edit) = self.get_text_edit(id);
+                    edit.set_wrap_mode(context, mode);
+                }
+                Command::TextEditSetOnChange { id, on_change } => {
+                    let (_node, edit) = self.get_text_edit(id);
+                    edit.set_on_text_changed(context, on_change);
+                }
+                Command::TextEditSetOnSelectionChange {
+                    id,
+                    on_selection_change,
+                } => {
+                    let (_node, edit) = self.get_text_edit(id);
+                    edit.set_on_selection_changed(context, on_selection_change);
+                }
 
                 Command::ListViewCreate {
                     id,
@@ -390,6 +863,7 @@ impl<'a> NativeTree<'a> {
                     id,
                     style,
                     command_recv,
+                    history,
                 } => {
                     self.nodes.insert(
                         id,
@@ -397,6 +871,7 @@ impl<'a> NativeTree<'a> {
                             Arc::new(NativeComponent::StackNavigator(NativeStackNavigator::new(
                                 context,
                                 command_recv,
+                                history,
                             ))),
                             style,
                         ),
@@ -435,6 +910,83 @@ impl<'a> NativeTree<'a> {
                     let child_node = self.nodes.get_mut(&child).unwrap();
                     child_node.parent = None;
                 }
+                Command::StackNavigatorSetTransition {
+                    id,
+                    transition,
+                    duration,
+                } => {
+                    let (_node, nav) = self.get_stack_nav(id);
+                    nav.set_transition(context, transition, duration);
+                }
+                Command::StackNavigatorSetPageEvents { id, events } => {
+                    let (_node, nav) = self.get_stack_nav(id);
+                    nav.set_page_events(context, events);
+                }
+
+                Command::TabNavigatorCreate {
+                    id,
+                    style,
+                    command_recv,
+                } => {
+                    self.nodes.insert(
+                        id,
+                        NativeNode::new(
+                            Arc::new(NativeComponent::TabNavigator(NativeNavigator::new(
+                                context,
+                                command_recv,
+                            ))),
+                            style,
+                        ),
+                    );
+
+                    self.inset_tab_bar(context, id);
+                }
+                Command::TabNavigatorSetTabs { id, tabs } => {
+                    let (_node, nav) = self.get_tab_nav(id);
+                    nav.set_tabs(context, &tabs);
+
+                    // the bar height may change with icons
+                    self.inset_tab_bar(context, id);
+                }
+                Command::TabNavigatorSetTabBarPosition { id, position } => {
+                    let (_node, nav) = self.get_tab_nav(id);
+                    nav.set_tab_bar_position(context, position);
+
+                    self.inset_tab_bar(context, id);
+                }
+                Command::TabNavigatorSetOnTabChange { id, on_tab_change } => {
+                    let (_node, nav) = self.get_tab_nav(id);
+                    nav.set_on_tab_change(context, on_tab_change);
+                }
+                Command::TabNavigatorAddChild { id, child, name } => {
+                    let (_node, nav) = self.get_tab_nav(id);
+
+                    nav.add_child(
+                        context,
+                        self.nodes.get(&child).unwrap().component.widget(),
+                        &name,
+                        child,
+                    );
+
+                    let node = self.nodes.get_mut(&id).unwrap();
+                    node.children.push(child);
+
+                    let child_node = self.nodes.get_mut(&child).unwrap();
+                    child_node.parent = Some(id);
+                }
+                Command::TabNavigatorRemoveChild { id, child, name } => {
+                    let (_node, nav) = self.get_tab_nav(id);
+
+                    nav.remove_child(context, &name);
+
+                    let node = self.nodes.get_mut(&id).unwrap();
+                    node.children.retain(|c| *c != child);
+
+                    // a replaced page may already be removed
+                    if let Some(child_node) = self.nodes.get_mut(&child) {
+                        child_node.parent = None;
+                    }
+                }
 
                 Command::CustomCreate {
                     id,
@@ -453,6 +1005,12 @@ impl<'a> NativeTree<'a> {
                     let (_node, custom) = self.get_custom(id);
                     custom.commit_custom_changes(changes);
                 }
+
+                Command::Lifecycle { node: _, callbacks } => {
+                    for cb in callbacks {
+                        cb();
+                    }
+                }
             }
         }
     }
@@ -1,9 +1,12 @@
 use std::sync::Arc;
 
 use crate::custom::NativeCustomElement;
+use crate::event::{KeyHandlers, PointerHandlers};
+use crate::focus::FocusProps;
 use crate::imp::{
-    NativeButton, NativeElement, NativeImageView, NativeListView, NativeScrollView,
-    NativeStackNavigator, NativeText, NativeTextEdit, NativeTextInput, NativeView,
+    NativeActivityIndicator, NativeButton, NativeElement, NativeImageView, NativeListView,
+    NativeModal, NativeNavigator, NativePicker, NativeProgressBar, NativeScrollView, NativeSlider,
+    NativeStackNavigator, NativeText, NativeTextEdit, NativeTextInput, NativeToggle, NativeView,
 };
 use crate::shadow_tree::NodeID;
 use crate::style::StyleRef;
@@ -16,13 +19,20 @@ pub enum NativeComponent {
     ImageView(NativeImageView),
     ScrollView(NativeScrollView),
     ListView(NativeListView),
+    Modal(NativeModal),
 
     Button(NativeButton),
+    Toggle(NativeToggle),
+    Slider(NativeSlider),
+    Picker(NativePicker),
+    ProgressBar(NativeProgressBar),
+    ActivityIndicator(NativeActivityIndicator),
     Text(NativeText),
     TextInput(NativeTextInput),
     TextEdit(NativeTextEdit),
 
     StackNavigator(NativeStackNavigator),
+    TabNavigator(NativeNavigator),
 
     Custom(Box<dyn NativeCustomElement>),
 }
@@ -34,18 +44,48 @@ impl NativeComponent {
             Self::ImageView(i) => i,
             Self::ScrollView(s) => s,
             Self::ListView(v) => v,
+            Self::Modal(m) => m,
             Self::Button(b) => b,
+            Self::Toggle(t) => t,
+            Self::Slider(s) => s,
+            Self::Picker(p) => p,
+            Self::ProgressBar(p) => p,
+            Self::ActivityIndicator(a) => a,
             Self::Text(t) => t,
             Self::TextInput(t) => t,
             Self::TextEdit(t) => t,
             Self::StackNavigator(s) => s,
+            Self::TabNavigator(t) => t,
             Self::Custom(c) => c.as_native_element(),
         }
     }
 
+    /// the widget if it can receive the keyboard focus
+    pub fn focusable(&self) -> Option<&dyn NativeFocusableImp> {
+        match self {
+            Self::Button(b) => Some(b),
+            Self::Toggle(t) => Some(t),
+            Self::Slider(s) => Some(s),
+            Self::Picker(p) => Some(p),
+            Self::TextInput(t) => Some(t),
+            Self::TextEdit(t) => Some(t),
+            _ => None,
+        }
+    }
+
+    /// the widget if it is drawn with the colours of its style
+    pub fn coloured(&self) -> Option<&dyn NativeStyledElement> {
+        match self {
+            Self::ProgressBar(p) => Some(p),
+            Self::ActivityIndicator(a) => Some(a),
+            _ => None,
+        }
+    }
+
     pub fn should_retain(&self, context: &mut Context) -> bool {
         match self {
             Self::StackNavigator(s) => s.should_retain(context),
+            Self::TabNavigator(t) => t.should_retain(context),
             _ => false,
         }
     }
@@ -62,7 +102,9 @@ impl NativeComponent {
         match self {
             Self::View(v) => v.layout_child(context, child.widget(), x, y, width, height),
             Self::ScrollView(s) => s.layout_child(context, child.widget(), x, y, width, height),
+            Self::Modal(m) => m.layout_child(context, child.widget(), x, y, width, height),
             Self::StackNavigator(s) => s.layout_child(context, child.widget(), x, y, width, height),
+            Self::TabNavigator(t) => t.layout_child(context, child.widget(), x, y, width, height),
             _ => todo!(),
         }
     }
@@ -73,6 +115,9 @@ pub struct NativeNode {
     pub children: Vec<NodeID>,
     pub component: Arc<NativeComponent>,
     pub style: StyleRef,
+    pub pointer: PointerHandlers,
+    pub keyboard: KeyHandlers,
+    pub focus: FocusProps,
 
     pub layout_style: taffy::Style,
     pub cache: taffy::Cache,
@@ -88,6 +133,9 @@ impl NativeNode {
             children: Vec::new(),
             style: style,
             component: component,
+            pointer: PointerHandlers::default(),
+            keyboard: KeyHandlers::default(),
+            focus: FocusProps::default(),
 
             layout_style: layout_style,
             cache: taffy::Cache::new(),
@@ -30,4 +30,22 @@ impl<'a> NativeTree<'a> {
             );
         }
     }
+
+    /// applies the colours of the style to the widgets drawn with them
+    pub(crate) fn apply_colours(&self, node_id: NodeID) {
+        let node = self.nodes.get(&node_id).expect("invalid node id");
+
+        let widget = match node.component.coloured() {
+            Some(widget) => widget,
+            None => return,
+        };
+
+        if let Some(colour) = node.style.get_colour() {
+            widget.set_colour(colour);
+        }
+
+        if let Some(colour) = node.style.get_background_colour() {
+            widget.set_background_colour(colour);
+        }
+    }
 }
@@ -1,9 +1,12 @@
+use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crossbeam_channel::Receiver;
+use parking_lot::RwLock;
 
 use crate::image::ImageSource;
-use crate::shadow_tree::component::NavigatorCommand;
+use crate::shadow_tree::component::{NavigatorCommand, ToggleKind};
 use crate::shadow_tree::NodeID;
 use crate::style::{
     BorderStyle, Colour, FontStyle, FontWeight, PointEvents, TextAlign, TextDecorationLine,
@@ -13,20 +16,86 @@ use crate::style::{
 use crate::imp::NativeElement;
 use crate::util::Comparable;
 use crate::widget::flatlist::{ListViewDataSourceWrapper, ListViewWidgetFactoryWrapper};
+use crate::widget::input::{InputFormatter, InputType};
+use crate::widget::slider::Orientation;
+use crate::widget::stack_navigator::{PageEvents, StackNavigatorTransition};
+use crate::widget::tab_navigator::{Tab, TabBarPosition};
+use crate::widget::text_edit::WrapMode;
 
 use super::context::Context;
 
-pub trait NativeButtonImp: NativeElement + NativeStyledElement {
+/// implemented by widgets that can receive the keyboard focus
+pub trait NativeFocusableImp {
+    /// move the keyboard focus to the widget, returns false if the widget can not be focused
+    fn focus(&self, ctx: &mut Context) -> bool;
+    /// remove the keyboard focus from the widget
+    fn blur(&self, ctx: &mut Context);
+}
+
+pub trait NativeButtonImp: NativeElement + NativeStyledElement + NativeFocusableImp {
     fn new(ctx: &mut Context) -> Self;
     fn set_disabled(&self, ctx: &mut Context, disabled: bool);
     fn set_label(&self, ctx: &mut Context, text: String);
     fn set_on_click(&self, ctx: &mut Context, on_click: Option<Arc<dyn Fn() + Send + Sync>>);
 }
 
+/// a checkbox or a switch with an optional label, the kind is fixed at creation
+pub trait NativeToggleImp: NativeElement + NativeStyledElement + NativeFocusableImp {
+    fn new(ctx: &mut Context, kind: ToggleKind) -> Self;
+    fn set_label(&self, ctx: &mut Context, label: &str);
+    /// check or uncheck the toggle, the toggle callback is not invoked
+    fn set_checked(&self, ctx: &mut Context, checked: bool);
+    fn set_disabled(&self, ctx: &mut Context, disabled: bool);
+    /// the callback is invoked with the new state when the user toggles it
+    fn set_on_toggle(&self, ctx: &mut Context, on_toggle: Option<Arc<dyn Fn(bool) + Send + Sync>>);
+}
+
+pub trait NativeSliderImp: NativeElement + NativeStyledElement + NativeFocusableImp {
+    fn new(ctx: &mut Context) -> Self;
+    fn set_range(&self, ctx: &mut Context, min: f64, max: f64);
+    /// zero for a continuous slider
+    fn set_step(&self, ctx: &mut Context, step: f64);
+    /// move the slider, the change callback is not invoked
+    fn set_value(&self, ctx: &mut Context, value: f64);
+    fn set_orientation(&self, ctx: &mut Context, orientation: Orientation);
+    /// the callback is invoked with the new value while the user moves the slider
+    fn set_on_change(&self, ctx: &mut Context, on_change: Option<Arc<dyn Fn(f64) + Send + Sync>>);
+    /// the callback is invoked with the value once the user releases the slider
+    fn set_on_release(&self, ctx: &mut Context, on_release: Option<Arc<dyn Fn(f64) + Send + Sync>>);
+}
+
+pub trait NativePickerImp: NativeElement + NativeStyledElement + NativeFocusableImp {
+    fn new(ctx: &mut Context) -> Self;
+    /// replace `removed` options from `position` with the `added` options.
+    /// the select callback is not invoked
+    fn splice_options(&self, ctx: &mut Context, position: usize, removed: usize, added: &[String]);
+    /// select an option, the select callback is not invoked
+    fn set_selected(&self, ctx: &mut Context, selected: Option<usize>);
+    /// the callback is invoked with the index of the option picked by the user
+    fn set_on_select(&self, ctx: &mut Context, on_select: Option<Arc<dyn Fn(usize) + Send + Sync>>);
+}
+
+pub trait NativeProgressBarImp: NativeElement + NativeStyledElement {
+    fn new(ctx: &mut Context) -> Self;
+    /// the completed fraction from 0 to 1
+    fn set_fraction(&self, ctx: &mut Context, fraction: f64);
+    /// pulse the bar instead of showing the fraction
+    fn set_indeterminate(&self, ctx: &mut Context, indeterminate: bool);
+    /// the text is hidden if none
+    fn set_text(&self, ctx: &mut Context, text: Option<&str>);
+}
+
+pub trait NativeActivityIndicatorImp: NativeElement + NativeStyledElement {
+    fn new(ctx: &mut Context) -> Self;
+    fn set_animating(&self, ctx: &mut Context, animating: bool);
+}
+
 pub trait NativeViewImp: NativeElement + NativeStyledElement {
     fn new(ctx: &mut Context) -> Self;
     fn insert_child(&self, ctx: &mut Context, index: usize, elem: &dyn NativeElement);
     fn remove_child(&self, ctx: &mut Context, elem: &dyn NativeElement);
+    /// move an existing child to `index`, the child should keep its state
+    fn move_child(&self, ctx: &mut Context, index: usize, elem: &dyn NativeElement);
     fn layout_child(
         &self,
         ctx: &mut Context,
@@ -44,6 +113,34 @@ pub trait NativeImageViewImp: NativeElement + NativeStyledElement {
     fn check_update(&self);
 }
 
+/// A modal shows its child in a layer above the window content.
+/// the modal itself is a placeholder in its parent, the layer is presented by the app.
+pub trait NativeModalImp: NativeElement + NativeStyledElement {
+    fn new(ctx: &mut Context) -> Self;
+    fn set_visible(&self, ctx: &mut Context, visible: bool);
+    fn is_visible(&self, ctx: &mut Context) -> bool;
+    /// a transparent modal does not dim the content below
+    fn set_transparent(&self, ctx: &mut Context, transparent: bool);
+    fn set_child(&self, ctx: &mut Context, child: &dyn NativeElement);
+    fn remove_child(&self, ctx: &mut Context);
+    fn layout_child(
+        &self,
+        ctx: &mut Context,
+        child: &dyn NativeElement,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    );
+    fn set_on_request_close(
+        &self,
+        ctx: &mut Context,
+        on_request_close: Option<Arc<dyn Fn() + Send + Sync>>,
+    );
+    /// called when the backdrop is pressed or Escape is pressed
+    fn request_close(&self, ctx: &mut Context);
+}
+
 pub trait NativeScrollViewImp: NativeElement + NativeStyledElement {
     fn new(ctx: &mut Context) -> Self;
     fn set_child(&self, ctx: &mut Context, child: &dyn NativeElement);
@@ -70,16 +167,49 @@ pub trait NativeTextImp: NativeElement + NativeStyledElement {
     fn set_text(&self, ctx: &mut Context, text: &str);
 }
 
-pub trait NativeTextInputImp: NativeElement + NativeStyledElement {
+pub trait NativeTextInputImp: NativeElement + NativeStyledElement + NativeFocusableImp {
     fn new(ctx: &mut Context) -> Self;
     fn set_background_text(&self, ctx: &mut Context, text: &str);
-    fn set_on_text_changed(&self, ctx: &mut Context, callback: Arc<dyn Fn(&str) + Sync + Send>);
-    fn set_on_enter_pressed(&self, ctx: &mut Context, callback: Arc<dyn Fn(&str) + Sync + Send>);
+    fn set_input_type(&self, ctx: &mut Context, input_type: InputType);
+    /// limit the number of characters, none for no limit
+    fn set_max_length(&self, ctx: &mut Context, max_length: Option<usize>);
+    /// the formatter is applied to the text typed by the user before the text changed callback
+    fn set_formatter(&self, ctx: &mut Context, formatter: Option<InputFormatter>);
+    /// replace the text, the text changed callback is not invoked.
+    /// does nothing if the text is unchanged, keeping the cursor in place.
+    fn set_text(&self, ctx: &mut Context, text: &str);
+    fn set_on_text_changed(
+        &self,
+        ctx: &mut Context,
+        callback: Option<Arc<dyn Fn(&str) + Sync + Send>>,
+    );
+    fn set_on_enter_pressed(
+        &self,
+        ctx: &mut Context,
+        callback: Option<Arc<dyn Fn(&str) + Sync + Send>>,
+    );
 }
 
-pub trait NativeTextEditImp: NativeElement + NativeStyledElement {
+pub trait NativeTextEditImp: NativeElement + NativeStyledElement + NativeFocusableImp {
     fn new(ctx: &mut Context) -> Self;
-    fn set_on_text_changed(&self, ctx: &mut Context, callback: Arc<dyn Fn(&str) + Sync + Send>);
+    /// replace the content, the text changed callback is not invoked.
+    /// does nothing if the content is unchanged, keeping the cursor in place.
+    fn set_text(&self, ctx: &mut Context, text: &str);
+    /// select a range in characters, the cursor is placed at the end of the range
+    fn set_selection(&self, ctx: &mut Context, selection: Range<usize>);
+    fn set_read_only(&self, ctx: &mut Context, read_only: bool);
+    fn set_wrap_mode(&self, ctx: &mut Context, mode: WrapMode);
+    fn set_on_text_changed(
+        &self,
+        ctx: &mut Context,
+        callback: Option<Arc<dyn Fn(&str) + Sync + Send>>,
+    );
+    /// the callback is invoked whenever the cursor or selection moves
+    fn set_on_selection_changed(
+        &self,
+        ctx: &mut Context,
+        callback: Option<Arc<dyn Fn(Range<usize>) + Sync + Send>>,
+    );
 }
 
 pub trait NativeListViewImp: NativeElement + NativeStyledElement {
@@ -91,7 +221,12 @@ pub trait NativeListViewImp: NativeElement + NativeStyledElement {
 }
 
 pub trait NativeStackNavigatorImp: NativeElement + NativeStyledElement {
-    fn new(ctx: &mut Context, command_recv: Receiver<NavigatorCommand>) -> Self;
+    /// the names of the pages in history are written to `history` after each navigation
+    fn new(
+        ctx: &mut Context,
+        command_recv: Receiver<NavigatorCommand>,
+        history: Arc<RwLock<Vec<String>>>,
+    ) -> Self;
     fn add_child(&self, ctx: &mut Context, child: &dyn NativeElement, name: &str, id: NodeID);
     fn remove_child(&self, ctx: &mut Context, name: &str);
     fn visible_child(&self, ctx: &mut Context) -> Option<NodeID>;
@@ -105,8 +240,22 @@ pub trait NativeStackNavigatorImp: NativeElement + NativeStyledElement {
         height: f32,
     );
     fn should_retain(&self, ctx: &mut Context) -> bool;
+    /// the transition used for navigation, a push may override the transition
+    fn set_transition(
+        &self,
+        ctx: &mut Context,
+        transition: StackNavigatorTransition,
+        duration: Duration,
+    );
+    /// focus and blur are called as the visible page changes,
+    /// going back is cancelled if `on_before_leave` of the visible page returns false
+    fn set_page_events(&self, ctx: &mut Context, events: Vec<(String, PageEvents)>);
+    /// called on every frame, applies the navigation commands received
+    fn check_update(&self);
 }
 
+/// A tab navigator shows the page of the selected tab beside a tab bar.
+/// the tab bar lists every tab, a tab may be selected before its page is added.
 pub trait NativeNavigatorImp: NativeElement + NativeStyledElement {
     fn new(ctx: &mut Context, command_recv: Receiver<NavigatorCommand>) -> Self;
     fn add_child(&self, ctx: &mut Context, child: &dyn NativeElement, name: &str, id: NodeID);
@@ -122,6 +271,19 @@ pub trait NativeNavigatorImp: NativeElement + NativeStyledElement {
         height: f32,
     );
     fn should_retain(&self, ctx: &mut Context) -> bool;
+    fn set_tabs(&self, ctx: &mut Context, tabs: &[Tab]);
+    fn set_tab_bar_position(&self, ctx: &mut Context, position: TabBarPosition);
+    fn tab_bar_position(&self, ctx: &mut Context) -> TabBarPosition;
+    /// height of the tab bar, the pages are laid out below or above it
+    fn tab_bar_height(&self, ctx: &mut Context) -> f32;
+    /// called with the name of the tab once the selected tab changes
+    fn set_on_tab_change(
+        &self,
+        ctx: &mut Context,
+        on_tab_change: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+    );
+    /// called on every frame, applies the navigation commands received
+    fn check_update(&self);
 }
 
 pub enum AvalableSpace {
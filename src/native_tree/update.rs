@@ -1,5 +1,5 @@
 use super::node::NativeComponent;
-use super::{NativeImageViewImp, NativeTree};
+use super::{NativeImageViewImp, NativeNavigatorImp, NativeStackNavigatorImp, NativeTree};
 
 
 
@@ -8,9 +8,14 @@ use super::{NativeImageViewImp, NativeTree};
 
 impl<'a> NativeTree<'a>{
     pub(crate) fn check_update(&self){
+        // apply the focus requested by focus handles
+        self.apply_focus_requests();
+
         for (_, node) in &self.nodes{
             match node.component.as_ref(){
                 NativeComponent::ImageView(im) => im.check_update(),
+                NativeComponent::StackNavigator(s) => s.check_update(),
+                NativeComponent::TabNavigator(t) => t.check_update(),
                 _ => {}
             }
         }
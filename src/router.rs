@@ -0,0 +1,462 @@
+//! Open the pages of navigators from URLs such as `myapp://orders/42`.
+//!
+//! A `Router` maps path patterns to pages of a navigator. A pattern segment starting
+//! with `:` matches any segment, the page receives the matched segments as `RouteParams`.
+//! The page of a nested navigator is addressed by its path from the root navigator,
+//! e.g. `settings/profile` is the page `profile` of the navigator in the page `settings`.
+//!
+//! ```ignore
+//! let router = Router::new(&navigator)
+//!     .with_scheme("myapp")
+//!     .with_route("/", "home")
+//!     .with_route("/orders/:id", "orders/order");
+//!
+//! App::builder().with_router(router).build().unwrap().launch(app);
+//! ```
+//!
+//! The router opens the URL given on the command line at launch,
+//! or the URL given to `AppBuilder::with_url` such as a path saved from `Router::path`.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::widget::navigation::ParentNavigator;
+
+/// Segments matched by the parameters of a route pattern, by parameter name.
+///
+/// Pages opened by a route with parameters read them with `StackNavigator::params`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RouteParams {
+    params: HashMap<String, String>,
+}
+
+impl RouteParams {
+    /// the segment matched by the parameter
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(|s| s.as_str())
+    }
+
+    /// parse the segment matched by the parameter, none if missing or invalid
+    pub fn parse<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.get(name)?.parse().ok()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Static(String),
+    Param(String),
+}
+
+#[derive(Debug, Clone)]
+struct Route {
+    pattern: Vec<Segment>,
+    /// path of the page from the root navigator
+    page: String,
+}
+
+impl Route {
+    /// returns the params if the path matches the pattern
+    fn matches(&self, segments: &[String]) -> Option<RouteParams> {
+        if segments.len() != self.pattern.len() {
+            return None;
+        }
+
+        let mut params = RouteParams::default();
+
+        for (pattern, segment) in self.pattern.iter().zip(segments) {
+            match pattern {
+                Segment::Static(s) if s == segment => {}
+                Segment::Static(_) => return None,
+                Segment::Param(name) => {
+                    params.params.insert(name.clone(), segment.clone());
+                }
+            }
+        }
+
+        return Some(params);
+    }
+
+    /// fill the pattern with the params, none if a param is missing
+    fn format(&self, params: &RouteParams) -> Option<String> {
+        let mut path = String::new();
+
+        for segment in &self.pattern {
+            path.push('/');
+
+            match segment {
+                Segment::Static(s) => path.push_str(s),
+                Segment::Param(name) => path.push_str(&encode(params.get(name)?)),
+            }
+        }
+
+        if path.is_empty() {
+            path.push('/');
+        }
+
+        return Some(path);
+    }
+}
+
+/// Maps URLs to the pages of a `StackNavigator` or `TabNavigator`.
+///
+/// Cloning a `Router` returns a router of the same navigator.
+#[derive(Clone)]
+pub struct Router {
+    navigator: Arc<dyn ParentNavigator>,
+    scheme: Option<String>,
+    routes: Vec<Route>,
+}
+
+impl Router {
+    /// a router without routes opening the pages of the navigator
+    pub fn new<N: ParentNavigator + Clone + 'static>(navigator: &N) -> Self {
+        Self {
+            navigator: Arc::new(navigator.clone()),
+            scheme: None,
+            routes: Vec::new(),
+        }
+    }
+
+    /// the scheme of deep links, `myapp` for `myapp://orders/42`
+    pub fn with_scheme<S: Into<String>>(mut self, scheme: S) -> Self {
+        self.set_scheme(scheme);
+        return self;
+    }
+
+    pub fn set_scheme<S: Into<String>>(&mut self, scheme: S) {
+        self.scheme = Some(scheme.into());
+    }
+
+    /// open the page when the path matches the pattern, e.g. `/orders/:id`.
+    /// routes are matched in the order they are added.
+    pub fn with_route(mut self, pattern: &str, page: &str) -> Self {
+        self.add_route(pattern, page);
+        return self;
+    }
+
+    pub fn add_route(&mut self, pattern: &str, page: &str) {
+        let pattern = segments(pattern)
+            .into_iter()
+            .map(|s| match s.strip_prefix(':') {
+                Some(name) => Segment::Param(name.to_string()),
+                None => Segment::Static(s),
+            })
+            .collect();
+
+        self.routes.push(Route {
+            pattern,
+            page: page.trim_matches('/').to_string(),
+        });
+    }
+
+    /// open the page of the first route matching the url or path,
+    /// returns false if no route matches or the url has another scheme.
+    ///
+    /// the page is opened once the navigators are rendered.
+    pub fn open(&self, url: &str) -> bool {
+        let path = match self.path_of(url) {
+            Some(path) => path,
+            None => return false,
+        };
+
+        let segments = segments(path);
+
+        for route in &self.routes {
+            if let Some(params) = route.matches(&segments) {
+                let params = if params.is_empty() {
+                    None
+                } else {
+                    Some(params)
+                };
+
+                self.navigator.route(&route.page, params);
+
+                return true;
+            }
+        }
+
+        return false;
+    }
+
+    /// the path of the visible page, e.g. `/orders/42`.
+    ///
+    /// the deepest visible page with a route is used,
+    /// none if no visible page has a route.
+    pub fn path(&self) -> Option<String> {
+        let mut pages = Vec::new();
+        let mut params = RouteParams::default();
+        let mut navigator = Some(self.navigator.clone());
+
+        // follow the visible pages through the nested navigators
+        while let Some(nav) = navigator {
+            let (page, page_params) = match nav.location() {
+                Some(location) => location,
+                None => break,
+            };
+
+            if let Some(page_params) = page_params {
+                params.params.extend(page_params.params);
+            }
+
+            navigator = nav.links().child(&page);
+            pages.push(page);
+        }
+
+        for depth in (1..=pages.len()).rev() {
+            let page = pages[..depth].join("/");
+
+            let path = self
+                .routes
+                .iter()
+                .filter(|route| route.page == page)
+                .find_map(|route| route.format(&params));
+
+            if path.is_some() {
+                return path;
+            }
+        }
+
+        return None;
+    }
+
+    /// the url of the visible page with the scheme, e.g. `myapp://orders/42`.
+    /// same as `path` if the router has no scheme.
+    pub fn url(&self) -> Option<String> {
+        let path = self.path()?;
+
+        match &self.scheme {
+            Some(scheme) => Some(format!("{}://{}", scheme, path.trim_start_matches('/'))),
+            None => Some(path),
+        }
+    }
+
+    /// returns true if the url has the scheme of the router
+    pub(crate) fn is_deep_link(&self, url: &str) -> bool {
+        match (&self.scheme, url.split_once("://")) {
+            (Some(scheme), Some((s, _))) => s == scheme,
+            _ => false,
+        }
+    }
+
+    /// the path of the url, none if the url has another scheme
+    fn path_of<'a>(&self, url: &'a str) -> Option<&'a str> {
+        let path = match url.split_once("://") {
+            Some(_) if !self.is_deep_link(url) => return None,
+            Some((_, path)) => path,
+            None => url,
+        };
+
+        // the query and fragment are ignored
+        let end = path.find(['?', '#']).unwrap_or(path.len());
+
+        return Some(&path[..end]);
+    }
+}
+
+impl std::fmt::Debug for Router {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Router")
+            .field("scheme", &self.scheme)
+            .field("routes", &self.routes)
+            .finish()
+    }
+}
+
+/// the decoded non empty segments of the path
+fn segments(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(decode)
+        .collect()
+}
+
+/// percent decode a segment, invalid escapes are kept as is
+fn decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = match bytes.get(i..i + 3) {
+            Some([b'%', h, l]) if h.is_ascii_hexdigit() && l.is_ascii_hexdigit() => {
+                let hex = [*h, *l];
+                u8::from_str_radix(std::str::from_utf8(&hex).unwrap(), 16).ok()
+            }
+            _ => None,
+        };
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    return String::from_utf8_lossy(&decoded).into_owned();
+}
+
+/// percent encode the characters of a segment that have a meaning in urls
+fn encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+
+    for c in segment.chars() {
+        match c {
+            '/' | '?' | '#' | '%' | ' ' => encoded.push_str(&format!("%{:02X}", c as u32)),
+            c => encoded.push(c),
+        }
+    }
+
+    return encoded;
+}
+
+#[test]
+fn test_route_matching() {
+    let route = Route {
+        pattern: vec![
+            Segment::Static("orders".to_string()),
+            Segment::Param("id".to_string()),
+        ],
+        page: "orders/order".to_string(),
+    };
+
+    let params = route.matches(&segments("/orders/42")).unwrap();
+    assert_eq!(params.get("id"), Some("42"));
+    assert_eq!(params.parse::<u32>("id"), Some(42));
+
+    assert!(route.matches(&segments("/orders")).is_none());
+    assert!(route.matches(&segments("/users/42")).is_none());
+
+    // params are encoded and decoded
+    let params = route.matches(&segments("/orders/a%2Fb")).unwrap();
+    assert_eq!(params.get("id"), Some("a/b"));
+    assert_eq!(route.format(&params).as_deref(), Some("/orders/a%2Fb"));
+}
+
+/// an app with the orders navigator nested in the page `orders` of the root navigator
+#[cfg(all(test, feature = "headless"))]
+fn test_app(
+    root: &crate::widget::StackNavigator,
+    orders: &crate::widget::StackNavigator,
+) -> impl crate::app::Application {
+    use crate::widget::Text;
+
+    let (root, orders) = (root.clone(), orders.clone());
+
+    return move || {
+        let o = orders.clone();
+
+        root.navigator()
+            .with_page("home", |_| Text::new("home page"))
+            .with_page("orders", move |_| {
+                o.navigator()
+                    .with_page("list", |_| Text::new("order list"))
+                    .with_page("order", |nav| {
+                        let id = nav
+                            .params::<RouteParams>()
+                            .and_then(|p| p.parse::<u32>("id"))
+                            .unwrap_or_default();
+
+                        Text::new(format!("order {}", id))
+                    })
+            })
+    };
+}
+
+#[cfg(all(test, feature = "headless"))]
+fn test_router(root: &crate::widget::StackNavigator) -> Router {
+    return Router::new(root)
+        .with_scheme("myapp")
+        .with_route("/", "home")
+        .with_route("/orders", "orders/list")
+        .with_route("/orders/:id", "orders/order");
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_router_launch_url() {
+    use crate::app::AppBuilder;
+    use crate::testing::TestRenderer;
+    use crate::widget::StackNavigator;
+
+    let root = StackNavigator::new();
+    let orders = StackNavigator::new();
+    let router = test_router(&root);
+
+    // the navigation state is restored before the first render
+    let builder = AppBuilder::new()
+        .with_router(router.clone())
+        .with_url("/orders/42");
+
+    let mut renderer = TestRenderer::from_builder(builder, test_app(&root, &orders));
+    renderer.step_n(2);
+    assert!(renderer.find_by_text("order 42").is_some());
+    assert_eq!(router.path().as_deref(), Some("/orders/42"));
+    assert_eq!(router.url().as_deref(), Some("myapp://orders/42"));
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_router_rejected_links() {
+    use crate::app::AppBuilder;
+    use crate::testing::TestRenderer;
+    use crate::widget::StackNavigator;
+
+    let root = StackNavigator::new();
+    let orders = StackNavigator::new();
+    let router = test_router(&root);
+
+    let builder = AppBuilder::new().with_router(router.clone());
+    let mut renderer = TestRenderer::from_builder(builder, test_app(&root, &orders));
+    renderer.step();
+
+    // deep links of other schemes or without a route are not opened
+    assert!(!router.open("otherapp://orders/7"));
+    assert!(!router.open("myapp://users/7"));
+    renderer.step_n(2);
+    assert!(renderer.find_by_text("home page").is_some());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_router_open() {
+    use crate::app::AppBuilder;
+    use crate::testing::TestRenderer;
+    use crate::widget::StackNavigator;
+
+    let root = StackNavigator::new();
+    let orders = StackNavigator::new();
+    let router = test_router(&root);
+
+    let builder = AppBuilder::new()
+        .with_router(router.clone())
+        .with_url("/orders/42");
+
+    let mut renderer = TestRenderer::from_builder(builder, test_app(&root, &orders));
+    renderer.step_n(2);
+
+    assert!(router.open("myapp://orders/7"));
+    renderer.step_n(2);
+    assert!(renderer.find_by_text("order 7").is_some());
+    assert_eq!(router.path().as_deref(), Some("/orders/7"));
+
+    // the opened page is pushed onto the nested navigator
+    orders.goback();
+    renderer.step();
+    assert!(renderer.find_by_text("order 42").is_some());
+
+    assert!(router.open("/"));
+    renderer.step();
+    assert!(renderer.find_by_text("home page").is_some());
+    assert_eq!(router.path().as_deref(), Some("/"));
+}
@@ -0,0 +1,149 @@
+use std::any::Any;
+use std::ops::Range;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use crate::event::{KeyEvent, PointerEvent};
+
+/// a callback calling the callback held by a slot
+pub(crate) trait Forward {
+    fn forward(slot: Arc<RwLock<Arc<Self>>>) -> Arc<Self>;
+}
+
+macro_rules! impl_forward {
+    ($(($($arg:ident: $ty:ty),*) $(-> $ret:ty)?;)*) => {$(
+        impl Forward for dyn Fn($($ty),*) $(-> $ret)? + Send + Sync {
+            fn forward(slot: Arc<RwLock<Arc<Self>>>) -> Arc<Self> {
+                Arc::new(move |$($arg: $ty),*| {
+                    // the callback may commit a new callback into the slot
+                    let callback = slot.read().clone();
+                    callback($($arg),*)
+                })
+            }
+        }
+    )*};
+}
+
+impl_forward! {
+    (event: &PointerEvent);
+    (event: &KeyEvent);
+    ();
+    (checked: bool);
+    () -> bool;
+    (value: f64);
+    (index: usize);
+    (text: &str);
+    (text: &str) -> String;
+    (selection: Range<usize>);
+}
+
+/// a callback given to the native node and the callback forwarding to it
+struct CallbackSlot<T: ?Sized> {
+    callback: Arc<RwLock<Arc<T>>>,
+    forward: Arc<T>,
+}
+
+/// Callbacks of the native node by name.
+///
+/// The native node is given a callback forwarding to the slot once,
+/// a commit swaps the latest callback into the slot of the retained node
+/// instead of sending the new callback to the native node.
+#[derive(Default, Clone)]
+pub(crate) struct CallbackSlots {
+    slots: Vec<(String, Arc<dyn Any + Send + Sync>)>,
+}
+
+impl CallbackSlots {
+    /// moves the slot of the callback from the slots of the retained node
+    /// and swaps the callback into it, a slot is created for a new callback.
+    ///
+    /// returns the callback forwarding to the slot, none without callback,
+    /// and true if the native node must be given the forwarding callback.
+    pub fn forward<T>(
+        &mut self,
+        name: &str,
+        callback: &Option<Arc<T>>,
+        old: Option<&CallbackSlots>,
+    ) -> (Option<Arc<T>>, bool)
+    where
+        T: ?Sized + Forward + Send + Sync + 'static,
+    {
+        let old = old.and_then(|old| old.find(name));
+        self.slots.retain(|(n, _)| *n != name);
+
+        let callback = match callback {
+            Some(callback) => callback.clone(),
+            // the callback is removed from the native node
+            None => return (None, old.is_some()),
+        };
+
+        if let Some(slot) = old {
+            if let Some(s) = slot.downcast_ref::<CallbackSlot<T>>() {
+                *s.callback.write() = callback;
+                let forward = s.forward.clone();
+
+                self.slots.push((name.to_string(), slot.clone()));
+                return (Some(forward), false);
+            }
+        }
+
+        let callback = Arc::new(RwLock::new(callback));
+        let forward = T::forward(callback.clone());
+
+        self.slots.push((
+            name.to_string(),
+            Arc::new(CallbackSlot {
+                callback,
+                forward: forward.clone(),
+            }),
+        ));
+
+        return (Some(forward), true);
+    }
+
+    fn find(&self, name: &str) -> Option<&Arc<dyn Any + Send + Sync>> {
+        self.slots.iter().find(|(n, _)| *n == name).map(|(_, s)| s)
+    }
+}
+
+#[test]
+fn test_callback_slots() {
+    use parking_lot::Mutex;
+
+    use crate::event::{PointerCallback, PointerEventKind};
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let callback = |name: &'static str| -> Option<PointerCallback> {
+        let log = log.clone();
+        Some(Arc::new(move |_| log.lock().push(name)))
+    };
+    let event = PointerEvent::new(PointerEventKind::Down, None, 0.0, 0.0);
+
+    let mut first = CallbackSlots::default();
+    let (forward, changed) = first.forward("on_pointer_down", &callback("first"), None);
+    assert!(changed);
+
+    // the latest callback is swapped into the slot of the retained node
+    let mut second = CallbackSlots::default();
+    let (same, changed) = second.forward("on_pointer_down", &callback("second"), Some(&first));
+    assert!(!changed);
+    assert!(Arc::ptr_eq(
+        forward.as_ref().unwrap(),
+        same.as_ref().unwrap()
+    ));
+
+    forward.unwrap()(&event);
+    assert_eq!(*log.lock(), ["second"]);
+
+    // a removed callback is removed from the native node
+    let mut third = CallbackSlots::default();
+    assert!(matches!(
+        third.forward("on_pointer_down", &None::<PointerCallback>, Some(&second)),
+        (None, true)
+    ));
+    assert!(matches!(
+        third.forward("on_pointer_up", &None::<PointerCallback>, Some(&second)),
+        (None, false)
+    ));
+}
@@ -1,15 +1,26 @@
 use std::any::Any;
+use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crossbeam_channel::Receiver;
+use parking_lot::RwLock;
 
+use crate::event::{KeyHandlers, PointerHandlers};
+use crate::focus::FocusProps;
 use crate::image::ImageSource;
 use crate::style::StyleRef;
 use crate::util::Comparable;
 use crate::widget::flatlist::ListViewWidgetFactoryWrapper;
+use crate::widget::input::{InputFormatter, InputType};
+use crate::widget::slider::Orientation;
+use crate::widget::stack_navigator::{PageEvents, StackNavigatorTransition};
+use crate::widget::tab_navigator::{Tab, TabBarPosition};
+use crate::widget::text_edit::WrapMode;
 use crate::{custom::NativeCustomElement, widget::flatlist::ListViewDataSourceWrapper};
 
-use super::{component::NavigatorCommand, NodeID};
+use super::component::{LifecycleCallback, NavigatorCommand, ToggleKind};
+use super::NodeID;
 
 pub enum Command {
     /// mount node as root
@@ -25,6 +36,21 @@ pub enum Command {
         node: NodeID,
         style: StyleRef,
     },
+    /// replace the pointer callbacks of a node
+    SetPointerHandlers {
+        node: NodeID,
+        handlers: PointerHandlers,
+    },
+    /// replace the key callbacks of a node
+    SetKeyHandlers {
+        node: NodeID,
+        handlers: KeyHandlers,
+    },
+    /// replace the focus handle, tab index and focus callbacks of a node
+    SetFocusProps {
+        node: NodeID,
+        focus: FocusProps,
+    },
 
     ViewCreate {
         id: NodeID,
@@ -42,6 +68,13 @@ pub enum Command {
         child: NodeID,
         index: usize,
     },
+    /// move an existing child from index `from` to index `to`
+    ViewMoveChild {
+        id: NodeID,
+        child: NodeID,
+        from: usize,
+        to: usize,
+    },
 
     ImageViewCreate {
         id: NodeID,
@@ -65,6 +98,33 @@ pub enum Command {
         child: NodeID,
     },
 
+    ///////////////////////////////////////
+    /////////   Modal commands   //////////
+    ///////////////////////////////////////
+    ModalCreate {
+        id: NodeID,
+        style: StyleRef,
+    },
+    ModalSetVisible {
+        id: NodeID,
+        visible: bool,
+    },
+    ModalSetTransparent {
+        id: NodeID,
+        transparent: bool,
+    },
+    ModalSetOnRequestClose {
+        id: NodeID,
+        on_request_close: Option<Arc<dyn Fn() + Send + Sync>>,
+    },
+    ModalSetChild {
+        id: NodeID,
+        child: NodeID,
+    },
+    ModalRemoveChild {
+        id: NodeID,
+    },
+
     ///////////////////////////////////////
     /////////   Button commands   /////////
     ///////////////////////////////////////
@@ -85,6 +145,119 @@ pub enum Command {
         disabled: bool,
     },
 
+    ///////////////////////////////////////
+    /////////   Toggle commands   /////////
+    ///////////////////////////////////////
+    ToggleCreate {
+        id: NodeID,
+        style: StyleRef,
+        kind: ToggleKind,
+    },
+    ToggleSetLabel {
+        id: NodeID,
+        label: String,
+    },
+    ToggleSetChecked {
+        id: NodeID,
+        checked: bool,
+    },
+    ToggleSetDisabled {
+        id: NodeID,
+        disabled: bool,
+    },
+    ToggleSetOnToggle {
+        id: NodeID,
+        on_toggle: Option<Arc<dyn Fn(bool) + Send + Sync>>,
+    },
+
+    ///////////////////////////////////////
+    /////////   Slider commands   /////////
+    ///////////////////////////////////////
+    SliderCreate {
+        id: NodeID,
+        style: StyleRef,
+    },
+    SliderSetRange {
+        id: NodeID,
+        min: f64,
+        max: f64,
+    },
+    SliderSetStep {
+        id: NodeID,
+        step: f64,
+    },
+    SliderSetValue {
+        id: NodeID,
+        value: f64,
+    },
+    SliderSetOrientation {
+        id: NodeID,
+        orientation: Orientation,
+    },
+    SliderSetOnChange {
+        id: NodeID,
+        on_change: Option<Arc<dyn Fn(f64) + Send + Sync>>,
+    },
+    SliderSetOnRelease {
+        id: NodeID,
+        on_release: Option<Arc<dyn Fn(f64) + Send + Sync>>,
+    },
+
+    ///////////////////////////////////////
+    /////////   Picker commands   /////////
+    ///////////////////////////////////////
+    PickerCreate {
+        id: NodeID,
+        style: StyleRef,
+    },
+    /// replace `removed` options from `position` with the `added` options
+    PickerSpliceOptions {
+        id: NodeID,
+        position: usize,
+        removed: usize,
+        added: Vec<String>,
+    },
+    PickerSetSelected {
+        id: NodeID,
+        selected: Option<usize>,
+    },
+    PickerSetOnSelect {
+        id: NodeID,
+        on_select: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    },
+
+    /////////////////////////////////////////////
+    /////////   Progress bar commands   /////////
+    /////////////////////////////////////////////
+    ProgressBarCreate {
+        id: NodeID,
+        style: StyleRef,
+    },
+    ProgressBarSetFraction {
+        id: NodeID,
+        fraction: f64,
+    },
+    ProgressBarSetIndeterminate {
+        id: NodeID,
+        indeterminate: bool,
+    },
+    ProgressBarSetText {
+        id: NodeID,
+        text: Option<String>,
+    },
+
+    ///////////////////////////////////////////////////
+    /////////   Activity indicator commands   /////////
+    ///////////////////////////////////////////////////
+    ActivityIndicatorCreate {
+        id: NodeID,
+        style: StyleRef,
+    },
+    ActivityIndicatorSetAnimating {
+        id: NodeID,
+        animating: bool,
+    },
+
     /////////////////////////////////////
     /////////   Text commands   /////////
     /////////////////////////////////////
@@ -109,11 +282,62 @@ pub enum Command {
         id: NodeID,
         text: String,
     },
+    TextInputSetInputType {
+        id: NodeID,
+        input_type: InputType,
+    },
+    TextInputSetMaxLength {
+        id: NodeID,
+        max_length: Option<usize>,
+    },
+    TextInputSetFormatter {
+        id: NodeID,
+        formatter: Option<InputFormatter>,
+    },
+    /// replace the text of the input
+    TextInputSetValue {
+        id: NodeID,
+        value: String,
+    },
+    TextInputSetOnChange {
+        id: NodeID,
+        on_change: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+    },
+    TextInputSetOnSubmit {
+        id: NodeID,
+        on_submit: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+    },
 
     TextEditCreate {
         id: NodeID,
         style: StyleRef,
     },
+    /// replace the content of the edit
+    TextEditSetText {
+        id: NodeID,
+        text: String,
+    },
+    /// select a range in characters
+    TextEditSetSelection {
+        id: NodeID,
+        selection: Range<usize>,
+    },
+    TextEditSetReadOnly {
+        id: NodeID,
+        read_only: bool,
+    },
+    TextEditSetWrapMode {
+        id: NodeID,
+        mode: WrapMode,
+    },
+    TextEditSetOnChange {
+        id: NodeID,
+        on_change: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+    },
+    TextEditSetOnSelectionChange {
+        id: NodeID,
+        on_selection_change: Option<Arc<dyn Fn(Range<usize>) + Send + Sync>>,
+    },
 
     /////////////////////////////////////////
     /////////   flatlist commands   /////////
@@ -133,6 +357,7 @@ pub enum Command {
         id: NodeID,
         style: StyleRef,
         command_recv: Receiver<NavigatorCommand>,
+        history: Arc<RwLock<Vec<String>>>,
     },
     StackNavigatorAddChild {
         id: NodeID,
@@ -144,6 +369,47 @@ pub enum Command {
         child: NodeID,
         name: String,
     },
+    StackNavigatorSetTransition {
+        id: NodeID,
+        transition: StackNavigatorTransition,
+        duration: Duration,
+    },
+    /// events of the pages by name
+    StackNavigatorSetPageEvents {
+        id: NodeID,
+        events: Vec<(String, PageEvents)>,
+    },
+
+    ////////////////////////////////////////
+    /////////   tab nav commands   /////////
+    ////////////////////////////////////////
+    TabNavigatorCreate {
+        id: NodeID,
+        style: StyleRef,
+        command_recv: Receiver<NavigatorCommand>,
+    },
+    TabNavigatorSetTabs {
+        id: NodeID,
+        tabs: Vec<Tab>,
+    },
+    TabNavigatorSetTabBarPosition {
+        id: NodeID,
+        position: TabBarPosition,
+    },
+    TabNavigatorSetOnTabChange {
+        id: NodeID,
+        on_tab_change: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+    },
+    TabNavigatorAddChild {
+        id: NodeID,
+        child: NodeID,
+        name: String,
+    },
+    TabNavigatorRemoveChild {
+        id: NodeID,
+        child: NodeID,
+        name: String,
+    },
     CustomCreate {
         id: NodeID,
         style: StyleRef,
@@ -153,21 +419,81 @@ pub enum Command {
         id: NodeID,
         changes: Box<dyn Any + Send + Sync>,
     },
+
+    /// invoke lifecycle callbacks of a node.
+    /// lifecycle commands are executed after all other commands of a commit.
+    Lifecycle {
+        node: NodeID,
+        callbacks: Vec<LifecycleCallback>,
+    },
 }
 
 impl std::fmt::Debug for Command {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let args = match self {
             Self::MountRoot { .. } => format!("mount root"),
+            Self::SetPointerHandlers { node, .. } => {
+                format!("set pointer handlers {{ node:{:?} }}", node)
+            }
+            Self::SetKeyHandlers { node, .. } => format!("set key handlers {{ node:{:?} }}", node),
+            Self::SetFocusProps { node, .. } => format!("set focus props {{ node:{:?} }}", node),
             Self::ButtonCreate { .. } => format!("button create"),
             Self::ButtonSetDisabled { .. } => format!("button set disabled"),
             Self::ButtonSetLabelText { .. } => format!("button set label text"),
             Self::ButtonSetOnClick { .. } => format!("button set on click"),
+            Self::ToggleCreate { kind, .. } => format!("toggle create {{ kind:{:?} }}", kind),
+            Self::ToggleSetChecked { checked, .. } => {
+                format!("toggle set checked {{ checked:{} }}", checked)
+            }
+            Self::SliderCreate { .. } => format!("slider create"),
+            Self::SliderSetValue { value, .. } => format!("slider set value {{ value:{} }}", value),
+            Self::PickerCreate { .. } => format!("picker create"),
+            Self::PickerSpliceOptions {
+                position,
+                removed,
+                added,
+                ..
+            } => format!(
+                "picker splice options {{ position:{}, removed:{}, added:{:?} }}",
+                position, removed, added
+            ),
+            Self::PickerSetSelected { selected, .. } => {
+                format!("picker set selected {{ selected:{:?} }}", selected)
+            }
+            Self::ProgressBarCreate { .. } => format!("progress bar create"),
+            Self::ProgressBarSetFraction { fraction, .. } => {
+                format!("progress bar set fraction {{ fraction:{} }}", fraction)
+            }
+            Self::ActivityIndicatorCreate { .. } => format!("activity indicator create"),
+            Self::ModalCreate { .. } => format!("modal create"),
+            Self::ModalSetVisible { visible, .. } => {
+                format!("modal set visible {{ visible:{} }}", visible)
+            }
             Self::ViewCreate { .. } => format!("view create"),
             Self::ViewSetChild { index, .. } => format!("view set child {{ index:{} }}", index),
-            Self::ViewRemoveChild { .. } => format!("view add child"),
+            Self::ViewRemoveChild { index, .. } => {
+                format!("view remove child {{ index:{} }}", index)
+            }
+            Self::ViewMoveChild { from, to, .. } => {
+                format!("view move child {{ from:{}, to:{} }}", from, to)
+            }
             Self::TextCreate { .. } => format!("text create"),
             Self::TextSetText { .. } => format!("text set text"),
+            Self::TextInputSetValue { .. } => format!("text input set value"),
+            Self::TextInputSetOnChange { .. } => format!("text input set on change"),
+            Self::TextInputSetOnSubmit { .. } => format!("text input set on submit"),
+            Self::TextEditSetText { .. } => format!("text edit set text"),
+            Self::TextEditSetSelection { selection, .. } => {
+                format!("text edit set selection {{ {:?} }}", selection)
+            }
+            Self::TabNavigatorCreate { .. } => format!("tab navigator create"),
+            Self::TabNavigatorAddChild { name, .. } => {
+                format!("tab navigator add child {{ name:{} }}", name)
+            }
+            Self::TabNavigatorRemoveChild { name, .. } => {
+                format!("tab navigator remove child {{ name:{} }}", name)
+            }
+            Self::Lifecycle { node, .. } => format!("lifecycle {{ node:{:?} }}", node),
             _ => format!(""),
         };
         f.write_str(&args)
@@ -1,7 +1,16 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::event::{KeyHandlers, PointerHandlers};
+use crate::focus::FocusProps;
+use crate::widget::input::InputType;
+use crate::widget::keyed::Key;
+use crate::widget::slider::Orientation;
+use crate::widget::stack_navigator::PageEvents;
+
+use super::callback::Forward;
 use super::command::Command;
-use super::component::CoreComponent;
+use super::component::{CoreComponent, LifecycleCallback, NodeMeta, StackNavigaterNode};
 use super::NodeID;
 
 /// commits the tree, perform necessary checks and transformation
@@ -15,11 +24,335 @@ pub fn commit_tree(tree: &mut CoreComponent, old: Option<&CoreComponent>) -> Vec
         node: tree.id().expect("expecting id"),
     });
 
+    // lifecycle callbacks are invoked after the tree is mounted
+    let (lifecycle, mut commands): (Vec<_>, Vec<_>) = commands
+        .into_iter()
+        .partition(|c| matches!(c, Command::Lifecycle { .. }));
+
+    commands.extend(lifecycle);
+
     return commands;
 }
 
-/// generate commands to commit to native tree
+/// generate commands to commit to native tree,
+/// including the lifecycle events of the node
 fn tree_generate_command(
+    current: &mut CoreComponent,
+    old: Option<&CoreComponent>,
+    cmd: &mut Vec<Command>,
+) -> NodeID {
+    // memoised subtree reused from the old tree, nothing has changed
+    if let Some(old) = old {
+        if current.meta().reused && current.id().is_some() && current.id() == old.id() {
+            return old.id().unwrap();
+        }
+    }
+
+    // native props and handlers are compared on the components rendered by memoised elements
+    let old_inner = old.map(|old| old.inner());
+
+    let id = match &mut *current {
+        // the subtree is only copied if shared with the committed tree
+        CoreComponent::Memo(m) => {
+            let inner = Arc::make_mut(&mut m.child);
+            let id = node_generate_command(inner, old_inner, cmd);
+            handlers_generate_command(id, inner, old_inner, cmd);
+            id
+        }
+        _ => {
+            let id = node_generate_command(current, old_inner, cmd);
+            handlers_generate_command(id, current, old_inner, cmd);
+            id
+        }
+    };
+
+    match old {
+        // node is retained
+        Some(old) if old.id() == Some(id) => {
+            lifecycle_command(id, &current.meta().lifecycle.on_update, cmd);
+        }
+        // node is replaced
+        Some(old) => {
+            unmount_generate_command(old, cmd);
+            lifecycle_command(id, &current.meta().lifecycle.on_mount, cmd);
+        }
+        // node is created
+        None => {
+            lifecycle_command(id, &current.meta().lifecycle.on_mount, cmd);
+        }
+    }
+
+    return id;
+}
+
+/// set the event callbacks of a created node or if callbacks are added or removed,
+/// the callbacks of a retained node are swapped into the slots of the node.
+fn handlers_generate_command(
+    id: NodeID,
+    current: &mut CoreComponent,
+    old: Option<&CoreComponent>,
+    cmd: &mut Vec<Command>,
+) {
+    let retained = old.filter(|old| old.id() == Some(id)).map(|old| old.meta());
+    let old = retained.map(|old| &old.slots);
+    let meta = current.meta_mut();
+    let (pointer, keyboard, focus) = (&meta.pointer, &meta.keyboard, &meta.focus);
+    let slots = &mut meta.slots;
+
+    let (on_pointer_down, down) = slots.forward("on_pointer_down", &pointer.on_pointer_down, old);
+    let (on_pointer_up, up) = slots.forward("on_pointer_up", &pointer.on_pointer_up, old);
+    let (on_pointer_move, moved) = slots.forward("on_pointer_move", &pointer.on_pointer_move, old);
+    let (on_pointer_enter, enter) =
+        slots.forward("on_pointer_enter", &pointer.on_pointer_enter, old);
+    let (on_pointer_leave, leave) =
+        slots.forward("on_pointer_leave", &pointer.on_pointer_leave, old);
+
+    if down || up || moved || enter || leave {
+        cmd.push(Command::SetPointerHandlers {
+            node: id,
+            handlers: PointerHandlers {
+                on_pointer_down,
+                on_pointer_up,
+                on_pointer_move,
+                on_pointer_enter,
+                on_pointer_leave,
+            },
+        });
+    }
+
+    let (on_key_down, down) = slots.forward("on_key_down", &keyboard.on_key_down, old);
+    let (on_key_up, up) = slots.forward("on_key_up", &keyboard.on_key_up, old);
+
+    if down || up {
+        cmd.push(Command::SetKeyHandlers {
+            node: id,
+            handlers: KeyHandlers {
+                on_key_down,
+                on_key_up,
+            },
+        });
+    }
+
+    let (on_focus, focus_changed) = slots.forward("on_focus", &focus.on_focus, old);
+    let (on_blur, blur_changed) = slots.forward("on_blur", &focus.on_blur, old);
+
+    let handle_changed = match retained {
+        Some(old) => !focus.is_same(&old.focus),
+        None => focus.handle.is_some() || focus.tab_index.is_some(),
+    };
+
+    if handle_changed || focus_changed || blur_changed {
+        cmd.push(Command::SetFocusProps {
+            node: id,
+            focus: FocusProps {
+                handle: focus.handle.clone(),
+                tab_index: focus.tab_index,
+                on_focus,
+                on_blur,
+            },
+        });
+    }
+}
+
+/// the callback to give to the native widget if the callback is added or removed,
+/// the callback of a retained node is swapped into the slot the native widget calls.
+fn slot_callback<T>(
+    name: &str,
+    callback: &Option<Arc<T>>,
+    meta: &mut NodeMeta,
+    old: Option<&NodeMeta>,
+) -> Option<Option<Arc<T>>>
+where
+    T: ?Sized + Forward + Send + Sync + 'static,
+{
+    let (forward, changed) = meta
+        .slots
+        .forward(name, callback, old.map(|old| &old.slots));
+    return changed.then_some(forward);
+}
+
+/// generate unmount events for a removed node and its children
+fn unmount_generate_command(old: &CoreComponent, cmd: &mut Vec<Command>) {
+    for child in old.child() {
+        unmount_generate_command(child, cmd);
+    }
+
+    if let Some(id) = old.id() {
+        lifecycle_command(id, &old.meta().lifecycle.on_unmount, cmd);
+    }
+}
+
+fn lifecycle_command(id: NodeID, callbacks: &[LifecycleCallback], cmd: &mut Vec<Command>) {
+    if !callbacks.is_empty() {
+        cmd.push(Command::Lifecycle {
+            node: id,
+            callbacks: callbacks.to_vec(),
+        });
+    }
+}
+
+/// Matches the children of a node with the old children, used by render to restore
+/// the hooks of a child and by commit to retain its native node.
+///
+/// keyed children match the first old sibling with the same key,
+/// children without key match the old sibling at the same index if it has no key.
+/// an old child is matched at most once, children with a duplicated key are recreated.
+pub(crate) struct ChildMatcher {
+    /// index of the first old child with the key
+    keys: HashMap<Key, usize>,
+    used: Vec<bool>,
+}
+
+impl ChildMatcher {
+    pub fn new(old: &[CoreComponent]) -> Self {
+        let mut keys = HashMap::new();
+
+        for (i, old) in old.iter().enumerate() {
+            if let Some(key) = &old.meta().key {
+                keys.entry(key.clone()).or_insert(i);
+            }
+        }
+
+        Self {
+            keys,
+            used: vec![false; old.len()],
+        }
+    }
+
+    /// the unmatched old child matching the child at `index`
+    pub fn find(&self, old: &[CoreComponent], index: usize, key: Option<&Key>) -> Option<usize> {
+        let found = match key {
+            Some(key) => self.keys.get(key).copied(),
+            None => old
+                .get(index)
+                .filter(|c| c.meta().key.is_none())
+                .map(|_| index),
+        };
+
+        return found.filter(|u| !self.used[*u]);
+    }
+
+    /// mark the old child as matched
+    pub fn claim(&mut self, matched: usize) {
+        self.used[matched] = true;
+    }
+
+    /// find and claim the old child matching the child at `index`
+    pub fn find_and_claim(
+        &mut self,
+        old: &[CoreComponent],
+        index: usize,
+        key: Option<&Key>,
+    ) -> Option<usize> {
+        let found = self.find(old, index, key);
+
+        if let Some(u) = found {
+            self.claim(u);
+        }
+
+        return found;
+    }
+
+    pub fn is_claimed(&self, index: usize) -> bool {
+        self.used[index]
+    }
+}
+
+/// reconcile the children of a view, native nodes of matched children are moved
+/// instead of recreated so that they keep their state.
+fn view_children_generate_command(
+    id: NodeID,
+    children: &mut [CoreComponent],
+    old_children: &[CoreComponent],
+    cmd: &mut Vec<Command>,
+) {
+    let mut matcher = ChildMatcher::new(old_children);
+
+    // match the new children with the old children
+    let matches: Vec<Option<usize>> = children
+        .iter()
+        .enumerate()
+        .map(|(i, child)| matcher.find_and_claim(old_children, i, child.meta().key.as_ref()))
+        .collect();
+
+    // children of the native view, kept in sync with the commands
+    let mut native_children: Vec<NodeID> = old_children.iter().map(|c| c.id().unwrap()).collect();
+
+    // remove old children that are not matched
+    for (u, old) in old_children.iter().enumerate() {
+        if matcher.is_claimed(u) {
+            continue;
+        }
+
+        unmount_generate_command(old, cmd);
+
+        let old_id = old.id().unwrap();
+        let index = native_children.iter().position(|c| *c == old_id).unwrap();
+        native_children.remove(index);
+
+        cmd.push(Command::ViewRemoveChild {
+            id,
+            child: old_id,
+            index,
+        });
+        cmd.push(Command::RemoveNode { node: old_id });
+    }
+
+    for (i, child) in children.iter_mut().enumerate() {
+        let old_child = matches[i].map(|u| &old_children[u]);
+
+        let child_id = tree_generate_command(child, old_child, cmd);
+
+        let from = match old_child.and_then(|c| c.id()) {
+            // native node is retained
+            Some(old_id) if old_id == child_id => {
+                native_children.iter().position(|c| *c == child_id).unwrap()
+            }
+            // native node is replaced in place
+            Some(old_id) => {
+                let index = native_children.iter().position(|c| *c == old_id).unwrap();
+                native_children[index] = child_id;
+
+                cmd.push(Command::ViewSetChild {
+                    id,
+                    child: child_id,
+                    index,
+                });
+
+                index
+            }
+            // new node is appended
+            None => {
+                let index = native_children.len();
+                native_children.push(child_id);
+
+                cmd.push(Command::ViewSetChild {
+                    id,
+                    child: child_id,
+                    index,
+                });
+
+                index
+            }
+        };
+
+        // children before `i` are already in place
+        if from != i {
+            native_children.remove(from);
+            native_children.insert(i, child_id);
+
+            cmd.push(Command::ViewMoveChild {
+                id,
+                child: child_id,
+                from,
+                to: i,
+            });
+        }
+    }
+}
+
+/// generate commands for the node itself
+fn node_generate_command(
     mut current: &mut CoreComponent,
     old: Option<&CoreComponent>,
     cmd: &mut Vec<Command>,
@@ -37,56 +370,7 @@ fn tree_generate_command(
                 })
             }
 
-            let mut v_iter = v.children.iter_mut();
-            let mut ov_iter = ov.children.iter();
-
-            let mut i = 0;
-
-            loop {
-                let child = v_iter.next();
-                let old_child = ov_iter.next();
-
-                match (child, old_child) {
-                    (Some(child), Some(old_child)) => {
-                        tree_generate_command(child, Some(old_child), cmd);
-
-                        if child.id() != old_child.id() {
-                            // replace child
-                            cmd.push(Command::ViewSetChild {
-                                id: v.id.unwrap(),
-                                child: child.id().unwrap(),
-                                index: i,
-                            })
-                        }
-                    }
-                    // remove old component
-                    (None, Some(old)) => {
-                        cmd.push(Command::ViewRemoveChild {
-                            id: v.id.unwrap(),
-                            child: old.id().unwrap(),
-                            index: i,
-                        });
-                        cmd.push(Command::RemoveNode {
-                            node: old.id().unwrap(),
-                        });
-                    }
-                    // add new child component
-                    (Some(child), None) => {
-                        // create child
-                        let child_id = tree_generate_command(child, None, cmd);
-                        // add child
-                        cmd.push(Command::ViewSetChild {
-                            id: v.id.unwrap(),
-                            child: child_id,
-                            index: i,
-                        })
-                    }
-                    // finish iteration
-                    (None, None) => break,
-                };
-
-                i += 1;
-            }
+            view_children_generate_command(v.id.unwrap(), &mut v.children, &ov.children, cmd);
         }
         // old and new not the same
         (CoreComponent::View(view), old_component) => {
@@ -168,8 +452,14 @@ fn tree_generate_command(
 
             match &mut v.child {
                 Some(child) => {
+                    // the old child is only retained if the key matches
+                    let old_children = ov.child.as_slice();
+                    let old_child = ChildMatcher::new(old_children)
+                        .find_and_claim(old_children, 0, child.meta().key.as_ref())
+                        .map(|u| &old_children[u]);
+
                     // generate command for child
-                    tree_generate_command(child, ov.child.as_ref(), cmd);
+                    tree_generate_command(child, old_child, cmd);
 
                     // check if child is the same
                     if child.id() != ov.child.as_ref().and_then(|c| c.id()) {
@@ -178,10 +468,21 @@ fn tree_generate_command(
                             child: child.id().unwrap(),
                         })
                     }
+
+                    // the old child is replaced by a child with a different key
+                    if let (Some(old), None) = (&ov.child, old_child) {
+                        unmount_generate_command(old, cmd);
+
+                        cmd.push(Command::RemoveNode {
+                            node: old.id().unwrap(),
+                        });
+                    }
                 }
                 None => {
                     // remove child
-                    if ov.child.is_some() {
+                    if let Some(old_child) = &ov.child {
+                        unmount_generate_command(old_child, cmd);
+
                         cmd.push(Command::ScrollViewRemoveChild { id: v.id.unwrap() })
                     }
                 }
@@ -202,6 +503,137 @@ fn tree_generate_command(
                 id: v.id.unwrap(),
                 style: v.style.clone(),
             });
+
+            if let Some(child) = &mut v.child {
+                let child_id = tree_generate_command(child, None, cmd);
+
+                cmd.push(Command::ScrollViewSetChild {
+                    id: v.id.unwrap(),
+                    child: child_id,
+                });
+            }
+        }
+        (CoreComponent::Modal(m), Some(CoreComponent::Modal(om))) => {
+            // set the id
+            m.id = om.id;
+
+            if !m.style.is_same(&om.style) {
+                cmd.push(Command::SetStyle {
+                    node: m.id.unwrap(),
+                    style: m.style.clone(),
+                })
+            }
+
+            if m.visible != om.visible {
+                cmd.push(Command::ModalSetVisible {
+                    id: m.id.unwrap(),
+                    visible: m.visible,
+                });
+            }
+
+            if m.transparent != om.transparent {
+                cmd.push(Command::ModalSetTransparent {
+                    id: m.id.unwrap(),
+                    transparent: m.transparent,
+                });
+            }
+
+            if let Some(on_request_close) = slot_callback(
+                "on_request_close",
+                &m.on_request_close,
+                &mut m.meta,
+                Some(&om.meta),
+            ) {
+                cmd.push(Command::ModalSetOnRequestClose {
+                    id: m.id.unwrap(),
+                    on_request_close,
+                });
+            }
+
+            match &mut m.child {
+                Some(child) => {
+                    // the old child is only retained if the key matches
+                    let old_children = om.child.as_slice();
+                    let old_child = ChildMatcher::new(old_children)
+                        .find_and_claim(old_children, 0, child.meta().key.as_ref())
+                        .map(|u| &old_children[u]);
+
+                    // generate command for child
+                    tree_generate_command(child, old_child, cmd);
+
+                    // check if child is the same
+                    if child.id() != om.child.as_ref().and_then(|c| c.id()) {
+                        cmd.push(Command::ModalSetChild {
+                            id: m.id.unwrap(),
+                            child: child.id().unwrap(),
+                        })
+                    }
+
+                    // the old child is replaced by a child with a different key
+                    if let (Some(old), None) = (&om.child, old_child) {
+                        unmount_generate_command(old, cmd);
+
+                        cmd.push(Command::RemoveNode {
+                            node: old.id().unwrap(),
+                        });
+                    }
+                }
+                None => {
+                    // the child is unmounted once the modal is hidden
+                    if let Some(old_child) = &om.child {
+                        unmount_generate_command(old_child, cmd);
+
+                        cmd.push(Command::ModalRemoveChild { id: m.id.unwrap() });
+                        cmd.push(Command::RemoveNode {
+                            node: old_child.id().unwrap(),
+                        });
+                    }
+                }
+            }
+        }
+        (CoreComponent::Modal(m), old_component) => {
+            // remove the old node
+            if let Some(old) = old_component {
+                cmd.push(Command::RemoveNode {
+                    node: old.id().unwrap(),
+                });
+            }
+
+            m.id = Some(NodeID::new_unique());
+
+            // create modal
+            cmd.push(Command::ModalCreate {
+                id: m.id.unwrap(),
+                style: m.style.clone(),
+            });
+
+            cmd.push(Command::ModalSetTransparent {
+                id: m.id.unwrap(),
+                transparent: m.transparent,
+            });
+
+            if let Some(on_request_close) =
+                slot_callback("on_request_close", &m.on_request_close, &mut m.meta, None)
+            {
+                cmd.push(Command::ModalSetOnRequestClose {
+                    id: m.id.unwrap(),
+                    on_request_close,
+                });
+            }
+
+            if let Some(child) = &mut m.child {
+                let child_id = tree_generate_command(child, None, cmd);
+
+                cmd.push(Command::ModalSetChild {
+                    id: m.id.unwrap(),
+                    child: child_id,
+                });
+            }
+
+            cmd.push(Command::ModalSetVisible {
+                id: m.id.unwrap(),
+                visible: m.visible,
+            });
         }
         (CoreComponent::Button(b), Some(CoreComponent::Button(ob))) => {
             b.id = ob.id;
@@ -281,6 +713,383 @@ fn tree_generate_command(
                 on_click: b.on_click.clone(),
             });
         }
+        // a checkbox is not turned into a switch, the node is replaced
+        (CoreComponent::Toggle(t), Some(CoreComponent::Toggle(ot))) if t.kind == ot.kind => {
+            t.id = ot.id;
+
+            if !t.style.is_same(&ot.style) {
+                cmd.push(Command::SetStyle {
+                    node: t.id.unwrap(),
+                    style: t.style.clone(),
+                })
+            }
+
+            if t.label != ot.label {
+                cmd.push(Command::ToggleSetLabel {
+                    id: t.id.unwrap(),
+                    label: t.label.clone(),
+                })
+            }
+
+            // the user may have toggled the node since the last commit,
+            // the state is always reset to the checked prop.
+            // the native toggle ignores the state if it is unchanged
+            cmd.push(Command::ToggleSetChecked {
+                id: t.id.unwrap(),
+                checked: t.checked,
+            });
+
+            if t.disabled != ot.disabled {
+                cmd.push(Command::ToggleSetDisabled {
+                    id: t.id.unwrap(),
+                    disabled: t.disabled,
+                })
+            }
+
+            if let Some(on_toggle) =
+                slot_callback("on_toggle", &t.on_toggle, &mut t.meta, Some(&ot.meta))
+            {
+                cmd.push(Command::ToggleSetOnToggle {
+                    id: t.id.unwrap(),
+                    on_toggle,
+                })
+            }
+        }
+        (CoreComponent::Toggle(t), old_component) => {
+            // remove the old node
+            if let Some(old) = old_component {
+                cmd.push(Command::RemoveNode {
+                    node: old.id().unwrap(),
+                });
+            }
+
+            t.id = Some(NodeID::new_unique());
+
+            cmd.push(Command::ToggleCreate {
+                id: t.id.unwrap(),
+                style: t.style.clone(),
+                kind: t.kind,
+            });
+
+            if !t.label.is_empty() {
+                cmd.push(Command::ToggleSetLabel {
+                    id: t.id.unwrap(),
+                    label: t.label.clone(),
+                })
+            }
+
+            if t.checked {
+                cmd.push(Command::ToggleSetChecked {
+                    id: t.id.unwrap(),
+                    checked: true,
+                })
+            }
+
+            if t.disabled {
+                cmd.push(Command::ToggleSetDisabled {
+                    id: t.id.unwrap(),
+                    disabled: true,
+                })
+            }
+
+            if let Some(on_toggle) = slot_callback("on_toggle", &t.on_toggle, &mut t.meta, None) {
+                cmd.push(Command::ToggleSetOnToggle {
+                    id: t.id.unwrap(),
+                    on_toggle,
+                })
+            }
+        }
+        (CoreComponent::Slider(s), Some(CoreComponent::Slider(os))) => {
+            s.id = os.id;
+
+            if !s.style.is_same(&os.style) {
+                cmd.push(Command::SetStyle {
+                    node: s.id.unwrap(),
+                    style: s.style.clone(),
+                })
+            }
+
+            // the range is updated before the value is clamped to it
+            if s.min != os.min || s.max != os.max {
+                cmd.push(Command::SliderSetRange {
+                    id: s.id.unwrap(),
+                    min: s.min,
+                    max: s.max,
+                })
+            }
+
+            if s.step != os.step {
+                cmd.push(Command::SliderSetStep {
+                    id: s.id.unwrap(),
+                    step: s.step,
+                })
+            }
+
+            // the user may have moved the slider since the last commit,
+            // the slider is always reset to the value prop.
+            // the native slider ignores the value if it is unchanged
+            cmd.push(Command::SliderSetValue {
+                id: s.id.unwrap(),
+                value: s.value,
+            });
+
+            if s.orientation != os.orientation {
+                cmd.push(Command::SliderSetOrientation {
+                    id: s.id.unwrap(),
+                    orientation: s.orientation,
+                })
+            }
+
+            if let Some(on_change) =
+                slot_callback("on_change", &s.on_change, &mut s.meta, Some(&os.meta))
+            {
+                cmd.push(Command::SliderSetOnChange {
+                    id: s.id.unwrap(),
+                    on_change,
+                })
+            }
+
+            if let Some(on_release) =
+                slot_callback("on_release", &s.on_release, &mut s.meta, Some(&os.meta))
+            {
+                cmd.push(Command::SliderSetOnRelease {
+                    id: s.id.unwrap(),
+                    on_release,
+                })
+            }
+        }
+        (CoreComponent::Slider(s), old_component) => {
+            // remove the old node
+            if let Some(old) = old_component {
+                cmd.push(Command::RemoveNode {
+                    node: old.id().unwrap(),
+                });
+            }
+
+            s.id = Some(NodeID::new_unique());
+
+            cmd.push(Command::SliderCreate {
+                id: s.id.unwrap(),
+                style: s.style.clone(),
+            });
+
+            cmd.push(Command::SliderSetRange {
+                id: s.id.unwrap(),
+                min: s.min,
+                max: s.max,
+            });
+
+            cmd.push(Command::SliderSetStep {
+                id: s.id.unwrap(),
+                step: s.step,
+            });
+
+            cmd.push(Command::SliderSetValue {
+                id: s.id.unwrap(),
+                value: s.value,
+            });
+
+            if s.orientation != Orientation::Horizontal {
+                cmd.push(Command::SliderSetOrientation {
+                    id: s.id.unwrap(),
+                    orientation: s.orientation,
+                })
+            }
+
+            if let Some(on_change) = slot_callback("on_change", &s.on_change, &mut s.meta, None) {
+                cmd.push(Command::SliderSetOnChange {
+                    id: s.id.unwrap(),
+                    on_change,
+                })
+            }
+
+            if let Some(on_release) = slot_callback("on_release", &s.on_release, &mut s.meta, None)
+            {
+                cmd.push(Command::SliderSetOnRelease {
+                    id: s.id.unwrap(),
+                    on_release,
+                })
+            }
+        }
+        (CoreComponent::Picker(p), Some(CoreComponent::Picker(op))) => {
+            p.id = op.id;
+
+            if !p.style.is_same(&op.style) {
+                cmd.push(Command::SetStyle {
+                    node: p.id.unwrap(),
+                    style: p.style.clone(),
+                })
+            }
+
+            // only the changed options are replaced
+            if let Some((position, removed, added)) = diff_options(&op.options, &p.options) {
+                cmd.push(Command::PickerSpliceOptions {
+                    id: p.id.unwrap(),
+                    position,
+                    removed,
+                    added,
+                })
+            }
+
+            // the user may have picked an option since the last commit,
+            // the picker is always reset to the selected prop.
+            // the native picker ignores the selection if it is unchanged
+            cmd.push(Command::PickerSetSelected {
+                id: p.id.unwrap(),
+                selected: p.selected,
+            });
+
+            if let Some(on_select) =
+                slot_callback("on_select", &p.on_select, &mut p.meta, Some(&op.meta))
+            {
+                cmd.push(Command::PickerSetOnSelect {
+                    id: p.id.unwrap(),
+                    on_select,
+                })
+            }
+        }
+        (CoreComponent::Picker(p), old_component) => {
+            // remove the old node
+            if let Some(old) = old_component {
+                cmd.push(Command::RemoveNode {
+                    node: old.id().unwrap(),
+                });
+            }
+
+            p.id = Some(NodeID::new_unique());
+
+            cmd.push(Command::PickerCreate {
+                id: p.id.unwrap(),
+                style: p.style.clone(),
+            });
+
+            if !p.options.is_empty() {
+                cmd.push(Command::PickerSpliceOptions {
+                    id: p.id.unwrap(),
+                    position: 0,
+                    removed: 0,
+                    added: p.options.clone(),
+                })
+            }
+
+            cmd.push(Command::PickerSetSelected {
+                id: p.id.unwrap(),
+                selected: p.selected,
+            });
+
+            if let Some(on_select) = slot_callback("on_select", &p.on_select, &mut p.meta, None) {
+                cmd.push(Command::PickerSetOnSelect {
+                    id: p.id.unwrap(),
+                    on_select,
+                })
+            }
+        }
+        (CoreComponent::ProgressBar(p), Some(CoreComponent::ProgressBar(op))) => {
+            p.id = op.id;
+
+            if !p.style.is_same(&op.style) {
+                cmd.push(Command::SetStyle {
+                    node: p.id.unwrap(),
+                    style: p.style.clone(),
+                })
+            }
+
+            if p.fraction != op.fraction {
+                cmd.push(Command::ProgressBarSetFraction {
+                    id: p.id.unwrap(),
+                    fraction: p.fraction,
+                })
+            }
+
+            if p.indeterminate != op.indeterminate {
+                cmd.push(Command::ProgressBarSetIndeterminate {
+                    id: p.id.unwrap(),
+                    indeterminate: p.indeterminate,
+                })
+            }
+
+            if p.text != op.text {
+                cmd.push(Command::ProgressBarSetText {
+                    id: p.id.unwrap(),
+                    text: p.text.clone(),
+                })
+            }
+        }
+        (CoreComponent::ProgressBar(p), old_component) => {
+            // remove the old node
+            if let Some(old) = old_component {
+                cmd.push(Command::RemoveNode {
+                    node: old.id().unwrap(),
+                });
+            }
+
+            p.id = Some(NodeID::new_unique());
+
+            cmd.push(Command::ProgressBarCreate {
+                id: p.id.unwrap(),
+                style: p.style.clone(),
+            });
+
+            if p.fraction != 0.0 {
+                cmd.push(Command::ProgressBarSetFraction {
+                    id: p.id.unwrap(),
+                    fraction: p.fraction,
+                })
+            }
+
+            if p.indeterminate {
+                cmd.push(Command::ProgressBarSetIndeterminate {
+                    id: p.id.unwrap(),
+                    indeterminate: true,
+                })
+            }
+
+            if p.text.is_some() {
+                cmd.push(Command::ProgressBarSetText {
+                    id: p.id.unwrap(),
+                    text: p.text.clone(),
+                })
+            }
+        }
+        (CoreComponent::ActivityIndicator(a), Some(CoreComponent::ActivityIndicator(oa))) => {
+            a.id = oa.id;
+
+            if !a.style.is_same(&oa.style) {
+                cmd.push(Command::SetStyle {
+                    node: a.id.unwrap(),
+                    style: a.style.clone(),
+                })
+            }
+
+            if a.animating != oa.animating {
+                cmd.push(Command::ActivityIndicatorSetAnimating {
+                    id: a.id.unwrap(),
+                    animating: a.animating,
+                })
+            }
+        }
+        (CoreComponent::ActivityIndicator(a), old_component) => {
+            // remove the old node
+            if let Some(old) = old_component {
+                cmd.push(Command::RemoveNode {
+                    node: old.id().unwrap(),
+                });
+            }
+
+            a.id = Some(NodeID::new_unique());
+
+            cmd.push(Command::ActivityIndicatorCreate {
+                id: a.id.unwrap(),
+                style: a.style.clone(),
+            });
+
+            if a.animating {
+                cmd.push(Command::ActivityIndicatorSetAnimating {
+                    id: a.id.unwrap(),
+                    animating: true,
+                })
+            }
+        }
         (CoreComponent::Text(t), Some(CoreComponent::Text(ot))) => {
             t.id = ot.id;
 
@@ -330,6 +1139,57 @@ fn tree_generate_command(
                     text: t.background_text.clone().unwrap_or_default(),
                 })
             }
+
+            if t.rules.input_type != ot.rules.input_type {
+                cmd.push(Command::TextInputSetInputType {
+                    id: t.id.unwrap(),
+                    input_type: t.rules.input_type,
+                })
+            }
+
+            if t.rules.max_length != ot.rules.max_length {
+                cmd.push(Command::TextInputSetMaxLength {
+                    id: t.id.unwrap(),
+                    max_length: t.rules.max_length,
+                })
+            }
+
+            if let Some(formatter) =
+                slot_callback("formatter", &t.rules.formatter, &mut t.meta, Some(&ot.meta))
+            {
+                cmd.push(Command::TextInputSetFormatter {
+                    id: t.id.unwrap(),
+                    formatter,
+                })
+            }
+
+            // the user may have edited the text since the last commit,
+            // a controlled input is always reset to its value.
+            // the native input ignores the value if the text is unchanged
+            if let Some(value) = &t.value {
+                cmd.push(Command::TextInputSetValue {
+                    id: t.id.unwrap(),
+                    value: value.clone(),
+                })
+            }
+
+            if let Some(on_change) =
+                slot_callback("on_change", &t.on_change, &mut t.meta, Some(&ot.meta))
+            {
+                cmd.push(Command::TextInputSetOnChange {
+                    id: t.id.unwrap(),
+                    on_change,
+                })
+            }
+
+            if let Some(on_submit) =
+                slot_callback("on_submit", &t.on_submit, &mut t.meta, Some(&ot.meta))
+            {
+                cmd.push(Command::TextInputSetOnSubmit {
+                    id: t.id.unwrap(),
+                    on_submit,
+                })
+            }
         }
         (CoreComponent::TextInput(t), old_component) => {
             // remove the old node
@@ -352,6 +1212,50 @@ fn tree_generate_command(
                     text: bg_text.clone(),
                 })
             }
+
+            if t.rules.input_type != InputType::Text {
+                cmd.push(Command::TextInputSetInputType {
+                    id: t.id.unwrap(),
+                    input_type: t.rules.input_type,
+                })
+            }
+
+            if t.rules.max_length.is_some() {
+                cmd.push(Command::TextInputSetMaxLength {
+                    id: t.id.unwrap(),
+                    max_length: t.rules.max_length,
+                })
+            }
+
+            if let Some(formatter) =
+                slot_callback("formatter", &t.rules.formatter, &mut t.meta, None)
+            {
+                cmd.push(Command::TextInputSetFormatter {
+                    id: t.id.unwrap(),
+                    formatter,
+                })
+            }
+
+            if let Some(value) = &t.value {
+                cmd.push(Command::TextInputSetValue {
+                    id: t.id.unwrap(),
+                    value: value.clone(),
+                })
+            }
+
+            if let Some(on_change) = slot_callback("on_change", &t.on_change, &mut t.meta, None) {
+                cmd.push(Command::TextInputSetOnChange {
+                    id: t.id.unwrap(),
+                    on_change,
+                })
+            }
+
+            if let Some(on_submit) = slot_callback("on_submit", &t.on_submit, &mut t.meta, None) {
+                cmd.push(Command::TextInputSetOnSubmit {
+                    id: t.id.unwrap(),
+                    on_submit,
+                })
+            }
         }
         (CoreComponent::TextEdit(t), Some(CoreComponent::TextEdit(ot))) => {
             t.id = ot.id;
@@ -362,6 +1266,61 @@ fn tree_generate_command(
                     style: t.style.clone(),
                 })
             }
+
+            // the content and selection are only applied when changed,
+            // the edits and cursor of the user are kept otherwise
+            if let Some(text) = &t.text {
+                if t.text != ot.text {
+                    cmd.push(Command::TextEditSetText {
+                        id: t.id.unwrap(),
+                        text: text.clone(),
+                    })
+                }
+            }
+
+            if let Some(selection) = &t.selection {
+                if t.selection != ot.selection {
+                    cmd.push(Command::TextEditSetSelection {
+                        id: t.id.unwrap(),
+                        selection: selection.clone(),
+                    })
+                }
+            }
+
+            if t.read_only != ot.read_only {
+                cmd.push(Command::TextEditSetReadOnly {
+                    id: t.id.unwrap(),
+                    read_only: t.read_only,
+                })
+            }
+
+            if t.wrap_mode != ot.wrap_mode {
+                cmd.push(Command::TextEditSetWrapMode {
+                    id: t.id.unwrap(),
+                    mode: t.wrap_mode,
+                })
+            }
+
+            if let Some(on_change) =
+                slot_callback("on_change", &t.on_change, &mut t.meta, Some(&ot.meta))
+            {
+                cmd.push(Command::TextEditSetOnChange {
+                    id: t.id.unwrap(),
+                    on_change,
+                })
+            }
+
+            if let Some(on_selection_change) = slot_callback(
+                "on_selection_change",
+                &t.on_selection_change,
+                &mut t.meta,
+                Some(&ot.meta),
+            ) {
+                cmd.push(Command::TextEditSetOnSelectionChange {
+                    id: t.id.unwrap(),
+                    on_selection_change,
+                })
+            }
         }
         (CoreComponent::TextEdit(t), old_component) => {
             // remove the old node
@@ -377,6 +1336,52 @@ fn tree_generate_command(
                 id: t.id.unwrap(),
                 style: t.style.clone(),
             });
+
+            if let Some(text) = &t.text {
+                cmd.push(Command::TextEditSetText {
+                    id: t.id.unwrap(),
+                    text: text.clone(),
+                })
+            }
+
+            if let Some(selection) = &t.selection {
+                cmd.push(Command::TextEditSetSelection {
+                    id: t.id.unwrap(),
+                    selection: selection.clone(),
+                })
+            }
+
+            if t.read_only {
+                cmd.push(Command::TextEditSetReadOnly {
+                    id: t.id.unwrap(),
+                    read_only: true,
+                })
+            }
+
+            // the native default differs between platforms
+            cmd.push(Command::TextEditSetWrapMode {
+                id: t.id.unwrap(),
+                mode: t.wrap_mode,
+            });
+
+            if let Some(on_change) = slot_callback("on_change", &t.on_change, &mut t.meta, None) {
+                cmd.push(Command::TextEditSetOnChange {
+                    id: t.id.unwrap(),
+                    on_change,
+                })
+            }
+
+            if let Some(on_selection_change) = slot_callback(
+                "on_selection_change",
+                &t.on_selection_change,
+                &mut t.meta,
+                None,
+            ) {
+                cmd.push(Command::TextEditSetOnSelectionChange {
+                    id: t.id.unwrap(),
+                    on_selection_change,
+                })
+            }
         }
         (CoreComponent::StackNavigator(s), old_component) => {
             // we handle stack navigator differently.
@@ -402,9 +1407,20 @@ fn tree_generate_command(
             }
 
             if let Some(os) = os {
+                if s.transition != os.transition || s.transition_duration != os.transition_duration
+                {
+                    cmd.push(Command::StackNavigatorSetTransition {
+                        id: s.id,
+                        transition: s.transition,
+                        duration: s.transition_duration,
+                    });
+                }
+
                 // remove screens that are no longer in the navigator
                 for (i, name) in os.child_names.iter().enumerate() {
                     if !s.child_names.contains(name) {
+                        unmount_generate_command(&os.children[i], cmd);
+
                         // remove child from navigator
                         cmd.push(Command::StackNavigatorRemoveChild {
                             id: s.id,
@@ -418,6 +1434,11 @@ fn tree_generate_command(
                     }
                 }
 
+                // the events are set if pages or callbacks are added or removed
+                if let Some(events) = page_events(s, Some(os)) {
+                    cmd.push(Command::StackNavigatorSetPageEvents { id: s.id, events });
+                }
+
                 // add pages that are not already in navigator
                 for (i, name) in s.child_names.iter().enumerate() {
                     if !os.child_names.contains(name) {
@@ -467,6 +1488,12 @@ fn tree_generate_command(
                     id: s.id,
                     style: s.style.clone(),
                     command_recv: s.command_reciever.clone(),
+                    history: s.history.clone(),
+                });
+                cmd.push(Command::StackNavigatorSetTransition {
+                    id: s.id,
+                    transition: s.transition,
+                    duration: s.transition_duration,
                 });
 
                 // loop through all the child
@@ -481,6 +1508,151 @@ fn tree_generate_command(
                         name: s.child_names[i].clone(),
                     });
                 }
+
+                if let Some(events) = page_events(s, None) {
+                    cmd.push(Command::StackNavigatorSetPageEvents { id: s.id, events });
+                }
+            }
+        }
+        (CoreComponent::TabNavigator(t), old_component) => {
+            // like the stack navigator, the tab navigator has the unique id of `TabNavigator`
+            let mut ot = None;
+
+            if let Some(old_component) = old_component {
+                if let CoreComponent::TabNavigator(o) = old_component {
+                    if o.id == t.id {
+                        ot = Some(o);
+                    }
+                }
+
+                if ot.is_none() {
+                    // remove the old node
+                    cmd.push(Command::RemoveNode {
+                        node: old_component.id().unwrap(),
+                    });
+                }
+            }
+
+            if let Some(ot) = ot {
+                if !t.style.is_same(&ot.style) {
+                    cmd.push(Command::SetStyle {
+                        node: t.id,
+                        style: t.style.clone(),
+                    })
+                }
+
+                if t.tabs != ot.tabs {
+                    cmd.push(Command::TabNavigatorSetTabs {
+                        id: t.id,
+                        tabs: t.tabs.clone(),
+                    });
+                }
+
+                if t.tab_bar_position != ot.tab_bar_position {
+                    cmd.push(Command::TabNavigatorSetTabBarPosition {
+                        id: t.id,
+                        position: t.tab_bar_position,
+                    });
+                }
+
+                if let Some(on_tab_change) = slot_callback(
+                    "on_tab_change",
+                    &t.on_tab_change,
+                    &mut t.meta,
+                    Some(&ot.meta),
+                ) {
+                    cmd.push(Command::TabNavigatorSetOnTabChange {
+                        id: t.id,
+                        on_tab_change,
+                    });
+                }
+
+                // remove pages that are no longer rendered
+                for (i, name) in ot.child_names.iter().enumerate() {
+                    if !t.child_names.contains(name) {
+                        unmount_generate_command(&ot.children[i], cmd);
+
+                        cmd.push(Command::TabNavigatorRemoveChild {
+                            id: t.id,
+                            child: ot.children[i].id().unwrap(),
+                            name: name.clone(),
+                        });
+                        cmd.push(Command::RemoveNode {
+                            node: ot.children[i].id().unwrap(),
+                        });
+                    }
+                }
+
+                for (i, name) in t.child_names.iter().enumerate() {
+                    let old = ot.child_names.iter().position(|n| n == name);
+
+                    let child_id = tree_generate_command(
+                        &mut t.children[i],
+                        old.map(|u| &ot.children[u]),
+                        cmd,
+                    );
+
+                    match old {
+                        // the page is retained
+                        Some(u) if ot.children[u].id() == Some(child_id) => {}
+                        // the page is replaced, the old node is already removed
+                        Some(u) => {
+                            cmd.push(Command::TabNavigatorRemoveChild {
+                                id: t.id,
+                                child: ot.children[u].id().unwrap(),
+                                name: name.clone(),
+                            });
+                            cmd.push(Command::TabNavigatorAddChild {
+                                id: t.id,
+                                child: child_id,
+                                name: name.clone(),
+                            });
+                        }
+                        // the page is rendered for the first time
+                        None => {
+                            cmd.push(Command::TabNavigatorAddChild {
+                                id: t.id,
+                                child: child_id,
+                                name: name.clone(),
+                            });
+                        }
+                    }
+                }
+            } else {
+                cmd.push(Command::TabNavigatorCreate {
+                    id: t.id,
+                    style: t.style.clone(),
+                    command_recv: t.command_reciever.clone(),
+                });
+
+                cmd.push(Command::TabNavigatorSetTabs {
+                    id: t.id,
+                    tabs: t.tabs.clone(),
+                });
+
+                cmd.push(Command::TabNavigatorSetTabBarPosition {
+                    id: t.id,
+                    position: t.tab_bar_position,
+                });
+
+                if let Some(on_tab_change) =
+                    slot_callback("on_tab_change", &t.on_tab_change, &mut t.meta, None)
+                {
+                    cmd.push(Command::TabNavigatorSetOnTabChange {
+                        id: t.id,
+                        on_tab_change,
+                    });
+                }
+
+                for (i, child) in t.children.iter_mut().enumerate() {
+                    let child_id = tree_generate_command(child, None, cmd);
+
+                    cmd.push(Command::TabNavigatorAddChild {
+                        id: t.id,
+                        child: child_id,
+                        name: t.child_names[i].clone(),
+                    });
+                }
             }
         }
         (CoreComponent::ListView(f), Some(CoreComponent::ListView(of))) => {
@@ -510,6 +1682,9 @@ fn tree_generate_command(
                 factory: f.factory.clone(),
             })
         }
+        (CoreComponent::Memo(_), _) => {
+            unreachable!("memoised components are committed by their inner component")
+        }
         (CoreComponent::Custom(custom), old_component) => {
             // check if the old component is also a custom component
             if let Some(CoreComponent::Custom(old_custom)) = old_component {
@@ -552,3 +1727,89 @@ fn tree_generate_command(
 
     return current.id().unwrap();
 }
+
+/// the smallest splice turning the old options into the new options.
+/// returns the position, the number of removed options and the added options,
+/// none if the options are unchanged.
+fn diff_options(old: &[String], new: &[String]) -> Option<(usize, usize, Vec<String>)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+
+    if prefix == old.len() && prefix == new.len() {
+        return None;
+    }
+
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    return Some((
+        prefix,
+        old.len() - prefix - suffix,
+        new[prefix..new.len() - suffix].to_vec(),
+    ));
+}
+
+/// the events of each stack navigator page by name,
+/// none if the native navigator already calls the slots holding the events.
+fn page_events(
+    s: &mut StackNavigaterNode,
+    old: Option<&StackNavigaterNode>,
+) -> Option<Vec<(String, PageEvents)>> {
+    let old_slots = old.map(|old| &old.meta.slots);
+    let slots = &mut s.meta.slots;
+    let mut changed = old.is_none_or(|old| old.child_names != s.child_names);
+    let mut events = Vec::new();
+
+    for (name, e) in s.child_names.iter().zip(&s.child_events) {
+        let (on_focus, focus) =
+            slots.forward(&format!("{}/on_focus", name), &e.on_focus, old_slots);
+        let (on_blur, blur) = slots.forward(&format!("{}/on_blur", name), &e.on_blur, old_slots);
+        let (on_before_leave, leave) = slots.forward(
+            &format!("{}/on_before_leave", name),
+            &e.on_before_leave,
+            old_slots,
+        );
+
+        changed |= focus || blur || leave;
+
+        events.push((
+            name.clone(),
+            PageEvents {
+                on_focus,
+                on_blur,
+                on_before_leave,
+            },
+        ));
+    }
+
+    return changed.then_some(events);
+}
+
+#[test]
+fn test_diff_options() {
+    let options = |o: &[&str]| o.iter().map(|o| o.to_string()).collect::<Vec<_>>();
+
+    let old = options(&["a", "b", "c"]);
+
+    assert_eq!(diff_options(&old, &old), None);
+    assert_eq!(
+        diff_options(&old, &options(&["a", "x", "b", "c"])),
+        Some((1, 0, options(&["x"])))
+    );
+    assert_eq!(
+        diff_options(&old, &options(&["a", "c"])),
+        Some((1, 1, vec![]))
+    );
+    assert_eq!(
+        diff_options(&old, &options(&["x", "y"])),
+        Some((0, 3, options(&["x", "y"])))
+    );
+    assert_eq!(
+        diff_options(&old, &options(&["a", "b", "c", "d"])),
+        Some((3, 0, options(&["d"])))
+    );
+    assert_eq!(diff_options(&[], &old), Some((0, 0, old.clone())));
+}
@@ -1,20 +1,127 @@
+use std::any::{Any, TypeId};
+use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crossbeam_channel::Receiver;
+use parking_lot::RwLock;
 
 use crate::custom::CustomElementWrapper;
+use crate::event::{KeyHandlers, PointerHandlers};
+use crate::focus::FocusProps;
 use crate::image::ImageSource;
+use crate::state::Tracker;
 use crate::style::StyleRef;
 use crate::util::Comparable;
 use crate::widget::flatlist::{ListViewDataSourceWrapper, ListViewWidgetFactoryWrapper};
+use crate::widget::input::InputRules;
+use crate::widget::keyed::Key;
+use crate::widget::slider::Orientation;
+use crate::widget::stack_navigator::{PageEvents, StackNavigatorTransition};
+use crate::widget::tab_navigator::{Tab, TabBarPosition};
+use crate::widget::text_edit::WrapMode;
 
+use super::callback::CallbackSlots;
+use super::render::HookStore;
 use super::NodeID;
 
+/// data attached to every core component that is not part of the native widget
+#[derive(Default, Clone)]
+pub struct NodeMeta {
+    /// key of the element, used to match the component among its siblings
+    pub(crate) key: Option<Key>,
+    /// identifier used to find the component in tests
+    pub(crate) test_id: Option<Arc<str>>,
+    /// hooks of the elements rendered into this component, outermost element first
+    pub(crate) hooks: Vec<Arc<HookStore>>,
+    /// lifecycle callbacks registered by the elements
+    pub(crate) lifecycle: Lifecycle,
+    /// pointer callbacks of the native node
+    pub(crate) pointer: PointerHandlers,
+    /// key callbacks of the native node
+    pub(crate) keyboard: KeyHandlers,
+    /// focus handle, tab index and focus callbacks of the native node
+    pub(crate) focus: FocusProps,
+    /// slots of the callbacks given to the native node
+    pub(crate) slots: CallbackSlots,
+    /// props of the memoised element rendered into this component
+    pub(crate) memo: Option<Memoised>,
+    /// true if the component is reused from the committed tree without rendering,
+    /// commit skips comparing the subtree.
+    pub(crate) reused: bool,
+}
+
+#[derive(Clone)]
+pub(crate) struct Memoised {
+    pub props: Arc<dyn Any + Send + Sync>,
+    pub eq: fn(&dyn Any, &dyn Any) -> bool,
+    /// states and contexts read while rendering the memoised element
+    pub deps: Vec<Dependency>,
+    /// number of renders requested by `request_render` when rendered
+    pub requests: u64,
+}
+
+impl Memoised {
+    pub fn new(props: Arc<dyn Any + Send + Sync>, eq: fn(&dyn Any, &dyn Any) -> bool) -> Self {
+        Self {
+            props,
+            eq,
+            deps: Vec::new(),
+            requests: 0,
+        }
+    }
+
+    pub fn is_same(&self, other: &Memoised) -> bool {
+        (self.eq)(self.props.as_ref(), other.props.as_ref())
+    }
+}
+
+/// a value read by a memoised element, the element is rendered again once it changes
+#[derive(Clone)]
+pub(crate) enum Dependency {
+    /// a state and its version when read
+    State(Arc<Tracker>, u64),
+    /// a context and the provided value, none if not provided
+    Context(TypeId, Option<Arc<dyn Any + Send + Sync>>),
+}
+
+impl std::fmt::Debug for NodeMeta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeMeta")
+            .field("key", &self.key)
+            .field("test_id", &self.test_id)
+            .field("hooks", &self.hooks.len())
+            .field("reused", &self.reused)
+            .finish_non_exhaustive()
+    }
+}
+
+pub type LifecycleCallback = Arc<dyn Fn() + Send + Sync + 'static>;
+
+#[derive(Default, Clone)]
+pub struct Lifecycle {
+    /// called after the native node is created and mounted
+    pub on_mount: Vec<LifecycleCallback>,
+    /// called after the native node is removed
+    pub on_unmount: Vec<LifecycleCallback>,
+    /// called after the mounted native node is updated by a render
+    pub on_update: Vec<LifecycleCallback>,
+}
+
+/// the component rendered by a memoised element,
+/// shared with the committed tree when the element is reused
+#[derive(Clone)]
+pub struct MemoNode {
+    pub child: Arc<CoreComponent>,
+    pub meta: NodeMeta,
+}
+
 #[derive(Default, Clone)]
 pub struct ViewNode {
     pub id: Option<NodeID>,
     pub style: StyleRef,
     pub children: Vec<CoreComponent>,
+    pub meta: NodeMeta,
 }
 
 #[derive(Clone)]
@@ -22,6 +129,7 @@ pub struct ImageViewNode {
     pub id: Option<NodeID>,
     pub style: StyleRef,
     pub src: Arc<Comparable<dyn ImageSource>>,
+    pub meta: NodeMeta,
 }
 
 #[derive(Default, Clone)]
@@ -29,6 +137,19 @@ pub struct ScrollViewNode {
     pub id: Option<NodeID>,
     pub style: StyleRef,
     pub child: Option<CoreComponent>,
+    pub meta: NodeMeta,
+}
+
+#[derive(Default, Clone)]
+pub struct ModalNode {
+    pub id: Option<NodeID>,
+    pub style: StyleRef,
+    pub visible: bool,
+    /// true if the content below is not dimmed
+    pub transparent: bool,
+    pub on_request_close: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
+    pub child: Option<CoreComponent>,
+    pub meta: NodeMeta,
 }
 
 #[derive(Default, Clone)]
@@ -38,6 +159,72 @@ pub struct ButtonNode {
     pub title: String,
     pub disabled: bool,
     pub on_click: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
+    pub meta: NodeMeta,
+}
+
+/// the appearance of a toggle node
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ToggleKind {
+    #[default]
+    Checkbox,
+    Switch,
+}
+
+/// node of a `Checkbox` or a `Switch`
+#[derive(Default, Clone)]
+pub struct ToggleNode {
+    pub id: Option<NodeID>,
+    pub style: StyleRef,
+    pub kind: ToggleKind,
+    pub label: String,
+    pub checked: bool,
+    pub disabled: bool,
+    pub on_toggle: Option<Arc<dyn Fn(bool) + Send + Sync + 'static>>,
+    pub meta: NodeMeta,
+}
+
+#[derive(Default, Clone)]
+pub struct SliderNode {
+    pub id: Option<NodeID>,
+    pub style: StyleRef,
+    pub min: f64,
+    pub max: f64,
+    /// zero for a continuous slider
+    pub step: f64,
+    pub value: f64,
+    pub orientation: Orientation,
+    pub on_change: Option<Arc<dyn Fn(f64) + Send + Sync + 'static>>,
+    pub on_release: Option<Arc<dyn Fn(f64) + Send + Sync + 'static>>,
+    pub meta: NodeMeta,
+}
+
+#[derive(Default, Clone)]
+pub struct PickerNode {
+    pub id: Option<NodeID>,
+    pub style: StyleRef,
+    /// labels of the options
+    pub options: Vec<String>,
+    pub selected: Option<usize>,
+    pub on_select: Option<Arc<dyn Fn(usize) + Send + Sync + 'static>>,
+    pub meta: NodeMeta,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ProgressBarNode {
+    pub id: Option<NodeID>,
+    pub style: StyleRef,
+    pub fraction: f64,
+    pub indeterminate: bool,
+    pub text: Option<String>,
+    pub meta: NodeMeta,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ActivityIndicatorNode {
+    pub id: Option<NodeID>,
+    pub style: StyleRef,
+    pub animating: bool,
+    pub meta: NodeMeta,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -45,25 +232,41 @@ pub struct TextNode {
     pub id: Option<NodeID>,
     pub style: StyleRef,
     pub text: String,
+    pub meta: NodeMeta,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Default, Clone)]
 pub struct TextInputNode {
     pub id: Option<NodeID>,
     pub style: StyleRef,
     pub background_text: Option<String>,
+    pub(crate) rules: InputRules,
+    /// the text of a controlled input, none if uncontrolled
+    pub value: Option<String>,
+    pub on_change: Option<Arc<dyn Fn(&str) + Send + Sync + 'static>>,
+    pub on_submit: Option<Arc<dyn Fn(&str) + Send + Sync + 'static>>,
+    pub meta: NodeMeta,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Default, Clone)]
 pub struct TextEditNode {
     pub id: Option<NodeID>,
     pub style: StyleRef,
+    pub text: Option<String>,
+    pub selection: Option<Range<usize>>,
+    pub read_only: bool,
+    pub wrap_mode: WrapMode,
+    pub on_change: Option<Arc<dyn Fn(&str) + Send + Sync + 'static>>,
+    pub on_selection_change: Option<Arc<dyn Fn(Range<usize>) + Send + Sync + 'static>>,
+    pub meta: NodeMeta,
 }
 
 #[derive(Debug, Clone)]
 pub enum NavigatorCommand {
     Goback,
     Push(String),
+    /// push with a transition other than the one of the navigator
+    PushWithTransition(String, StackNavigatorTransition),
     Goto(String),
 }
 
@@ -72,10 +275,33 @@ pub struct StackNavigaterNode {
     pub id: NodeID,
     pub style: StyleRef,
     pub command_reciever: Receiver<NavigatorCommand>,
+    /// names of the pages in history, written by the native navigator
+    pub history: Arc<RwLock<Vec<String>>>,
+    pub transition: StackNavigatorTransition,
+    pub transition_duration: Duration,
     /// the component of each screen
     pub children: Vec<CoreComponent>,
     /// describe the stack screen
     pub child_names: Vec<String>,
+    /// navigation events of each screen
+    pub child_events: Vec<PageEvents>,
+    pub meta: NodeMeta,
+}
+
+#[derive(Clone)]
+pub struct TabNavigatorNode {
+    pub id: NodeID,
+    pub style: StyleRef,
+    pub command_reciever: Receiver<NavigatorCommand>,
+    /// every tab of the tab bar, including tabs whose page is not rendered
+    pub tabs: Vec<Tab>,
+    pub tab_bar_position: TabBarPosition,
+    pub on_tab_change: Option<Arc<dyn Fn(&str) + Send + Sync + 'static>>,
+    /// the rendered pages
+    pub children: Vec<CoreComponent>,
+    /// the tab of each page
+    pub child_names: Vec<String>,
+    pub meta: NodeMeta,
 }
 
 #[derive(Clone)]
@@ -84,25 +310,35 @@ pub struct ListViewNode {
     pub(crate) style: StyleRef,
     pub(crate) data: Arc<ListViewDataSourceWrapper>,
     pub(crate) factory: Arc<ListViewWidgetFactoryWrapper>,
+    pub(crate) meta: NodeMeta,
 }
 
 #[derive(Clone)]
 pub struct CustomNode {
     pub id: Option<NodeID>,
     pub(crate) wrapper: CustomElementWrapper,
+    pub meta: NodeMeta,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CoreComponentType {
     View,
     ImageView,
     ScrollView,
     ListView,
+    Modal,
     Button,
+    Checkbox,
+    Switch,
+    Slider,
+    Picker,
+    ProgressBar,
+    ActivityIndicator,
     Text,
     TextInput,
     TextEdit,
     StackNavigator,
+    TabNavigator,
     Custom,
 }
 
@@ -111,15 +347,24 @@ pub enum CoreComponent {
     View(Box<ViewNode>),
     ImageView(Box<ImageViewNode>),
     ScrollView(Box<ScrollViewNode>),
+    Modal(Box<ModalNode>),
     Button(Box<ButtonNode>),
+    Toggle(Box<ToggleNode>),
+    Slider(Box<SliderNode>),
+    Picker(Box<PickerNode>),
+    ProgressBar(Box<ProgressBarNode>),
+    ActivityIndicator(Box<ActivityIndicatorNode>),
     Text(Box<TextNode>),
     TextInput(Box<TextInputNode>),
     TextEdit(Box<TextEditNode>),
     StackNavigator(Box<StackNavigaterNode>),
+    TabNavigator(Box<TabNavigatorNode>),
 
     ListView(Box<ListViewNode>),
 
     Custom(Box<CustomNode>),
+
+    Memo(Box<MemoNode>),
 }
 
 impl CoreComponent {
@@ -128,13 +373,24 @@ impl CoreComponent {
             Self::View(_) => CoreComponentType::View,
             Self::ImageView(_) => CoreComponentType::ImageView,
             Self::ScrollView(_) => CoreComponentType::ScrollView,
+            Self::Modal(_) => CoreComponentType::Modal,
             Self::Button(_) => CoreComponentType::Button,
+            Self::Toggle(t) => match t.kind {
+                ToggleKind::Checkbox => CoreComponentType::Checkbox,
+                ToggleKind::Switch => CoreComponentType::Switch,
+            },
+            Self::Slider(_) => CoreComponentType::Slider,
+            Self::Picker(_) => CoreComponentType::Picker,
+            Self::ProgressBar(_) => CoreComponentType::ProgressBar,
+            Self::ActivityIndicator(_) => CoreComponentType::ActivityIndicator,
             Self::Text(_) => CoreComponentType::Text,
             Self::TextInput(_) => CoreComponentType::TextInput,
             Self::TextEdit(_) => CoreComponentType::TextEdit,
             Self::ListView(_) => CoreComponentType::ListView,
             Self::StackNavigator(_) => CoreComponentType::StackNavigator,
+            Self::TabNavigator(_) => CoreComponentType::TabNavigator,
             Self::Custom(_) => CoreComponentType::Custom,
+            Self::Memo(m) => m.child.ty(),
         }
     }
     pub fn id(&self) -> Option<NodeID> {
@@ -142,13 +398,67 @@ impl CoreComponent {
             Self::View(v) => v.id,
             Self::ImageView(v) => v.id,
             Self::ScrollView(v) => v.id,
+            Self::Modal(m) => m.id,
             Self::Button(b) => b.id,
+            Self::Toggle(t) => t.id,
+            Self::Slider(s) => s.id,
+            Self::Picker(p) => p.id,
+            Self::ProgressBar(p) => p.id,
+            Self::ActivityIndicator(a) => a.id,
             Self::Text(t) => t.id,
             Self::TextInput(t) => t.id,
             Self::TextEdit(t) => t.id,
             Self::StackNavigator(n) => Some(n.id),
+            Self::TabNavigator(n) => Some(n.id),
             Self::ListView(f) => f.id,
             Self::Custom(c) => c.id,
+            Self::Memo(m) => m.child.id(),
+        }
+    }
+
+    pub fn meta(&self) -> &NodeMeta {
+        match self {
+            Self::View(v) => &v.meta,
+            Self::ImageView(v) => &v.meta,
+            Self::ScrollView(v) => &v.meta,
+            Self::Modal(m) => &m.meta,
+            Self::Button(b) => &b.meta,
+            Self::Toggle(t) => &t.meta,
+            Self::Slider(s) => &s.meta,
+            Self::Picker(p) => &p.meta,
+            Self::ProgressBar(p) => &p.meta,
+            Self::ActivityIndicator(a) => &a.meta,
+            Self::Text(t) => &t.meta,
+            Self::TextInput(t) => &t.meta,
+            Self::TextEdit(t) => &t.meta,
+            Self::StackNavigator(n) => &n.meta,
+            Self::TabNavigator(n) => &n.meta,
+            Self::ListView(f) => &f.meta,
+            Self::Custom(c) => &c.meta,
+            Self::Memo(m) => &m.meta,
+        }
+    }
+
+    pub fn meta_mut(&mut self) -> &mut NodeMeta {
+        match self {
+            Self::View(v) => &mut v.meta,
+            Self::ImageView(v) => &mut v.meta,
+            Self::ScrollView(v) => &mut v.meta,
+            Self::Modal(m) => &mut m.meta,
+            Self::Button(b) => &mut b.meta,
+            Self::Toggle(t) => &mut t.meta,
+            Self::Slider(s) => &mut s.meta,
+            Self::Picker(p) => &mut p.meta,
+            Self::ProgressBar(p) => &mut p.meta,
+            Self::ActivityIndicator(a) => &mut a.meta,
+            Self::Text(t) => &mut t.meta,
+            Self::TextInput(t) => &mut t.meta,
+            Self::TextEdit(t) => &mut t.meta,
+            Self::StackNavigator(n) => &mut n.meta,
+            Self::TabNavigator(n) => &mut n.meta,
+            Self::ListView(f) => &mut f.meta,
+            Self::Custom(c) => &mut c.meta,
+            Self::Memo(m) => &mut m.meta,
         }
     }
 
@@ -159,10 +469,21 @@ impl CoreComponent {
                 Some(c) => core::slice::from_mut(c),
                 None => &mut [],
             },
+            Self::Modal(m) => match &mut m.child {
+                Some(c) => core::slice::from_mut(c),
+                None => &mut [],
+            },
             Self::StackNavigator(s) => &mut s.children,
+            Self::TabNavigator(t) => &mut t.children,
             Self::Custom(c) => c.wrapper.children_mut(),
+            Self::Memo(m) => Arc::make_mut(&mut m.child).child_mut(),
             Self::ImageView(_)
             | Self::Button(_)
+            | Self::Toggle(_)
+            | Self::Slider(_)
+            | Self::Picker(_)
+            | Self::ProgressBar(_)
+            | Self::ActivityIndicator(_)
             | Self::Text(_)
             | Self::TextInput(_)
             | Self::TextEdit(_)
@@ -177,14 +498,35 @@ impl CoreComponent {
                 Some(c) => core::slice::from_ref(c),
                 None => &mut [],
             },
+            Self::Modal(m) => match &m.child {
+                Some(c) => core::slice::from_ref(c),
+                None => &mut [],
+            },
             Self::StackNavigator(s) => &s.children,
+            Self::TabNavigator(t) => &t.children,
             Self::Custom(c) => c.wrapper.children(),
+            Self::Memo(m) => m.child.child(),
             Self::ImageView(_)
             | Self::Button(_)
+            | Self::Toggle(_)
+            | Self::Slider(_)
+            | Self::Picker(_)
+            | Self::ProgressBar(_)
+            | Self::ActivityIndicator(_)
             | Self::Text(_)
             | Self::TextInput(_)
             | Self::TextEdit(_)
             | Self::ListView(_) => &mut [],
         }
     }
+
+    /// the component rendered by a memoised element, the component itself otherwise.
+    /// native props and handlers are set on the inner component,
+    /// the memoised component holds the meta of the elements.
+    pub(crate) fn inner(&self) -> &CoreComponent {
+        match self {
+            Self::Memo(m) => m.child.inner(),
+            _ => self,
+        }
+    }
 }
@@ -1,6 +1,8 @@
+pub mod callback;
 pub mod command;
 pub mod commit;
 pub mod component;
+pub mod render;
 
 use std::sync::atomic::{AtomicU64, Ordering};
 
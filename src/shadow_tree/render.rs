@@ -0,0 +1,626 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::app::Application;
+use crate::private::ElementLike;
+use crate::state::{DirtyFlag, Tracker};
+use crate::widget::keyed::Key;
+
+use super::commit::ChildMatcher;
+use super::component::{CoreComponent, Dependency, Lifecycle, MemoNode, Memoised, NodeMeta};
+
+/// hook slots of a single element, kept across renders
+/// as long as the element stays at the same position in the tree.
+pub(crate) struct HookStore {
+    /// type of the element owning the hooks
+    element_type: TypeId,
+    slots: Mutex<Vec<Box<dyn Any + Send>>>,
+}
+
+impl HookStore {
+    fn new(element_type: TypeId) -> Self {
+        Self {
+            element_type,
+            slots: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+/// an element being rendered at a position in the tree
+struct Frame {
+    /// the parent in the committed tree and the index of the element in its parent
+    position: Option<(*const CoreComponent, usize)>,
+    /// the matching component in the committed tree, resolved on first use
+    old: Option<Option<*const CoreComponent>>,
+    /// index of the matching component in its parent
+    matched: Option<usize>,
+    /// matches the children of the element with the children of the old component
+    children: Option<ChildMatcher>,
+    /// key of the element
+    key: Option<Key>,
+    /// test id of the element
+    test_id: Option<Arc<str>>,
+    /// hook stores of the elements rendered at this position, outermost first
+    hooks: Vec<Arc<HookStore>>,
+    /// index of the next hook slot of the innermost element
+    slot: usize,
+    /// lifecycle callbacks registered by the elements
+    lifecycle: Lifecycle,
+    /// context values provided to the descendants
+    contexts: Vec<(TypeId, Arc<dyn Any + Send + Sync>)>,
+    /// props of the memoised element
+    memo: Option<Memoised>,
+    /// number of contexts provided before the memoised element,
+    /// the others are provided inside the memoised element
+    memo_contexts: usize,
+    /// true if the old component is reused without rendering
+    reused: bool,
+    /// true if the key matched another component after the hooks were restored
+    restart: bool,
+}
+
+/// the frame before the first user defined element is rendered
+struct Snapshot {
+    /// number of elements rendered before the user defined element
+    depth: usize,
+    contexts: usize,
+    test_id: Option<Arc<str>>,
+    memo: Option<Memoised>,
+    memo_contexts: usize,
+}
+
+struct RenderContext {
+    /// root of the committed tree
+    root: Option<*const CoreComponent>,
+    /// dirty flag of the application being rendered, none for list items
+    owner: Option<Arc<DirtyFlag>>,
+    frames: Vec<Frame>,
+}
+
+impl RenderContext {
+    /// the matching component in the committed tree of the frame at `i`,
+    /// matched among the siblings by the matcher of the parent frame.
+    fn old(&mut self, i: usize) -> Option<*const CoreComponent> {
+        let (parents, frames) = self.frames.split_at_mut(i);
+        let frame = &mut frames[0];
+
+        if let Some(old) = frame.old {
+            return old;
+        }
+
+        let old = frame.position.and_then(|(parent, index)| {
+            let siblings = unsafe { (*parent).child() };
+
+            let matched = parents
+                .last_mut()?
+                .children
+                .get_or_insert_with(|| ChildMatcher::new(siblings))
+                .find(siblings, index, frame.key.as_ref());
+
+            frame.matched = matched;
+
+            matched.map(|u| &siblings[u] as *const CoreComponent)
+        });
+
+        frame.old = Some(old);
+
+        return old;
+    }
+}
+
+thread_local! {
+    static RENDER_CONTEXT: RefCell<Vec<RenderContext>> = const { RefCell::new(Vec::new()) };
+}
+
+/// render the application into core components.
+/// `old` is the committed tree, used to restore the state of elements.
+/// states read while rendering mark `owner` dirty when mutated.
+pub(crate) fn render_app(
+    app: &mut dyn Application,
+    old: Option<&CoreComponent>,
+    owner: &Arc<DirtyFlag>,
+) -> CoreComponent {
+    with_render_context(old, Some(owner.clone()), || {
+        let mut element = app.render();
+
+        render_element(&mut element, 0)
+    })
+}
+
+/// render the element tree into core components.
+/// `old` is the committed tree, used to restore the state of elements.
+pub(crate) fn render_root(
+    element: &mut Box<dyn ElementLike>,
+    old: Option<&CoreComponent>,
+) -> CoreComponent {
+    with_render_context(old, None, || render_element(element, 0))
+}
+
+fn with_render_context<F>(
+    old: Option<&CoreComponent>,
+    owner: Option<Arc<DirtyFlag>>,
+    render: F,
+) -> CoreComponent
+where
+    F: FnOnce() -> CoreComponent,
+{
+    RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        ctx.push(RenderContext {
+            root: old.map(|o| o as *const CoreComponent),
+            owner,
+            frames: Vec::new(),
+        })
+    });
+
+    let component = render();
+
+    RENDER_CONTEXT.with_borrow_mut(|ctx| ctx.pop());
+
+    return component;
+}
+
+/// dirty flag of the application being rendered on this thread, if any
+pub(crate) fn render_owner() -> Option<Arc<DirtyFlag>> {
+    RENDER_CONTEXT.with_borrow(|ctx| ctx.last().and_then(|ctx| ctx.owner.clone()))
+}
+
+/// render a child element until a core component is reached.
+/// `index` is the position of the child in its parent.
+pub(crate) fn render_element(element: &mut Box<dyn ElementLike>, index: usize) -> CoreComponent {
+    let has_context = RENDER_CONTEXT.with_borrow(|ctx| !ctx.is_empty());
+
+    // rendering outside of a tree, e.g. list view items
+    if !has_context {
+        return render_root(element, None);
+    }
+
+    enter_frame(index);
+
+    // elements returned by the previous element, the last one is rendered next
+    let mut chain: Vec<Box<dyn ElementLike>> = Vec::new();
+    let mut snapshot: Option<Snapshot> = None;
+    let mut restarted = false;
+
+    // keep rendering until core component is reached
+    let mut component = loop {
+        let depth = chain.len();
+        let elem = match chain.last_mut() {
+            Some(e) => e.as_mut(),
+            None => element.as_mut(),
+        };
+
+        // only user defined elements can use hooks
+        if elem.as_element().is_some() {
+            if snapshot.is_none() {
+                snapshot = Some(take_snapshot(depth));
+            }
+
+            begin_element(elem.element_type_id());
+        }
+
+        let result = elem.render();
+
+        // the key matched another component than the hooks were restored from,
+        // the user defined elements are rendered again with the hooks of the keyed component.
+        // a key changing on every render is only resolved once
+        if take_restart() && !restarted {
+            let snapshot = snapshot.as_ref().expect("no hooks restored");
+
+            chain.truncate(snapshot.depth);
+            restore_snapshot(snapshot);
+            restarted = true;
+
+            continue;
+        }
+
+        match result {
+            Ok(c) => break c,
+            Err(e) => chain.push(e),
+        }
+    };
+
+    let frame = exit_frame();
+
+    // the component of a memoised element is shared with the next render
+    if frame.memo.is_some() && !frame.reused {
+        component = CoreComponent::Memo(Box::new(MemoNode {
+            child: Arc::new(component),
+            meta: NodeMeta::default(),
+        }));
+    }
+
+    // store the hooks alongside the component
+    let meta = component.meta_mut();
+
+    if frame.reused {
+        // the inner elements are not rendered, keep their hooks and callbacks
+        let depth = frame.hooks.len();
+        let inner = meta.hooks.split_off(depth.min(meta.hooks.len()));
+        meta.hooks = frame.hooks;
+        meta.hooks.extend(inner);
+
+        // keep the key and test id assigned by the inner elements
+        meta.key = frame.key.or(meta.key.take());
+        meta.test_id = frame.test_id.or(meta.test_id.take());
+    } else {
+        meta.hooks = frame.hooks;
+        meta.lifecycle = frame.lifecycle;
+        meta.key = frame.key;
+        meta.test_id = frame.test_id;
+    }
+    meta.memo = frame.memo;
+    meta.reused = frame.reused;
+
+    return component;
+}
+
+fn enter_frame(index: usize) {
+    RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        let ctx = ctx.last_mut().expect("no render context");
+
+        // the old component is resolved once the key is known
+        let (position, old) = match ctx.frames.len() {
+            0 => (None, Some(ctx.root)),
+            len => (ctx.old(len - 1).map(|p| (p, index)), None),
+        };
+
+        ctx.frames.push(Frame {
+            position,
+            old,
+            matched: None,
+            children: None,
+            key: None,
+            test_id: None,
+            hooks: Vec::new(),
+            slot: 0,
+            lifecycle: Lifecycle::default(),
+            contexts: Vec::new(),
+            memo: None,
+            memo_contexts: 0,
+            reused: false,
+            restart: false,
+        });
+    })
+}
+
+fn exit_frame() -> Frame {
+    RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        let ctx = ctx.last_mut().expect("no render context");
+
+        // the old component is claimed, later siblings can not match it
+        ctx.old(ctx.frames.len() - 1);
+
+        let frame = ctx.frames.pop().expect("no render frame");
+
+        if let Some(parent) = ctx.frames.last_mut() {
+            if let (Some(matched), Some(children)) = (frame.matched, &mut parent.children) {
+                children.claim(matched);
+            }
+        }
+
+        return frame;
+    })
+}
+
+fn take_snapshot(depth: usize) -> Snapshot {
+    RENDER_CONTEXT.with_borrow(|ctx| {
+        let frame = ctx
+            .last()
+            .and_then(|ctx| ctx.frames.last())
+            .expect("no render frame");
+
+        Snapshot {
+            depth,
+            contexts: frame.contexts.len(),
+            test_id: frame.test_id.clone(),
+            memo: frame.memo.clone(),
+            memo_contexts: frame.memo_contexts,
+        }
+    })
+}
+
+/// reset the frame to the snapshot, the key is kept
+fn restore_snapshot(snapshot: &Snapshot) {
+    RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        let frame = ctx
+            .last_mut()
+            .and_then(|ctx| ctx.frames.last_mut())
+            .expect("no render frame");
+
+        // only user defined elements use hooks and lifecycle callbacks
+        frame.hooks.clear();
+        frame.slot = 0;
+        frame.lifecycle = Lifecycle::default();
+        frame.contexts.truncate(snapshot.contexts);
+        frame.test_id = snapshot.test_id.clone();
+        frame.memo = snapshot.memo.clone();
+        frame.memo_contexts = snapshot.memo_contexts;
+        frame.reused = false;
+        frame.children = None;
+    })
+}
+
+fn take_restart() -> bool {
+    RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        let frame = ctx
+            .last_mut()
+            .and_then(|ctx| ctx.frames.last_mut())
+            .expect("no render frame");
+
+        core::mem::replace(&mut frame.restart, false)
+    })
+}
+
+/// called before a user defined element is rendered
+fn begin_element(element_type: TypeId) {
+    RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        let ctx = ctx.last_mut().expect("no render context");
+        let old = ctx.old(ctx.frames.len() - 1);
+        let frame = ctx.frames.last_mut().expect("no render frame");
+
+        let depth = frame.hooks.len();
+
+        // reuse the hooks if the same element was rendered at this position
+        let old_store = old
+            .and_then(|p| unsafe { (*p).meta().hooks.get(depth) })
+            .filter(|s| s.element_type == element_type);
+
+        let store = match old_store {
+            Some(s) => s.clone(),
+            None => Arc::new(HookStore::new(element_type)),
+        };
+
+        frame.hooks.push(store);
+        frame.slot = 0;
+    })
+}
+
+/// get the next hook slot of the element being rendered,
+/// initialising it with `init` on the first render.
+pub(crate) fn use_hook<T, I, F, R>(init: I, f: F) -> R
+where
+    T: Any + Send,
+    I: FnOnce() -> T,
+    F: FnOnce(&mut T) -> R,
+{
+    let (store, slot) = RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        let frame = ctx
+            .last_mut()
+            .and_then(|ctx| ctx.frames.last_mut())
+            .expect("hooks can only be called inside `Element::render`");
+
+        let store = frame
+            .hooks
+            .last()
+            .expect("hooks can only be called inside `Element::render`")
+            .clone();
+
+        let slot = frame.slot;
+        frame.slot += 1;
+
+        (store, slot)
+    });
+
+    let mut slots = store.slots.lock();
+
+    if slots.len() == slot {
+        // first render, initialise the slot.
+        // the lock is released in case `init` calls into the store
+        drop(slots);
+        let value = init();
+        slots = store.slots.lock();
+        slots.push(Box::new(value));
+    }
+
+    let value = slots[slot]
+        .downcast_mut::<T>()
+        .expect("hooks must be called in the same order on every render");
+
+    return f(value);
+}
+
+/// register lifecycle callbacks for the element being rendered
+pub(crate) fn register_lifecycle<F: FnOnce(&mut Lifecycle)>(f: F) {
+    RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        let frame = ctx
+            .last_mut()
+            .and_then(|ctx| ctx.frames.last_mut())
+            .expect("lifecycle hooks can only be called inside `Element::render`");
+
+        f(&mut frame.lifecycle)
+    })
+}
+
+/// provide a context value to the element being rendered and its descendants
+pub(crate) fn provide_context<T: Any + Send + Sync>(value: Arc<T>) {
+    RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        let frame = ctx
+            .last_mut()
+            .and_then(|ctx| ctx.frames.last_mut())
+            .expect("no render frame");
+
+        frame.contexts.push((TypeId::of::<T>(), value));
+    })
+}
+
+/// find the context value provided by the nearest ancestor
+pub(crate) fn find_context<T: Any + Send + Sync>() -> Option<Arc<T>> {
+    RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        let ctx = ctx.last_mut()?;
+
+        let found = lookup_context(ctx, TypeId::of::<T>());
+        let value = found.as_ref().map(|(_, _, value)| value.clone());
+
+        record_context(ctx, TypeId::of::<T>(), found);
+
+        return value?.downcast::<T>().ok();
+    })
+}
+
+/// the nearest context of the type, with the index of the providing frame
+/// and the index of the context in the frame
+fn lookup_context(
+    ctx: &RenderContext,
+    ty: TypeId,
+) -> Option<(usize, usize, Arc<dyn Any + Send + Sync>)> {
+    for (f, frame) in ctx.frames.iter().enumerate().rev() {
+        for (c, (t, value)) in frame.contexts.iter().enumerate().rev() {
+            if *t == ty {
+                return Some((f, c, value.clone()));
+            }
+        }
+    }
+
+    return None;
+}
+
+/// add a context read to the dependencies of the memoised elements
+/// the context is provided outside of
+fn record_context(
+    ctx: &mut RenderContext,
+    ty: TypeId,
+    found: Option<(usize, usize, Arc<dyn Any + Send + Sync>)>,
+) {
+    for (i, frame) in ctx.frames.iter_mut().enumerate() {
+        // a context provided inside the memoised element is provided again when rendered
+        let outside = match &found {
+            Some((f, c, _)) => *f < i || (*f == i && *c < frame.memo_contexts),
+            None => true,
+        };
+
+        if let Some(memo) = frame.memo.as_mut().filter(|_| outside) {
+            let value = found.as_ref().map(|(_, _, value)| value.clone());
+            memo.deps.push(Dependency::Context(ty, value));
+        }
+    }
+}
+
+/// add a state read to the dependencies of the memoised elements being rendered
+pub(crate) fn record_state(tracker: &Arc<Tracker>) {
+    RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        if let Some(ctx) = ctx.last_mut() {
+            push_state(ctx, tracker, tracker.version());
+        }
+    })
+}
+
+fn push_state(ctx: &mut RenderContext, tracker: &Arc<Tracker>, version: u64) {
+    for memo in ctx.frames.iter_mut().filter_map(|f| f.memo.as_mut()) {
+        let recorded = memo
+            .deps
+            .iter()
+            .any(|d| matches!(d, Dependency::State(t, _) if Arc::ptr_eq(t, tracker)));
+
+        if !recorded {
+            memo.deps.push(Dependency::State(tracker.clone(), version));
+        }
+    }
+}
+
+/// returns true if the dependency is unchanged since it was recorded
+fn is_current(ctx: &RenderContext, dep: &Dependency) -> bool {
+    match dep {
+        Dependency::State(tracker, version) => tracker.version() == *version,
+        Dependency::Context(ty, value) => {
+            let current = lookup_context(ctx, *ty).map(|(_, _, value)| value);
+
+            match (current, value) {
+                (Some(a), Some(b)) => Arc::as_ptr(&a) as *const () == Arc::as_ptr(b) as *const (),
+                (None, None) => true,
+                _ => false,
+            }
+        }
+    }
+}
+
+/// register the props of a memoised element being rendered.
+/// returns the committed component if the props are unchanged
+/// and no state or context read by the committed subtree has changed.
+pub(crate) fn memoise(mut memo: Memoised) -> Option<CoreComponent> {
+    RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        let ctx = ctx.last_mut().expect("no render context");
+
+        let old = ctx.old(ctx.frames.len() - 1).map(|p| unsafe { &*p });
+
+        // the subtree is rendered again after a render request,
+        // it may depend on values that are not states
+        memo.requests = ctx.owner.as_ref().map_or(0, |owner| owner.requests());
+
+        let reused = match old {
+            Some(old) => match &old.meta().memo {
+                Some(old_memo)
+                    if old_memo.is_same(&memo)
+                        && old_memo.requests == memo.requests
+                        && old_memo.deps.iter().all(|d| is_current(ctx, d)) =>
+                {
+                    Some((old.clone(), old_memo.deps.clone()))
+                }
+                _ => None,
+            },
+            None => None,
+        };
+
+        let frame = ctx.frames.last_mut().expect("no render frame");
+        frame.reused = reused.is_some();
+        frame.memo_contexts = frame.contexts.len();
+        frame.memo = Some(memo);
+
+        let (component, deps) = reused?;
+
+        // the subtree is not rendered, its dependencies are recorded
+        // for this element and the memoised ancestors
+        for dep in deps {
+            match dep {
+                Dependency::State(tracker, version) => push_state(ctx, &tracker, version),
+                Dependency::Context(ty, _) => {
+                    let found = lookup_context(ctx, ty);
+                    record_context(ctx, ty, found);
+                }
+            }
+        }
+
+        return Some(component);
+    })
+}
+
+/// assign a key to the element being rendered
+pub(crate) fn set_key(key: Key) {
+    RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        let ctx = ctx.last_mut().expect("no render context");
+        let i = ctx.frames.len().checked_sub(1).expect("no render frame");
+        let frame = &mut ctx.frames[i];
+
+        frame.key = Some(key);
+
+        // the root has no siblings
+        if frame.position.is_none() {
+            return;
+        }
+
+        // match the old component again with the key
+        let before = frame.old.take();
+        let after = ctx.old(i);
+
+        let frame = &mut ctx.frames[i];
+
+        // the hooks of the elements rendered before the key belong to another component
+        if !frame.hooks.is_empty() && before != Some(after) {
+            frame.restart = true;
+        }
+    })
+}
+
+/// assign a test id to the element being rendered
+pub(crate) fn set_test_id(id: Arc<str>) {
+    RENDER_CONTEXT.with_borrow_mut(|ctx| {
+        let frame = ctx
+            .last_mut()
+            .and_then(|ctx| ctx.frames.last_mut())
+            .expect("no render frame");
+
+        frame.test_id = Some(id);
+    })
+}
@@ -0,0 +1,326 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+
+use parking_lot::{Condvar, Mutex, RwLock, RwLockReadGuard};
+
+use crate::shadow_tree::render::{record_state, render_owner};
+
+/// Dirty flag of an application, set when the application must be rendered again.
+///
+/// Every application and test renderer owns a flag, a `State` read while rendering
+/// the application marks the flag of that application only.
+pub(crate) struct DirtyFlag {
+    dirty: Mutex<bool>,
+    condvar: Condvar,
+    /// number of renders requested by `request_render`
+    requests: AtomicU64,
+}
+
+/// flags of the live applications
+static FLAGS: Mutex<Vec<Weak<DirtyFlag>>> = parking_lot::const_mutex(Vec::new());
+
+thread_local! {
+    /// flags of the applications created on this thread,
+    /// native callbacks run on the thread of their application
+    static THREAD_FLAGS: RefCell<Vec<Weak<DirtyFlag>>> = const { RefCell::new(Vec::new()) };
+}
+
+impl DirtyFlag {
+    /// a new flag, the first frame is always rendered
+    pub fn new() -> Arc<Self> {
+        let flag = Arc::new(Self {
+            dirty: Mutex::new(true),
+            condvar: Condvar::new(),
+            requests: AtomicU64::new(0),
+        });
+
+        let mut flags = FLAGS.lock();
+        flags.retain(|f| f.strong_count() > 0);
+        flags.push(Arc::downgrade(&flag));
+
+        THREAD_FLAGS.with_borrow_mut(|flags| {
+            flags.retain(|f| f.strong_count() > 0);
+            flags.push(Arc::downgrade(&flag));
+        });
+
+        return flag;
+    }
+
+    /// marks the application dirty, wakes up the render thread
+    pub fn request(&self) {
+        let mut dirty = self.dirty.lock();
+        *dirty = true;
+        self.condvar.notify_all();
+    }
+
+    /// blocks the current thread until the application is marked dirty,
+    /// and clears the dirty flag.
+    #[cfg(not(any(target_os = "android", feature = "headless")))]
+    pub fn wait(&self) {
+        let mut dirty = self.dirty.lock();
+
+        while !*dirty {
+            self.condvar.wait(&mut dirty);
+        }
+
+        *dirty = false;
+    }
+
+    /// clears the dirty flag, returns true if the application was dirty.
+    #[cfg(any(target_os = "android", feature = "headless"))]
+    pub fn take(&self) -> bool {
+        let mut dirty = self.dirty.lock();
+        core::mem::replace(&mut *dirty, false)
+    }
+
+    /// number of renders requested by `request_render`,
+    /// memoised elements are rendered again after a request.
+    pub fn requests(&self) -> u64 {
+        self.requests.load(Ordering::SeqCst)
+    }
+}
+
+/// marks the applications created on the calling thread dirty,
+/// every application if the thread has none. the render thread will call
+/// `Application::render` and commit the tree on the next frame.
+///
+/// mutating a `State` only marks the applications that rendered it.
+pub fn request_render() {
+    for flag in thread_flags() {
+        flag.requests.fetch_add(1, Ordering::SeqCst);
+        flag.request();
+    }
+}
+
+/// marks the applications created on the calling thread dirty like `request_render`,
+/// without rendering memoised elements again.
+///
+/// used by native widgets to commit their controlled props after a user edit.
+pub(crate) fn mark_dirty() {
+    for flag in thread_flags() {
+        flag.request();
+    }
+}
+
+/// flags of the applications created on the calling thread, every application if none
+fn thread_flags() -> Vec<Arc<DirtyFlag>> {
+    let flags: Vec<Arc<DirtyFlag>> =
+        THREAD_FLAGS.with_borrow(|flags| flags.iter().filter_map(|f| f.upgrade()).collect());
+
+    if flags.is_empty() {
+        return FLAGS.lock().iter().filter_map(|f| f.upgrade()).collect();
+    }
+
+    return flags;
+}
+
+/// applications and memoised elements that read the state while rendering
+#[derive(Default)]
+pub(crate) struct Tracker {
+    flags: Mutex<Vec<Weak<DirtyFlag>>>,
+    /// incremented whenever the value is mutated
+    version: AtomicU64,
+}
+
+impl Tracker {
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    /// called before the state is read, subscribes the application being rendered
+    /// and the memoised elements rendering the reader.
+    fn track(self: &Arc<Self>) {
+        record_state(self);
+
+        let owner = match render_owner() {
+            Some(owner) => owner,
+            None => return,
+        };
+
+        let mut flags = self.flags.lock();
+
+        if !flags.iter().any(|f| f.as_ptr() == Arc::as_ptr(&owner)) {
+            flags.retain(|f| f.strong_count() > 0);
+            flags.push(Arc::downgrade(&owner));
+        }
+    }
+
+    /// called after the state is mutated, marks the owners dirty.
+    /// falls back to `request_render` if the state was never rendered
+    fn notify(&self) {
+        self.version.fetch_add(1, Ordering::SeqCst);
+
+        let owners: Vec<Arc<DirtyFlag>> = {
+            let flags = self.flags.lock();
+
+            if flags.is_empty() {
+                drop(flags);
+                request_render();
+                return;
+            }
+
+            flags.iter().filter_map(|f| f.upgrade()).collect()
+        };
+
+        for owner in owners {
+            owner.request();
+        }
+    }
+}
+
+/// A reactive value shared between `render` and callbacks.
+///
+/// Reading a `State` while rendering subscribes the application to it.
+/// Mutating it through `set` or `update` marks the subscribed applications dirty
+/// so that they are rendered again, `Memo` elements rendering a reader are not reused.
+/// Cloning a `State` returns a handle to the same value.
+pub struct State<T> {
+    inner: Arc<RwLock<T>>,
+    tracker: Arc<Tracker>,
+}
+
+impl<T> Clone for State<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            tracker: self.tracker.clone(),
+        }
+    }
+}
+
+impl<T: Default> Default for State<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for State<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("State").field(&*self.inner.read()).finish()
+    }
+}
+
+impl<T> State<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(value)),
+            tracker: Default::default(),
+        }
+    }
+
+    /// acquire a read lock to the value
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.tracker.track();
+        self.inner.read()
+    }
+
+    /// call `f` with a reference to the value
+    pub fn with<R, F: FnOnce(&T) -> R>(&self, f: F) -> R {
+        f(&self.read())
+    }
+
+    /// replace the value and request a render
+    pub fn set(&self, value: T) {
+        *self.inner.write() = value;
+        self.tracker.notify();
+    }
+
+    /// mutate the value in place and request a render
+    pub fn update<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R {
+        let r = f(&mut self.inner.write());
+        self.tracker.notify();
+        return r;
+    }
+
+    /// returns true if both states point to the same value
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T: Clone> State<T> {
+    /// returns a copy of the value
+    pub fn get(&self) -> T {
+        self.read().clone()
+    }
+}
+
+impl<T: PartialEq> State<T> {
+    /// replace the value, only request a render if the value is not equal.
+    /// returns true if the value has changed.
+    pub fn set_if_changed(&self, value: T) -> bool {
+        let mut lock = self.inner.write();
+
+        if *lock == value {
+            return false;
+        }
+
+        *lock = value;
+        drop(lock);
+
+        self.tracker.notify();
+        return true;
+    }
+}
+
+#[test]
+fn test_state_set() {
+    let state = State::new(1);
+    let copy = state.clone();
+
+    assert!(state.ptr_eq(&copy));
+    assert!(!state.ptr_eq(&State::new(1)));
+
+    state.set(2);
+    assert_eq!(copy.get(), 2);
+    assert_eq!(state.tracker.version(), 1);
+
+    assert_eq!(state.update(|v| std::mem::replace(v, 3)), 2);
+    assert_eq!(copy.get(), 3);
+    assert_eq!(state.tracker.version(), 2);
+
+    // an equal value is not a change
+    assert!(!state.set_if_changed(3));
+    assert_eq!(state.tracker.version(), 2);
+
+    assert!(state.set_if_changed(4));
+    assert_eq!(copy.get(), 4);
+    assert_eq!(state.tracker.version(), 3);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_state_render() {
+    use crate::testing::TestRenderer;
+    use crate::widget::Text;
+
+    let first = State::new(0);
+    let second = State::new(0);
+    let (f, s) = (first.clone(), second.clone());
+
+    let mut first_renderer = TestRenderer::new(move || Text::new(format!("{}", f.get())));
+    let mut second_renderer = TestRenderer::new(move || Text::new(format!("{}", s.get())));
+
+    // nothing changed since the first render
+    assert!(!first_renderer.step());
+    assert!(!second_renderer.step());
+
+    // only the application that rendered the state is rendered again
+    first.set(1);
+    assert!(first_renderer.step());
+    assert!(!second_renderer.step());
+    assert!(first_renderer.find_by_text("1").is_some());
+
+    second.update(|v| *v += 1);
+    assert!(!first_renderer.step());
+    assert!(second_renderer.step());
+
+    // an equal value does not render
+    assert!(!first.set_if_changed(1));
+    assert!(!first_renderer.step());
+
+    assert!(first.set_if_changed(2));
+    assert!(first_renderer.step());
+    assert!(first_renderer.find_by_text("2").is_some());
+}
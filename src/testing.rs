@@ -0,0 +1,602 @@
+//! Render an application in memory and interact with it in tests.
+//!
+//! Available with the `headless` feature.
+//!
+//! ```ignore
+//! let mut renderer = TestRenderer::new(app);
+//!
+//! renderer.find_by_test_id("submit").unwrap().click();
+//! renderer.step();
+//!
+//! assert!(renderer.find_by_text("submitted").is_some());
+//! ```
+
+use std::ops::Range;
+
+use crate::app::{AppBuilder, Application};
+use crate::event::{Accelerator, KeyEvent, KeyEventKind, PointerButton, PointerEventKind};
+use crate::headless::{Frame, HeadlessRenderer, HeadlessWidget};
+use crate::native_tree::context::Context;
+use crate::native_tree::node::NativeComponent;
+use crate::native_tree::{NativeNavigatorImp, NativeStackNavigatorImp, NativeTree};
+use crate::shadow_tree::component::CoreComponent;
+use crate::widget::StackNavigatorTransition;
+
+pub use crate::headless::Frame as Rect;
+pub use crate::shadow_tree::component::CoreComponentType as NodeType;
+
+/// Renders an application into headless widgets.
+///
+/// Unlike `App::launch`, nothing runs in the background:
+/// the application is only rendered when `render` or `step` is called.
+pub struct TestRenderer {
+    renderer: HeadlessRenderer,
+}
+
+impl TestRenderer {
+    /// render the application once into a 320 by 320 window
+    pub fn new<A: Application>(app: A) -> Self {
+        Self::with_size(app, 320, 320)
+    }
+
+    /// render the application once into a window of the given size
+    pub fn with_size<A: Application>(app: A, width: u32, height: u32) -> Self {
+        let mut renderer = Self {
+            renderer: HeadlessRenderer::new(Box::new(app), width, height),
+        };
+
+        renderer.render();
+
+        return renderer;
+    }
+
+    /// render the application once with the size and accelerators of the builder.
+    /// the router of the builder opens the url of the builder, the command line is ignored.
+    pub fn from_builder<A: Application>(builder: AppBuilder, app: A) -> Self {
+        if let (Some(router), Some(url)) = (&builder.router, &builder.url) {
+            router.open(url);
+        }
+
+        let mut renderer = Self::with_size(
+            app,
+            builder.width.unwrap_or(320),
+            builder.height.unwrap_or(320),
+        );
+
+        renderer.renderer.set_accelerators(builder.accelerators);
+
+        return renderer;
+    }
+
+    /// render the application, commit the tree and compute the layout
+    pub fn render(&mut self) {
+        self.renderer.frame(true);
+    }
+
+    /// run a single frame as the render loop of `App::launch` does.
+    ///
+    /// the application is only rendered if a state it rendered is mutated
+    /// or a render is requested, navigation requested since the last frame is applied.
+    /// returns true if the application is rendered.
+    pub fn step(&mut self) -> bool {
+        self.renderer.frame(false)
+    }
+
+    /// run `n` frames
+    pub fn step_n(&mut self, n: usize) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+
+    /// resize the window and recompute the layout
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.renderer.resize(width, height);
+    }
+
+    /// press the primary button at a position relative to the window
+    pub fn pointer_down(&self, x: f32, y: f32) {
+        self.renderer.native_tree().dispatch_pointer(
+            PointerEventKind::Down,
+            Some(PointerButton::Primary),
+            x,
+            y,
+        );
+    }
+
+    /// release the primary button at a position relative to the window
+    pub fn pointer_up(&self, x: f32, y: f32) {
+        self.renderer.native_tree().dispatch_pointer(
+            PointerEventKind::Up,
+            Some(PointerButton::Primary),
+            x,
+            y,
+        );
+    }
+
+    /// move the pointer to a position relative to the window
+    pub fn pointer_move(&self, x: f32, y: f32) {
+        self.renderer
+            .native_tree()
+            .dispatch_pointer(PointerEventKind::Move, None, x, y);
+    }
+
+    /// move the pointer out of the window
+    pub fn pointer_leave(&self) {
+        self.renderer
+            .native_tree()
+            .dispatch_pointer(PointerEventKind::Leave, None, 0.0, 0.0);
+    }
+
+    /// press a key such as `a` or `Ctrl+S`.
+    /// the accelerators are triggered before the focused node receives the key,
+    /// `Tab` and `Shift+Tab` move the focus.
+    pub fn key_down(&self, key: &str) {
+        self.renderer.key_event(&key_event(KeyEventKind::Down, key));
+    }
+
+    /// release a key such as `a` or `Ctrl+S`
+    pub fn key_up(&self, key: &str) {
+        self.renderer.key_event(&key_event(KeyEventKind::Up, key));
+    }
+
+    /// the node with the keyboard focus
+    pub fn focused(&self) -> Option<TestNode<'_>> {
+        let id = self.renderer.native_tree().focused()?;
+        self.find(|n| n.component.id() == Some(id))
+    }
+
+    /// the root node of the committed tree
+    pub fn root(&self) -> Option<TestNode<'_>> {
+        let component = self.renderer.comitted_tree()?;
+
+        Some(TestNode {
+            tree: self.renderer.native_tree(),
+            component,
+        })
+    }
+
+    /// find the first visible node that satisfies the predicate, in tree order
+    pub fn find<F: Fn(&TestNode) -> bool>(&self, predicate: F) -> Option<TestNode<'_>> {
+        self.root()?.find(predicate)
+    }
+
+    /// find all the visible nodes that satisfy the predicate, in tree order
+    pub fn find_all<F: Fn(&TestNode) -> bool>(&self, predicate: F) -> Vec<TestNode<'_>> {
+        match self.root() {
+            Some(root) => root.find_all(predicate),
+            None => Vec::new(),
+        }
+    }
+
+    /// find the first visible node displaying exactly `text`
+    pub fn find_by_text(&self, text: &str) -> Option<TestNode<'_>> {
+        self.find(|n| n.text().as_deref() == Some(text))
+    }
+
+    /// find all the visible nodes of a type
+    pub fn find_all_by_type(&self, ty: NodeType) -> Vec<TestNode<'_>> {
+        self.find_all(|n| n.node_type() == ty)
+    }
+
+    /// find the visible node with the test id, see `ElementExt::test_id`
+    pub fn find_by_test_id(&self, id: &str) -> Option<TestNode<'_>> {
+        self.find(|n| n.test_id() == Some(id))
+    }
+}
+
+/// A committed node and its native widget.
+#[derive(Clone, Copy)]
+pub struct TestNode<'a> {
+    tree: &'a NativeTree<'static>,
+    pub(crate) component: &'a CoreComponent,
+}
+
+impl<'a> TestNode<'a> {
+    pub fn node_type(&self) -> NodeType {
+        self.component.ty()
+    }
+
+    pub fn test_id(&self) -> Option<&'a str> {
+        self.component.meta().test_id.as_deref()
+    }
+
+    /// the native component of the node
+    pub(crate) fn native(&self) -> &'a NativeComponent {
+        let id = self.component.id().expect("node is not committed");
+
+        self.tree
+            .get_node(id)
+            .expect("node is not mounted")
+            .component()
+    }
+
+    /// the recorded state of the widget
+    pub fn widget(&self) -> HeadlessWidget {
+        self.native().widget().as_headless_widget().clone()
+    }
+
+    /// the text, label or content displayed by the node
+    pub fn text(&self) -> Option<String> {
+        self.widget().record().text.clone()
+    }
+
+    /// layout relative to the parent
+    pub fn layout(&self) -> Rect {
+        let id = self.component.id().expect("node is not committed");
+        let node = self.tree.get_node(id).expect("node is not mounted");
+
+        Frame {
+            x: node.computed_layout.location.x,
+            y: node.computed_layout.location.y,
+            width: node.computed_layout.size.width,
+            height: node.computed_layout.size.height,
+        }
+    }
+
+    /// layout relative to the window
+    pub fn absolute_layout(&self) -> Rect {
+        let mut rect = self.layout();
+
+        let mut parent = self
+            .component
+            .id()
+            .filter(|id| !self.tree.is_modal(*id))
+            .and_then(|id| self.tree.get_node(id))
+            .and_then(|n| n.parent);
+
+        while let Some(id) = parent {
+            let node = self.tree.get_node(id).expect("node is not mounted");
+
+            rect.x += node.computed_layout.location.x;
+            rect.y += node.computed_layout.location.y;
+
+            // the layer of a modal is positioned relative to the window
+            if self.tree.is_modal(id) {
+                break;
+            }
+
+            parent = node.parent;
+        }
+
+        return rect;
+    }
+
+    /// the visible children, only the current page of a navigator is visible
+    pub fn children(&self) -> Vec<TestNode<'a>> {
+        let visible_page = match self.native() {
+            NativeComponent::StackNavigator(n) => Some(n.visible_child(&mut Context::dummy())),
+            NativeComponent::TabNavigator(n) => Some(n.visible_child(&mut Context::dummy())),
+            _ => None,
+        };
+
+        self.component
+            .child()
+            .iter()
+            .filter(|c| match visible_page {
+                Some(page) => c.id().is_some() && c.id() == page,
+                None => true,
+            })
+            .map(|c| TestNode {
+                tree: self.tree,
+                component: c,
+            })
+            .collect()
+    }
+
+    /// find the first visible node in the subtree, including this node
+    pub fn find<F: Fn(&TestNode) -> bool>(&self, predicate: F) -> Option<TestNode<'a>> {
+        self.find_all(predicate).into_iter().next()
+    }
+
+    /// find all the visible nodes in the subtree, including this node
+    pub fn find_all<F: Fn(&TestNode) -> bool>(&self, predicate: F) -> Vec<TestNode<'a>> {
+        let mut found = Vec::new();
+        self.collect(&predicate, &mut found);
+        return found;
+    }
+
+    fn collect<F: Fn(&TestNode) -> bool>(&self, predicate: &F, found: &mut Vec<TestNode<'a>>) {
+        if predicate(self) {
+            found.push(*self);
+        }
+
+        for child in self.children() {
+            child.collect(predicate, found);
+        }
+    }
+
+    /// click the button, panics if the node is not a button
+    pub fn click(&self) {
+        match self.native() {
+            NativeComponent::Button(b) => b.click(),
+            _ => panic!("{:?} can not be clicked", self.node_type()),
+        }
+    }
+
+    /// toggle the checkbox or switch, panics if the node is neither
+    pub fn toggle(&self) {
+        match self.native() {
+            NativeComponent::Toggle(t) => t.toggle(),
+            _ => panic!("{:?} can not be toggled", self.node_type()),
+        }
+    }
+
+    /// returns true if the checkbox or switch is checked, panics if the node is neither
+    pub fn is_checked(&self) -> bool {
+        match self.native() {
+            NativeComponent::Toggle(t) => t.is_checked(),
+            _ => panic!("{:?} can not be checked", self.node_type()),
+        }
+    }
+
+    /// drag the slider to a value and release it, panics if the node is not a slider
+    pub fn slide_to(&self, value: f64) {
+        match self.native() {
+            NativeComponent::Slider(s) => {
+                s.slide_to(value);
+                s.release();
+            }
+            _ => panic!("{:?} is not a slider", self.node_type()),
+        }
+    }
+
+    /// the value of the slider or the fraction of the progress bar, panics if the node is neither
+    pub fn value(&self) -> f64 {
+        match self.native() {
+            NativeComponent::Slider(s) => s.value(),
+            NativeComponent::ProgressBar(p) => p.fraction(),
+            _ => panic!("{:?} has no value", self.node_type()),
+        }
+    }
+
+    /// pick an option of the picker, panics if the node is not a picker
+    pub fn pick(&self, index: usize) {
+        match self.native() {
+            NativeComponent::Picker(p) => p.pick(index),
+            _ => panic!("{:?} is not a picker", self.node_type()),
+        }
+    }
+
+    /// the labels of the options of the picker, panics if the node is not a picker
+    pub fn options(&self) -> Vec<String> {
+        match self.native() {
+            NativeComponent::Picker(p) => p.options(),
+            _ => panic!("{:?} is not a picker", self.node_type()),
+        }
+    }
+
+    /// the selected option of the picker, panics if the node is not a picker
+    pub fn selected(&self) -> Option<usize> {
+        match self.native() {
+            NativeComponent::Picker(p) => p.selected(),
+            _ => panic!("{:?} is not a picker", self.node_type()),
+        }
+    }
+
+    /// the transition of the last navigation of the stack navigator and whether it went back,
+    /// panics if the node is not a stack navigator
+    pub fn last_transition(&self) -> Option<(StackNavigatorTransition, bool)> {
+        match self.native() {
+            NativeComponent::StackNavigator(n) => n.last_transition(),
+            _ => panic!("{:?} is not a stack navigator", self.node_type()),
+        }
+    }
+
+    /// press a tab in the tab bar, panics if the node is not a tab navigator
+    pub fn select_tab(&self, name: &str) {
+        match self.native() {
+            NativeComponent::TabNavigator(n) => n.select_tab(name),
+            _ => panic!("{:?} is not a tab navigator", self.node_type()),
+        }
+    }
+
+    /// the labels in the tab bar, panics if the node is not a tab navigator
+    pub fn tabs(&self) -> Vec<String> {
+        match self.native() {
+            NativeComponent::TabNavigator(n) => {
+                n.tabs().iter().map(|t| t.label().to_string()).collect()
+            }
+            _ => panic!("{:?} is not a tab navigator", self.node_type()),
+        }
+    }
+
+    /// the name of the selected tab, panics if the node is not a tab navigator
+    pub fn selected_tab(&self) -> Option<String> {
+        match self.native() {
+            NativeComponent::TabNavigator(n) => n.selected_tab(),
+            _ => panic!("{:?} is not a tab navigator", self.node_type()),
+        }
+    }
+
+    /// returns true if the activity indicator spins or the progress bar pulses,
+    /// panics if the node is neither
+    pub fn is_animating(&self) -> bool {
+        match self.native() {
+            NativeComponent::ActivityIndicator(a) => a.is_animating(),
+            NativeComponent::ProgressBar(p) => p.is_indeterminate(),
+            _ => panic!("{:?} can not be animated", self.node_type()),
+        }
+    }
+
+    /// press and release the primary button at the centre of the node
+    pub fn press(&self) {
+        let rect = self.absolute_layout();
+        let x = rect.x + rect.width / 2.0;
+        let y = rect.y + rect.height / 2.0;
+
+        for kind in [PointerEventKind::Down, PointerEventKind::Up] {
+            self.tree
+                .dispatch_pointer(kind, Some(PointerButton::Primary), x, y);
+        }
+    }
+
+    /// press a key such as `a` or `Ctrl+S` as if the node were focused,
+    /// the event bubbles to the ancestors but does not trigger accelerators
+    pub fn key_down(&self, key: &str) {
+        let id = self.component.id().expect("node is not committed");
+        self.tree
+            .dispatch_key(id, &key_event(KeyEventKind::Down, key));
+    }
+
+    /// release a key such as `a` or `Ctrl+S` as if the node were focused
+    pub fn key_up(&self, key: &str) {
+        let id = self.component.id().expect("node is not committed");
+        self.tree
+            .dispatch_key(id, &key_event(KeyEventKind::Up, key));
+    }
+
+    /// move the keyboard focus to the node, returns false if the node can not be focused
+    pub fn focus(&self) -> bool {
+        let id = self.component.id().expect("node is not committed");
+        self.tree.focus_node(id)
+    }
+
+    /// remove the keyboard focus from the node if it is focused
+    pub fn blur(&self) {
+        let id = self.component.id().expect("node is not committed");
+        self.tree.blur_node(id);
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.component.id().is_some() && self.tree.focused() == self.component.id()
+    }
+
+    /// replace the text as if typed by the user,
+    /// panics if the node is not a text input or text edit
+    pub fn input_text(&self, text: &str) {
+        match self.native() {
+            NativeComponent::TextInput(t) => t.input_text(text),
+            NativeComponent::TextEdit(t) => t.input_text(text),
+            _ => panic!("{:?} does not accept text", self.node_type()),
+        }
+    }
+
+    /// select a range in characters as if by the user,
+    /// panics if the node is not a text edit
+    pub fn select(&self, selection: Range<usize>) {
+        match self.native() {
+            NativeComponent::TextEdit(t) => t.select(selection),
+            _ => panic!("{:?} can not be selected", self.node_type()),
+        }
+    }
+
+    /// press enter in the text input, panics if the node is not a text input
+    pub fn submit(&self) {
+        match self.native() {
+            NativeComponent::TextInput(t) => t.press_enter(),
+            _ => panic!("{:?} can not be submitted", self.node_type()),
+        }
+    }
+}
+
+/// create a key event from a key combination such as `Ctrl+S`
+fn key_event(kind: KeyEventKind, key: &str) -> KeyEvent {
+    let accelerator = key
+        .parse::<Accelerator>()
+        .unwrap_or_else(|e| panic!("invalid key: {}", e));
+
+    let mut chars = accelerator.key.chars();
+
+    let character = match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    };
+
+    return KeyEvent::new(kind, accelerator.key, character, 0, accelerator.modifiers);
+}
+
+impl std::fmt::Debug for TestNode<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestNode")
+            .field("type", &self.node_type())
+            .field("test_id", &self.test_id())
+            .field("text", &self.text())
+            .finish()
+    }
+}
+
+#[test]
+fn test_find_by_text() {
+    use crate::style::{Dimension, FlexDirection, Style};
+    use crate::widget::{Button, ElementExt, Text, View};
+
+    static STYLE: Style = Style {
+        flex_direction: FlexDirection::Column,
+        width: Dimension::Points(200.0),
+        ..Style::DEFAULT
+    };
+
+    let renderer = TestRenderer::new(|| {
+        View::new()
+            .with_style(&STYLE)
+            .with_child(Text::new("title"))
+            .with_child(Button::new().with_label("add").test_id("add"))
+    });
+
+    let text = renderer.find_by_text("title").expect("text is rendered");
+    assert_eq!(text.node_type(), NodeType::Text);
+    assert_eq!(text.layout().width, 200.0);
+
+    let button = renderer.find_by_test_id("add").expect("button is rendered");
+    assert_eq!(button.text().as_deref(), Some("add"));
+    assert_eq!(button.absolute_layout().y, text.layout().height);
+
+    assert!(renderer.find_by_text("missing").is_none());
+    assert_eq!(renderer.find_all_by_type(NodeType::Text).len(), 1);
+}
+
+#[test]
+fn test_click() {
+    use crate::widget::{Button, ElementExt, Text, View};
+    use crate::State;
+
+    let count = State::new(0);
+    let c = count.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let c = c.clone();
+
+        View::new()
+            .with_child(Text::new(format!("count: {}", c.get())))
+            .with_child(
+                Button::new()
+                    .with_on_click(move || c.update(|c| *c += 1))
+                    .test_id("add"),
+            )
+    });
+
+    renderer.find_by_test_id("add").unwrap().click();
+    assert_eq!(count.get(), 1);
+
+    // the application is rendered on the next step
+    assert!(renderer.find_by_text("count: 0").is_some());
+    assert!(renderer.step());
+    assert!(renderer.find_by_text("count: 1").is_some());
+}
+
+#[test]
+fn test_find_visible_page() {
+    use crate::widget::{StackNavigator, Text};
+
+    let navigator = StackNavigator::new();
+    let nav = navigator.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        nav.navigator()
+            .with_page("home", |_| Text::new("home page"))
+            .with_page("settings", |_| Text::new("settings page"))
+    });
+
+    // only the visible page can be found
+    assert!(renderer.find_by_text("home page").is_some());
+    assert!(renderer.find_by_text("settings page").is_none());
+
+    navigator.push("settings");
+    renderer.step();
+
+    assert!(renderer.find_by_text("home page").is_none());
+    assert!(renderer.find_by_text("settings page").is_some());
+    // the home page is kept in the navigator but hidden
+    assert_eq!(renderer.find_all_by_type(NodeType::Text).len(), 1);
+}
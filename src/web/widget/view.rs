@@ -53,6 +53,16 @@ impl NativeViewImp for HtmlView {
     fn remove_child(&self, elem: &dyn NativeElement) {
         let _ = self.div.remove_child(elem.as_html_element());
     }
+    fn move_child(&self, index: usize, elem: &dyn NativeElement) {
+        let elem = elem.as_html_element();
+        let _ = self.div.remove_child(elem);
+
+        let children = self.div.children();
+        let sibling = children.get_with_index(index as u32);
+        let _ = self
+            .div
+            .insert_before(elem, sibling.as_ref().map(|s| s.as_ref()));
+    }
     fn set_child_position(&self, child: &dyn NativeElement, x: f32, y: f32) {
         let elem = child.as_html_element();
         let style = elem.style();
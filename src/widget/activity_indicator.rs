@@ -0,0 +1,113 @@
+use crate::private::{ElementLike, NativeElement};
+use crate::shadow_tree::component::{ActivityIndicatorNode, CoreComponent};
+use crate::style::StyleRef;
+
+/// A spinner showing that an operation is in progress.
+///
+/// The colour of the style is used for the spinner.
+pub struct ActivityIndicator {
+    tree_node: ActivityIndicatorNode,
+}
+
+impl ActivityIndicator {
+    /// an animating spinner
+    pub fn new() -> Self {
+        Self {
+            tree_node: ActivityIndicatorNode {
+                animating: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn with_style<S: Into<StyleRef>>(mut self, style: S) -> Self {
+        self.set_style(style);
+        return self;
+    }
+
+    pub fn set_style<S: Into<StyleRef>>(&mut self, style: S) {
+        self.tree_node.style = style.into();
+    }
+
+    /// the spinner is still shown when it is not animating
+    pub fn with_animating(mut self, animating: bool) -> Self {
+        self.set_animating(animating);
+        return self;
+    }
+
+    pub fn set_animating(&mut self, animating: bool) {
+        self.tree_node.animating = animating;
+    }
+}
+
+impl Default for ActivityIndicator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NativeElement for ActivityIndicator {
+    fn core_component(&mut self) -> CoreComponent {
+        CoreComponent::ActivityIndicator(Box::new(self.tree_node.clone()))
+    }
+    fn render(&mut self) {}
+}
+
+impl ElementLike for ActivityIndicator {
+    fn as_native(&mut self) -> Option<&mut dyn NativeElement> {
+        Some(self)
+    }
+    fn as_element(&mut self) -> Option<&mut dyn crate::Element> {
+        None
+    }
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_activity_indicator() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+    use crate::State;
+
+    let loading = State::new(true);
+    let l = loading.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        ActivityIndicator::new()
+            .with_animating(l.get())
+            .test_id("spinner")
+    });
+
+    assert!(renderer.find_by_test_id("spinner").unwrap().is_animating());
+
+    loading.set(false);
+    renderer.step();
+    assert!(!renderer.find_by_test_id("spinner").unwrap().is_animating());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_activity_indicator_colour() {
+    use crate::style::{Colour, Style};
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+
+    static BLUE: Style = Style {
+        colour: Some(Colour {
+            r: 0,
+            g: 0,
+            b: 255,
+            a: 255,
+        }),
+        ..Style::DEFAULT
+    };
+
+    let renderer = TestRenderer::new(|| {
+        ActivityIndicator::new()
+            .with_style(&BLUE)
+            .test_id("spinner")
+    });
+
+    let spinner = renderer.find_by_test_id("spinner").unwrap();
+    assert_eq!(spinner.widget().record().style.colour, BLUE.colour);
+}
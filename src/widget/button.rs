@@ -44,6 +44,9 @@ impl Button {
     }
 }
 
+impl_key_events!(Button, tree_node.meta.keyboard);
+impl_focus_events!(Button, tree_node.meta.focus);
+
 impl NativeElement for Button {
     fn core_component(&mut self) -> CoreComponent {
         CoreComponent::Button(Box::new(self.tree_node.clone()))
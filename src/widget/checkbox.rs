@@ -0,0 +1,56 @@
+use crate::shadow_tree::component::{ToggleKind, ToggleNode};
+
+/// A box that can be checked, with an optional label.
+///
+/// The checkbox is controlled: it always shows the `checked` prop,
+/// `on_toggle` should update the state the prop is rendered from.
+pub struct Checkbox {
+    tree_node: ToggleNode,
+}
+
+impl_toggle!(Checkbox, ToggleKind::Checkbox);
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_checkbox_toggle() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+    use crate::State;
+
+    let agreed = State::new(false);
+    let a = agreed.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let a1 = a.clone();
+
+        Checkbox::new()
+            .with_label("I agree")
+            .with_checked(a.get())
+            .with_on_toggle(move |checked| a1.set(checked))
+            .test_id("agree")
+    });
+
+    let agree = renderer.find_by_test_id("agree").unwrap();
+    assert_eq!(agree.text().as_deref(), Some("I agree"));
+    assert!(!agree.is_checked());
+
+    agree.toggle();
+    renderer.step();
+    assert!(agreed.get());
+    assert!(renderer.find_by_test_id("agree").unwrap().is_checked());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_checkbox_layout() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, View};
+
+    let renderer = TestRenderer::new(|| {
+        View::new().with_child(Checkbox::new().with_label("I agree").test_id("agree"))
+    });
+
+    // the box, the spacing and the seven characters of the label
+    let agree = renderer.find_by_test_id("agree").unwrap();
+    assert_eq!(agree.layout().width, 16.0 + 6.0 + 7.0 * 8.0);
+}
@@ -125,6 +125,7 @@ impl NativeElement for ListView {
             style: self.style.clone(),
             data: self.data.clone(),
             factory: self.factory.clone(),
+            meta: Default::default(),
         }))
     }
     fn render(&mut self) {}
@@ -1,14 +1,16 @@
 use std::sync::Arc;
 
+use crate::event::PointerHandlers;
 use crate::image::ImageSource;
 use crate::private::{ElementLike, NativeElement};
-use crate::shadow_tree::component::{CoreComponent, ImageViewNode};
+use crate::shadow_tree::component::{CoreComponent, ImageViewNode, NodeMeta};
 use crate::style::StyleRef;
 use crate::util::Comparable;
 
 pub struct ImageView {
     src: Arc<Comparable<dyn ImageSource>>,
     style: StyleRef,
+    pointer: PointerHandlers,
 }
 
 impl ImageView {
@@ -19,6 +21,7 @@ impl ImageView {
                 data: src,
             }),
             style: StyleRef::DEFAULT,
+            pointer: PointerHandlers::default(),
         }
     }
 
@@ -44,12 +47,18 @@ impl ImageView {
     }
 }
 
+impl_pointer_events!(ImageView);
+
 impl NativeElement for ImageView {
     fn core_component(&mut self) -> CoreComponent {
         CoreComponent::ImageView(Box::new(ImageViewNode {
             id: None,
             style: self.style.clone(),
             src: self.src.clone(),
+            meta: NodeMeta {
+                pointer: self.pointer.clone(),
+                ..Default::default()
+            },
         }))
     }
     fn render(&mut self) {}
@@ -1,10 +1,109 @@
+use std::sync::Arc;
+
+use crate::event::KeyHandlers;
+use crate::focus::FocusProps;
 use crate::private::{ElementLike, NativeElement};
-use crate::shadow_tree::component::{CoreComponent, TextInputNode};
+use crate::shadow_tree::component::{CoreComponent, NodeMeta, TextInputNode};
 use crate::style::StyleRef;
 
+/// the kind of text accepted by a `TextInput`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InputType {
+    #[default]
+    Text,
+    /// the text is hidden while typing
+    Password,
+    /// digits, a decimal point and a leading minus sign
+    Number,
+    /// whitespace is removed
+    Email,
+}
+
+/// transforms the text typed by the user before `on_change` is called
+pub type InputFormatter = Arc<dyn Fn(&str) -> String + Send + Sync + 'static>;
+
+/// the rules applied to the text typed by the user
+#[derive(Default, Clone)]
+pub(crate) struct InputRules {
+    pub input_type: InputType,
+    /// maximum number of characters
+    pub max_length: Option<usize>,
+    pub formatter: Option<InputFormatter>,
+}
+
+impl InputRules {
+    /// filter the text by the input type, then format and truncate it
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = match self.input_type {
+            InputType::Text | InputType::Password => text.to_string(),
+            InputType::Number => filter_number(text),
+            InputType::Email => text.chars().filter(|c| !c.is_whitespace()).collect(),
+        };
+
+        if let Some(formatter) = &self.formatter {
+            text = formatter(&text);
+        }
+
+        if let Some(max) = self.max_length {
+            if let Some((index, _)) = text.char_indices().nth(max) {
+                text.truncate(index);
+            }
+        }
+
+        return text;
+    }
+}
+
+fn filter_number(text: &str) -> String {
+    let mut result = String::new();
+
+    for c in text.chars() {
+        let accepted = match c {
+            '0'..='9' => true,
+            '-' => result.is_empty(),
+            '.' => !result.contains('.'),
+            _ => false,
+        };
+
+        if accepted {
+            result.push(c);
+        }
+    }
+
+    return result;
+}
+
+/// format the digits of the text with a pattern, `#` is replaced by a digit.
+/// other characters of the pattern are inserted once a digit follows them,
+/// e.g. `(###) ###-####` formats `5551234` as `(555) 123-4`
+fn apply_mask(pattern: &str, text: &str) -> String {
+    let mut digits = text.chars().filter(|c| c.is_ascii_digit()).peekable();
+    let mut result = String::new();
+
+    for p in pattern.chars() {
+        if digits.peek().is_none() {
+            break;
+        }
+
+        if p == '#' {
+            result.extend(digits.next());
+        } else {
+            result.push(p);
+        }
+    }
+
+    return result;
+}
+
 pub struct TextInput {
     style: StyleRef,
     bg_text: Option<String>,
+    rules: InputRules,
+    value: Option<String>,
+    on_change: Option<Arc<dyn Fn(&str) + Send + Sync + 'static>>,
+    on_submit: Option<Arc<dyn Fn(&str) + Send + Sync + 'static>>,
+    keyboard: KeyHandlers,
+    focus: FocusProps,
 }
 
 impl TextInput {
@@ -12,6 +111,12 @@ impl TextInput {
         Self {
             style: StyleRef::DEFAULT,
             bg_text: None,
+            rules: InputRules::default(),
+            value: None,
+            on_change: None,
+            on_submit: None,
+            keyboard: KeyHandlers::default(),
+            focus: FocusProps::default(),
         }
     }
 
@@ -24,6 +129,99 @@ impl TextInput {
         self.bg_text = Some(text.into())
     }
 
+    pub fn with_input_type(mut self, input_type: InputType) -> Self {
+        self.set_input_type(input_type);
+        return self;
+    }
+
+    pub fn set_input_type(&mut self, input_type: InputType) {
+        self.rules.input_type = input_type
+    }
+
+    /// limit the number of characters the user can enter
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.set_max_length(max_length);
+        return self;
+    }
+
+    pub fn set_max_length(&mut self, max_length: usize) {
+        self.rules.max_length = Some(max_length)
+    }
+
+    /// transform the text typed by the user, the result is shown in the input
+    /// and passed to `on_change`. the formatter must return its input unchanged
+    /// when called with a formatted text.
+    pub fn with_formatter<F>(mut self, formatter: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.set_formatter(formatter);
+        return self;
+    }
+
+    pub fn set_formatter<F>(&mut self, formatter: F)
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.rules.formatter = Some(Arc::new(formatter))
+    }
+
+    /// format the digits typed by the user with a pattern such as `(###) ###-####`
+    /// or `##/##/####`, each `#` is replaced by a digit
+    pub fn with_mask<S: Into<String>>(mut self, pattern: S) -> Self {
+        self.set_mask(pattern);
+        return self;
+    }
+
+    pub fn set_mask<S: Into<String>>(&mut self, pattern: S) {
+        let pattern = pattern.into();
+        self.set_formatter(move |text| apply_mask(&pattern, text))
+    }
+
+    /// make the input controlled, the input always shows `value`.
+    /// every edit renders the application again, text typed by the user
+    /// is replaced by `value` unless `on_change` updates the value.
+    pub fn with_value<S: Into<String>>(mut self, value: S) -> Self {
+        self.set_value(value);
+        return self;
+    }
+
+    pub fn set_value<S: Into<String>>(&mut self, value: S) {
+        self.value = Some(value.into())
+    }
+
+    /// called with the new text whenever the user edits the text
+    pub fn with_on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.set_on_change(callback);
+        return self;
+    }
+
+    pub fn set_on_change<F>(&mut self, callback: F)
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Arc::new(callback))
+    }
+
+    /// called with the text when the user presses enter
+    pub fn with_on_submit<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.set_on_submit(callback);
+        return self;
+    }
+
+    pub fn set_on_submit<F>(&mut self, callback: F)
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_submit = Some(Arc::new(callback))
+    }
+
     pub fn with_style<S: Into<StyleRef>>(mut self, style: S) -> Self {
         self.set_style(style);
         return self;
@@ -34,12 +232,30 @@ impl TextInput {
     }
 }
 
+impl Default for TextInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl_key_events!(TextInput, keyboard);
+impl_focus_events!(TextInput, focus);
+
 impl NativeElement for TextInput {
     fn core_component(&mut self) -> CoreComponent {
         CoreComponent::TextInput(Box::new(TextInputNode {
             id: None,
             style: self.style.clone(),
-            background_text: None,
+            background_text: self.bg_text.clone(),
+            rules: self.rules.clone(),
+            value: self.value.clone(),
+            on_change: self.on_change.clone(),
+            on_submit: self.on_submit.clone(),
+            meta: NodeMeta {
+                keyboard: self.keyboard.clone(),
+                focus: self.focus.clone(),
+                ..Default::default()
+            },
         }))
     }
     fn render(&mut self) {}
@@ -53,3 +269,215 @@ impl ElementLike for TextInput {
         None
     }
 }
+
+#[test]
+fn test_input_rules() {
+    let rules = InputRules {
+        input_type: InputType::Number,
+        max_length: Some(5),
+        formatter: None,
+    };
+    assert_eq!(rules.apply("-1a2.3.4-5"), "-12.3");
+
+    let rules = InputRules {
+        formatter: Some(Arc::new(|t: &str| apply_mask("(###) ###-####", t))),
+        ..Default::default()
+    };
+    assert_eq!(rules.apply("555123"), "(555) 123");
+    assert_eq!(rules.apply("(555) 123"), "(555) 123");
+    assert_eq!(rules.apply(""), "");
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_rejected_edit() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+
+    let mut renderer = TestRenderer::new(|| {
+        // the value is never updated
+        TextInput::new()
+            .with_value("fixed")
+            .with_on_change(|_| {})
+            .test_id("input")
+    });
+
+    let input = renderer.find_by_test_id("input").unwrap();
+    input.input_text("edited");
+    assert_eq!(input.text().as_deref(), Some("edited"));
+
+    // no state is mutated, the edit itself renders the application
+    assert!(renderer.step());
+
+    let input = renderer.find_by_test_id("input").unwrap();
+    assert_eq!(input.text().as_deref(), Some("fixed"));
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_controlled_value() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+    use crate::State;
+
+    let value = State::new(String::new());
+    let v = value.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let v1 = v.clone();
+
+        // names longer than 5 characters are rejected
+        TextInput::new()
+            .with_value(v.get())
+            .with_on_change(move |text| {
+                if text.len() <= 5 {
+                    v1.set(text.to_string());
+                }
+            })
+            .test_id("name")
+    });
+
+    renderer.find_by_test_id("name").unwrap().input_text("hello");
+    renderer.step();
+    assert_eq!(value.get(), "hello");
+
+    renderer.find_by_test_id("name").unwrap().input_text("hello world");
+    renderer.step();
+    assert_eq!(value.get(), "hello");
+    let input = renderer.find_by_test_id("name").unwrap();
+    assert_eq!(input.text().as_deref(), Some("hello"));
+
+    // the state is the source of truth
+    value.set("bye".into());
+    renderer.step();
+    let input = renderer.find_by_test_id("name").unwrap();
+    assert_eq!(input.text().as_deref(), Some("bye"));
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_typing_reuses_memo() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Memo, Text, View};
+    use crate::State;
+
+    static RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+    let value = State::new(String::new());
+    let v = value.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let v1 = v.clone();
+
+        View::new()
+            .with_child(
+                TextInput::new()
+                    .with_value(v.get())
+                    .with_on_change(move |text| v1.set(text.to_string()))
+                    .test_id("name"),
+            )
+            .with_child(Memo::new((), |_| {
+                RENDERS.fetch_add(1, Ordering::SeqCst);
+                Text::new("header")
+            }))
+    });
+
+    for text in ["h", "he", "hel"] {
+        renderer.find_by_test_id("name").unwrap().input_text(text);
+        renderer.step();
+    }
+
+    assert_eq!(value.get(), "hel");
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 1);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_submit() {
+    use crate::native_tree::node::NativeComponent;
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+    use crate::State;
+
+    let submitted = State::new(Vec::<String>::new());
+    let s = submitted.clone();
+
+    let renderer = TestRenderer::new(move || {
+        let s = s.clone();
+
+        TextInput::new()
+            .with_background_text("name")
+            .with_on_submit(move |text| s.update(|s| s.push(text.to_string())))
+            .test_id("name")
+    });
+
+    let input = renderer.find_by_test_id("name").unwrap();
+    match input.native() {
+        NativeComponent::TextInput(t) => assert_eq!(t.background_text(), "name"),
+        _ => unreachable!(),
+    }
+
+    // an uncontrolled input keeps the text of the user
+    input.input_text("bye");
+    input.submit();
+    assert_eq!(submitted.get(), ["bye"]);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_password() {
+    use crate::native_tree::node::NativeComponent;
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+
+    let renderer = TestRenderer::new(|| {
+        TextInput::new()
+            .with_input_type(InputType::Password)
+            .with_max_length(4)
+            .test_id("pin")
+    });
+
+    let pin = renderer.find_by_test_id("pin").unwrap();
+    match pin.native() {
+        NativeComponent::TextInput(t) => assert_eq!(t.input_type(), InputType::Password),
+        _ => unreachable!(),
+    }
+
+    pin.input_text("123456");
+    assert_eq!(pin.text().as_deref(), Some("1234"));
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_mask() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+    use crate::State;
+
+    let changes = State::new(Vec::<String>::new());
+    let c = changes.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let c = c.clone();
+
+        TextInput::new()
+            .with_mask("##/##/####")
+            .with_on_change(move |text| c.update(|c| c.push(text.to_string())))
+            .test_id("date")
+    });
+
+    // the formatted text is passed to on_change
+    let date = renderer.find_by_test_id("date").unwrap();
+    date.input_text("3112");
+    date.input_text("31/12/1999");
+    assert_eq!(date.text().as_deref(), Some("31/12/1999"));
+    assert_eq!(changes.get(), ["31/12", "31/12/1999"]);
+
+    // the mask of the latest render formats the text
+    renderer.render();
+    let date = renderer.find_by_test_id("date").unwrap();
+    date.input_text("01011");
+    assert_eq!(date.text().as_deref(), Some("01/01/1"));
+}
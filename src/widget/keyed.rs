@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use crate::private::{ElementLike, NativeElement};
+use crate::shadow_tree::component::CoreComponent;
+use crate::shadow_tree::render::set_key;
+
+/// Identifies an element among its siblings across renders.
+///
+/// Children of a view are matched with the committed children by key instead of
+/// by position. A keyed child keeps its state and native widget when it is moved,
+/// children without a key are matched by position.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    Int(i64),
+    String(Arc<str>),
+}
+
+macro_rules! impl_key_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Key {
+                fn from(value: $t) -> Self {
+                    Self::Int(value as i64)
+                }
+            }
+        )*
+    };
+}
+
+impl_key_from_int!(i8, i16, i32, i64, u8, u16, u32, u64, isize, usize);
+
+impl From<&str> for Key {
+    fn from(value: &str) -> Self {
+        Self::String(Arc::from(value))
+    }
+}
+
+impl From<String> for Key {
+    fn from(value: String) -> Self {
+        Self::String(Arc::from(value))
+    }
+}
+
+impl From<Arc<str>> for Key {
+    fn from(value: Arc<str>) -> Self {
+        Self::String(value)
+    }
+}
+
+/// Assigns a key to its child, see `Key`.
+pub struct Keyed {
+    key: Key,
+    child: Option<Box<dyn ElementLike>>,
+}
+
+impl Keyed {
+    pub fn new<K: Into<Key>>(key: K, child: impl ElementLike) -> Self {
+        Self {
+            key: key.into(),
+            child: Some(Box::new(child)),
+        }
+    }
+}
+
+impl ElementLike for Keyed {
+    fn as_native(&mut self) -> Option<&mut dyn NativeElement> {
+        None
+    }
+    fn as_element(&mut self) -> Option<&mut dyn crate::Element> {
+        None
+    }
+    fn render(&mut self) -> Result<CoreComponent, Box<dyn ElementLike>> {
+        set_key(self.key.clone());
+
+        Err(self.child.take().expect("keyed element rendered twice"))
+    }
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_keyed_hooks() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::hooks::use_state;
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Text, View};
+    use crate::{Element, ElementLike, State};
+
+    /// an item assigning its own key, shows the id of its hooks
+    struct Item {
+        name: &'static str,
+    }
+
+    impl Element for Item {
+        fn render(&self) -> ElementLike {
+            static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+            let id = use_state(|| NEXT_ID.fetch_add(1, Ordering::SeqCst));
+            let text = format!("{} {}", self.name, id.get());
+
+            Box::new(Keyed::new(self.name, Text::new(text).test_id("item")))
+        }
+    }
+
+    let items = State::new(vec!["a", "b"]);
+    let i = items.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let mut view = View::new();
+
+        for name in i.get() {
+            view.add_child(Item { name });
+        }
+
+        view
+    });
+
+    let texts = |renderer: &TestRenderer| {
+        renderer
+            .root()
+            .unwrap()
+            .children()
+            .iter()
+            .map(|c| c.text().unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(texts(&renderer), ["a 0", "b 1"]);
+
+    // the hooks are matched by the key assigned by the item
+    items.set(vec!["b", "a"]);
+    renderer.step();
+    assert_eq!(texts(&renderer), ["b 1", "a 0"]);
+
+    // only the first item with a duplicated key keeps the hooks
+    items.set(vec!["a", "a"]);
+    renderer.step();
+
+    let texts = texts(&renderer);
+    assert_eq!(texts[0], "a 0");
+    assert_ne!(texts[1], "a 0");
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_keyed_children() {
+    use parking_lot::Mutex;
+
+    use crate::hooks::{on_mount, on_unmount};
+    use crate::testing::TestRenderer;
+    use crate::widget::{Text, View};
+    use crate::{Element, ElementLike, State};
+
+    static LOG: Mutex<Vec<String>> = parking_lot::const_mutex(Vec::new());
+
+    /// logs the creation and removal of its native node
+    struct Item(&'static str);
+
+    impl Element for Item {
+        fn render(&self) -> ElementLike {
+            let name = self.0;
+
+            on_mount(move || LOG.lock().push(format!("mount {}", name)));
+            on_unmount(move || LOG.lock().push(format!("unmount {}", name)));
+
+            Box::new(Text::new(name))
+        }
+    }
+
+    let items = State::new(vec!["a", "b", "c"]);
+    let i = items.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let mut view = View::new();
+
+        for name in i.get() {
+            view.add_child(Keyed::new(name, Item(name)));
+        }
+
+        view
+    });
+
+    let texts = |renderer: &TestRenderer| {
+        renderer
+            .root()
+            .unwrap()
+            .children()
+            .iter()
+            .map(|c| c.text().unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    let mut update = |names: Vec<&'static str>| {
+        items.set(names);
+        renderer.step();
+
+        let log = std::mem::take(&mut *LOG.lock());
+        (texts(&renderer), log)
+    };
+
+    LOG.lock().clear();
+
+    // only the inserted child is created
+    let (texts, log) = update(vec!["d", "a", "b", "c"]);
+    assert_eq!(texts, ["d", "a", "b", "c"]);
+    assert_eq!(log, ["mount d"]);
+
+    // only the removed child is removed
+    let (texts, log) = update(vec!["d", "a", "c"]);
+    assert_eq!(texts, ["d", "a", "c"]);
+    assert_eq!(log, ["unmount b"]);
+
+    // reordered children are moved
+    let (texts, log) = update(vec!["c", "d", "a"]);
+    assert_eq!(texts, ["c", "d", "a"]);
+    assert!(log.is_empty());
+
+    // a duplicated key is created again
+    let (texts, log) = update(vec!["c", "c", "a"]);
+    assert_eq!(texts, ["c", "c", "a"]);
+    assert_eq!(log, ["unmount d", "mount c"]);
+}
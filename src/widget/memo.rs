@@ -0,0 +1,214 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use crate::private::{ElementLike, NativeElement};
+use crate::shadow_tree::component::{CoreComponent, Memoised};
+use crate::shadow_tree::render::memoise;
+
+/// Skips rendering its child if the props are unchanged since the last render.
+///
+/// The previously committed subtree and its native nodes are reused as is,
+/// the child is neither rendered nor compared.
+/// The child is still rendered if a `State` read by the subtree is mutated,
+/// a context used by the subtree is provided with another `Arc`,
+/// or a render is requested with `request_render`.
+pub struct Memo {
+    memo: Memoised,
+    render: Option<Box<dyn FnOnce() -> Box<dyn ElementLike>>>,
+}
+
+impl Memo {
+    /// `render` is only called if `props` is not equal to the props of the last render.
+    pub fn new<P, F, E>(props: P, render: F) -> Self
+    where
+        P: PartialEq + Send + Sync + 'static,
+        F: FnOnce(&P) -> E + 'static,
+        E: ElementLike,
+    {
+        let props = Arc::new(props);
+        let p = props.clone();
+
+        Self {
+            memo: Memoised::new(props, props_eq::<P>),
+            render: Some(Box::new(move || Box::new(render(&p)))),
+        }
+    }
+
+    /// `child` is only rendered if `key` is not equal to the key of the last render.
+    pub fn with_key<K, E>(key: K, child: E) -> Self
+    where
+        K: PartialEq + Send + Sync + 'static,
+        E: ElementLike,
+    {
+        Self {
+            memo: Memoised::new(Arc::new(key), props_eq::<K>),
+            render: Some(Box::new(move || Box::new(child))),
+        }
+    }
+}
+
+fn props_eq<P: PartialEq + 'static>(a: &dyn Any, b: &dyn Any) -> bool {
+    match (a.downcast_ref::<P>(), b.downcast_ref::<P>()) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+impl ElementLike for Memo {
+    fn as_native(&mut self) -> Option<&mut dyn NativeElement> {
+        None
+    }
+    fn as_element(&mut self) -> Option<&mut dyn crate::Element> {
+        None
+    }
+    fn render(&mut self) -> Result<CoreComponent, Box<dyn ElementLike>> {
+        // reuse the committed component if props are unchanged
+        if let Some(component) = memoise(self.memo.clone()) {
+            return Ok(component);
+        }
+
+        let render = self.render.take().expect("memo rendered twice");
+
+        Err(render())
+    }
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_memo() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::hooks::{use_context, use_state};
+    use crate::testing::TestRenderer;
+    use crate::widget::{Button, ElementExt, Provider, Text, View};
+    use crate::{Element, ElementLike, State};
+
+    static RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+    /// a counter showing the theme of its provider
+    struct Counter;
+
+    impl Element for Counter {
+        fn render(&self) -> ElementLike {
+            RENDERS.fetch_add(1, Ordering::SeqCst);
+
+            let count = use_state(|| 0);
+            let theme = use_context::<&'static str>().map_or("none", |t| *t);
+            let c = count.clone();
+
+            Box::new(
+                View::new()
+                    .with_child(Text::new(format!("{} {}", theme, count.get())).test_id("label"))
+                    .with_child(
+                        Button::new()
+                            .with_on_click(move || c.update(|c| *c += 1))
+                            .test_id("increment"),
+                    ),
+            )
+        }
+    }
+
+    let props = State::new(0);
+    let theme = State::new(Arc::new("light"));
+    let other = State::new(0);
+    let (p, t, o) = (props.clone(), theme.clone(), other.clone());
+
+    let mut renderer = TestRenderer::new(move || {
+        // read outside of the memoised subtree
+        let _ = o.get();
+
+        Provider::from_arc(t.get(), Memo::new(p.get(), |_| Counter))
+    });
+
+    let label = |renderer: &TestRenderer| renderer.find_by_test_id("label").unwrap().text();
+
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 1);
+    assert_eq!(label(&renderer).as_deref(), Some("light 0"));
+
+    // unchanged props, states and contexts
+    other.set(1);
+    assert!(renderer.step());
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 1);
+
+    // a state of the memoised subtree
+    renderer.find_by_test_id("increment").unwrap().click();
+    assert!(renderer.step());
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 2);
+    assert_eq!(label(&renderer).as_deref(), Some("light 1"));
+
+    // the props
+    props.set(1);
+    renderer.step();
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 3);
+
+    // the provided context, the hook state is kept
+    theme.set(Arc::new("dark"));
+    renderer.step();
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 4);
+    assert_eq!(label(&renderer).as_deref(), Some("dark 1"));
+
+    other.set(2);
+    renderer.step();
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 4);
+    assert_eq!(label(&renderer).as_deref(), Some("dark 1"));
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_memo_reuse() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::hooks::on_update;
+    use crate::state::request_render;
+    use crate::testing::TestRenderer;
+    use crate::widget::{Text, View};
+    use crate::State;
+
+    static RENDERS: AtomicUsize = AtomicUsize::new(0);
+    static UPDATES: AtomicUsize = AtomicUsize::new(0);
+
+    let key = State::new(0);
+    let other = State::new(0);
+    let (k, o) = (key.clone(), other.clone());
+
+    let mut renderer = TestRenderer::new(move || {
+        let text = format!("other {}", o.get());
+        let key = k.get();
+
+        View::new()
+            .with_child(Text::new(text))
+            .with_child(Memo::new(key, |key| {
+                RENDERS.fetch_add(1, Ordering::SeqCst);
+                on_update(|| {
+                    UPDATES.fetch_add(1, Ordering::SeqCst);
+                });
+
+                Text::new(format!("key {}", key))
+            }))
+    });
+
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 1);
+
+    // the reused subtree is committed as is, its siblings are updated
+    other.set(1);
+    renderer.step();
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 1);
+    assert_eq!(UPDATES.load(Ordering::SeqCst), 0);
+    assert!(renderer.find_by_text("other 1").is_some());
+    assert!(renderer.find_by_text("key 0").is_some());
+
+    // a forced render of the application reuses the subtree
+    renderer.render();
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 1);
+
+    // a requested render does not
+    request_render();
+    renderer.step();
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 2);
+    assert_eq!(UPDATES.load(Ordering::SeqCst), 1);
+
+    key.set(1);
+    renderer.step();
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 3);
+    assert!(renderer.find_by_text("key 1").is_some());
+}
@@ -1,24 +1,290 @@
+/// implements the pointer event setters of a widget with a `pointer: PointerHandlers` field
+macro_rules! impl_pointer_events {
+    ($ty:ty) => {
+        impl $ty {
+            impl_pointer_events!(@setter on_pointer_down, with_on_pointer_down, set_on_pointer_down);
+            impl_pointer_events!(@setter on_pointer_up, with_on_pointer_up, set_on_pointer_up);
+            impl_pointer_events!(@setter on_pointer_move, with_on_pointer_move, set_on_pointer_move);
+            impl_pointer_events!(@setter on_pointer_enter, with_on_pointer_enter, set_on_pointer_enter);
+            impl_pointer_events!(@setter on_pointer_leave, with_on_pointer_leave, set_on_pointer_leave);
+        }
+    };
+    (@setter $field:ident, $with:ident, $set:ident) => {
+        pub fn $with<F>(mut self, callback: F) -> Self
+        where
+            F: Fn(&crate::event::PointerEvent) + Send + Sync + 'static,
+        {
+            self.$set(callback);
+            return self;
+        }
+
+        pub fn $set<F>(&mut self, callback: F)
+        where
+            F: Fn(&crate::event::PointerEvent) + Send + Sync + 'static,
+        {
+            self.pointer.$field = Some(std::sync::Arc::new(callback));
+        }
+    };
+}
+
+/// implements the key event setters of a widget, `$field` is the path to its `KeyHandlers`.
+/// generic widgets are written as `Widget<T>`
+macro_rules! impl_key_events {
+    ($ty:ident $(<$($g:ident),+>)?, $($field:ident).+) => {
+        impl $(<$($g),+>)? $ty $(<$($g),+>)? {
+            /// called when a key is pressed while the element or one of its children is focused
+            pub fn with_on_key_down<F>(mut self, callback: F) -> Self
+            where
+                F: Fn(&crate::event::KeyEvent) + Send + Sync + 'static,
+            {
+                self.set_on_key_down(callback);
+                return self;
+            }
+
+            pub fn set_on_key_down<F>(&mut self, callback: F)
+            where
+                F: Fn(&crate::event::KeyEvent) + Send + Sync + 'static,
+            {
+                self.$($field).+.on_key_down = Some(std::sync::Arc::new(callback));
+            }
+
+            /// called when a key is released while the element or one of its children is focused
+            pub fn with_on_key_up<F>(mut self, callback: F) -> Self
+            where
+                F: Fn(&crate::event::KeyEvent) + Send + Sync + 'static,
+            {
+                self.set_on_key_up(callback);
+                return self;
+            }
+
+            pub fn set_on_key_up<F>(&mut self, callback: F)
+            where
+                F: Fn(&crate::event::KeyEvent) + Send + Sync + 'static,
+            {
+                self.$($field).+.on_key_up = Some(std::sync::Arc::new(callback));
+            }
+        }
+    };
+}
+
+/// implements the focus setters of a widget, `$field` is the path to its `FocusProps`.
+/// generic widgets are written as `Widget<T>`
+macro_rules! impl_focus_events {
+    ($ty:ident $(<$($g:ident),+>)?, $($field:ident).+) => {
+        impl $(<$($g),+>)? $ty $(<$($g),+>)? {
+            /// attach a handle to move the keyboard focus to the element
+            pub fn with_focus_handle(mut self, handle: &crate::focus::FocusHandle) -> Self {
+                self.set_focus_handle(handle);
+                return self;
+            }
+
+            pub fn set_focus_handle(&mut self, handle: &crate::focus::FocusHandle) {
+                self.$($field).+.handle = Some(handle.clone());
+            }
+
+            /// position of the element in tab order, a negative index removes it from tab order
+            pub fn with_tab_index(mut self, index: i32) -> Self {
+                self.set_tab_index(index);
+                return self;
+            }
+
+            pub fn set_tab_index(&mut self, index: i32) {
+                self.$($field).+.tab_index = Some(index);
+            }
+
+            /// called when the element receives the keyboard focus
+            pub fn with_on_focus<F>(mut self, callback: F) -> Self
+            where
+                F: Fn() + Send + Sync + 'static,
+            {
+                self.set_on_focus(callback);
+                return self;
+            }
+
+            pub fn set_on_focus<F>(&mut self, callback: F)
+            where
+                F: Fn() + Send + Sync + 'static,
+            {
+                self.$($field).+.on_focus = Some(std::sync::Arc::new(callback));
+            }
+
+            /// called when the element loses the keyboard focus
+            pub fn with_on_blur<F>(mut self, callback: F) -> Self
+            where
+                F: Fn() + Send + Sync + 'static,
+            {
+                self.set_on_blur(callback);
+                return self;
+            }
+
+            pub fn set_on_blur<F>(&mut self, callback: F)
+            where
+                F: Fn() + Send + Sync + 'static,
+            {
+                self.$($field).+.on_blur = Some(std::sync::Arc::new(callback));
+            }
+        }
+    };
+}
+
+/// implements a toggle widget with a `tree_node: ToggleNode` field,
+/// `$kind` is the `ToggleKind` it is created with
+macro_rules! impl_toggle {
+    ($ty:ident, $kind:expr) => {
+        impl $ty {
+            pub fn new() -> Self {
+                Self {
+                    tree_node: crate::shadow_tree::component::ToggleNode {
+                        kind: $kind,
+                        ..Default::default()
+                    },
+                }
+            }
+
+            pub fn with_style<S: Into<crate::style::StyleRef>>(mut self, style: S) -> Self {
+                self.set_style(style);
+                return self;
+            }
+
+            pub fn set_style<S: Into<crate::style::StyleRef>>(&mut self, style: S) {
+                self.tree_node.style = style.into();
+            }
+
+            pub fn with_label<S: Into<String>>(mut self, label: S) -> Self {
+                self.set_label(label);
+                return self;
+            }
+
+            pub fn set_label<S: Into<String>>(&mut self, label: S) {
+                self.tree_node.label = label.into();
+            }
+
+            pub fn with_checked(mut self, checked: bool) -> Self {
+                self.set_checked(checked);
+                return self;
+            }
+
+            pub fn set_checked(&mut self, checked: bool) {
+                self.tree_node.checked = checked;
+            }
+
+            pub fn with_disabled(mut self, disabled: bool) -> Self {
+                self.set_disabled(disabled);
+                return self;
+            }
+
+            pub fn set_disabled(&mut self, disabled: bool) {
+                self.tree_node.disabled = disabled;
+            }
+
+            /// called with the new state when the user toggles the element
+            pub fn with_on_toggle<F>(mut self, callback: F) -> Self
+            where
+                F: Fn(bool) + Send + Sync + 'static,
+            {
+                self.set_on_toggle(callback);
+                return self;
+            }
+
+            pub fn set_on_toggle<F>(&mut self, callback: F)
+            where
+                F: Fn(bool) + Send + Sync + 'static,
+            {
+                self.tree_node.on_toggle = Some(std::sync::Arc::new(callback))
+            }
+        }
+
+        impl Default for $ty {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl_key_events!($ty, tree_node.meta.keyboard);
+        impl_focus_events!($ty, tree_node.meta.focus);
+
+        impl crate::private::NativeElement for $ty {
+            fn core_component(&mut self) -> crate::shadow_tree::component::CoreComponent {
+                crate::shadow_tree::component::CoreComponent::Toggle(Box::new(
+                    self.tree_node.clone(),
+                ))
+            }
+            fn render(&mut self) {}
+        }
+
+        impl crate::private::ElementLike for $ty {
+            fn as_native(&mut self) -> Option<&mut dyn crate::private::NativeElement> {
+                Some(self)
+            }
+            fn as_element(&mut self) -> Option<&mut dyn crate::Element> {
+                None
+            }
+        }
+    };
+}
+
+pub mod activity_indicator;
 pub mod button;
+pub mod checkbox;
 pub mod flatlist;
 pub mod image_view;
 pub mod input;
+pub mod keyed;
+pub mod memo;
+pub mod modal;
+pub(crate) mod navigation;
+pub mod picker;
+pub mod progress_bar;
+pub mod provider;
 pub mod scrollview;
+pub mod slider;
 pub mod stack_navigator;
+pub mod switch;
+pub mod tab_navigator;
+pub mod test_id;
 pub mod text;
 pub mod text_edit;
 pub mod view;
 
+pub use activity_indicator::ActivityIndicator;
 pub use button::Button;
+pub use checkbox::Checkbox;
 pub use flatlist::ListView;
 pub use image_view::ImageView;
-pub use input::TextInput;
+pub use input::{InputType, TextInput};
+pub use keyed::{Key, Keyed};
+pub use memo::Memo;
+pub use modal::Modal;
+pub use picker::Picker;
+pub use progress_bar::ProgressBar;
+pub use provider::Provider;
 pub use scrollview::ScrollView;
-pub use stack_navigator::{StackNavigator, StackNavigatorElement};
+pub use slider::{Orientation, Slider};
+pub use stack_navigator::{StackNavigator, StackNavigatorElement, StackNavigatorTransition};
+pub use switch::Switch;
+pub use tab_navigator::{Tab, TabBarPosition, TabNavigator, TabNavigatorElement};
+pub use test_id::TestId;
 pub use text::Text;
-pub use text_edit::TextEdit;
+pub use text_edit::{TextEdit, TextEditHandle, WrapMode};
 pub use view::View;
 
 use crate::private::ElementLike;
+
+/// Methods available on every element.
+pub trait ElementExt: ElementLike + Sized {
+    /// assign a key to the element, equivalent to `Keyed::new(key, self)`
+    fn keyed<K: Into<Key>>(self, key: K) -> Keyed {
+        Keyed::new(key, self)
+    }
+
+    /// assign a test id to the element, equivalent to `TestId::new(id, self)`
+    fn test_id(self, id: &str) -> TestId {
+        TestId::new(id, self)
+    }
+}
+
+impl<T: ElementLike> ElementExt for T {}
+
 pub trait BuildableWidget {
     type Builder: WidgetBuilder<Widget = Self>;
     fn builder() -> Self::Builder;
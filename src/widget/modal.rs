@@ -0,0 +1,257 @@
+use std::sync::Arc;
+
+use crate::private::{ElementLike, NativeElement};
+use crate::shadow_tree::component::{CoreComponent, ModalNode};
+use crate::shadow_tree::render::render_element;
+use crate::style::StyleRef;
+
+/// Content presented above the rest of the window.
+///
+/// The child is laid out in its own layer the size of the window,
+/// the style of the modal positions the child within the layer.
+/// Pointer events do not reach the content below a visible modal.
+///
+/// The modal is controlled: it is shown while the `visible` prop is true,
+/// `on_request_close` is called when the backdrop is pressed or Escape is pressed
+/// and should hide the modal.
+pub struct Modal {
+    style: StyleRef,
+    visible: bool,
+    transparent: bool,
+    on_request_close: Option<Arc<dyn Fn() + Send + Sync>>,
+    child: Option<Box<dyn ElementLike>>,
+    rendered_child: Option<CoreComponent>,
+}
+
+impl Modal {
+    pub fn new() -> Self {
+        Self {
+            style: StyleRef::DEFAULT,
+            visible: false,
+            transparent: false,
+            on_request_close: None,
+            child: None,
+            rendered_child: None,
+        }
+    }
+
+    pub fn with_child(mut self, child: impl ElementLike) -> Self {
+        self.child = Some(Box::new(child));
+        return self;
+    }
+
+    pub fn set_child(&mut self, child: impl ElementLike) {
+        self.child = Some(Box::new(child));
+    }
+
+    pub fn with_style<S: Into<StyleRef>>(mut self, style: S) -> Self {
+        self.set_style(style);
+        return self;
+    }
+
+    pub fn set_style<S: Into<StyleRef>>(&mut self, style: S) {
+        self.style = style.into();
+    }
+
+    /// show the modal, the child is only rendered while visible
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.set_visible(visible);
+        return self;
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    /// do not dim the content below the modal
+    pub fn with_transparent(mut self, transparent: bool) -> Self {
+        self.set_transparent(transparent);
+        return self;
+    }
+
+    pub fn set_transparent(&mut self, transparent: bool) {
+        self.transparent = transparent;
+    }
+
+    /// called when the user asks to dismiss the modal
+    pub fn with_on_request_close<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.set_on_request_close(callback);
+        return self;
+    }
+
+    pub fn set_on_request_close<F>(&mut self, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_request_close = Some(Arc::new(callback))
+    }
+}
+
+impl Default for Modal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NativeElement for Modal {
+    fn core_component(&mut self) -> CoreComponent {
+        CoreComponent::Modal(Box::new(ModalNode {
+            id: None,
+            style: self.style.clone(),
+            visible: self.visible,
+            transparent: self.transparent,
+            on_request_close: self.on_request_close.clone(),
+            child: self.rendered_child.take(),
+            meta: Default::default(),
+        }))
+    }
+    fn render(&mut self) {
+        if !self.visible {
+            return;
+        }
+
+        if let Some(child) = &mut self.child {
+            // render the child until core component is reached
+            let comp = render_element(child, 0);
+
+            self.rendered_child = Some(comp)
+        }
+    }
+}
+
+impl ElementLike for Modal {
+    fn as_native(&mut self) -> Option<&mut dyn NativeElement> {
+        Some(self)
+    }
+    fn as_element(&mut self) -> Option<&mut dyn crate::Element> {
+        None
+    }
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_modal_visible() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Text};
+    use crate::State;
+
+    let open = State::new(false);
+    let o = open.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        Modal::new()
+            .with_visible(o.get())
+            .with_child(Text::new("dialog").test_id("dialog"))
+    });
+
+    // the child is not rendered while hidden
+    assert!(renderer.find_by_test_id("dialog").is_none());
+
+    open.set(true);
+    renderer.step();
+    assert!(renderer.find_by_test_id("dialog").is_some());
+
+    open.set(false);
+    renderer.step();
+    assert!(renderer.find_by_test_id("dialog").is_none());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_modal_layout() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Text, View};
+
+    let renderer = TestRenderer::new(|| {
+        View::new().with_child(Text::new("below")).with_child(
+            Modal::new()
+                .with_visible(true)
+                .with_child(Text::new("dialog").test_id("dialog"))
+                .test_id("modal"),
+        )
+    });
+
+    // the modal fills the window in its own layer, the view is laid out without it
+    let modal = renderer.find_by_test_id("modal").unwrap();
+    assert_eq!(modal.layout().width, 320.0);
+    assert_eq!(modal.layout().height, 320.0);
+    assert_eq!(renderer.find_by_text("below").unwrap().layout().x, 0.0);
+
+    // the auto margins centre the six characters in the window
+    let dialog = renderer.find_by_test_id("dialog").unwrap();
+    assert_eq!(dialog.absolute_layout().x, (320.0 - 6.0 * 8.0) / 2.0);
+    assert_eq!(dialog.widget().record().frame, dialog.layout());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_modal_pointer() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Text, View};
+    use crate::State;
+
+    let open = State::new(true);
+    let presses = State::new(0);
+    let (o, p) = (open.clone(), presses.clone());
+
+    let renderer = TestRenderer::new(move || {
+        let (o1, p1) = (o.clone(), p.clone());
+
+        View::new()
+            .with_child(
+                Text::new("below")
+                    .with_on_pointer_down(move |_| p1.update(|p| *p += 1))
+                    .test_id("below"),
+            )
+            .with_child(
+                Modal::new()
+                    .with_visible(o.get())
+                    .with_on_request_close(move || o1.set(false))
+                    .with_child(Text::new("dialog").test_id("dialog")),
+            )
+    });
+
+    // the text below the modal can not be pressed,
+    // the dialog itself does not close the modal
+    let below = renderer.find_by_test_id("below").unwrap().absolute_layout();
+    renderer.pointer_down(below.x + 1.0, below.y + 1.0);
+    renderer.find_by_test_id("dialog").unwrap().press();
+    assert_eq!(presses.get(), 0);
+    assert!(open.get());
+
+    // releasing the pointer on the backdrop requests to close the modal
+    renderer.pointer_up(below.x + 1.0, below.y + 1.0);
+    assert!(!open.get());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_modal_escape() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Text};
+    use crate::State;
+
+    let open = State::new(true);
+    let o = open.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let o1 = o.clone();
+
+        Modal::new()
+            .with_visible(o.get())
+            .with_on_request_close(move || o1.set(false))
+            .with_child(Text::new("dialog"))
+    });
+
+    renderer.key_down("Escape");
+    assert!(!open.get());
+
+    // escape does nothing once the modal is hidden
+    renderer.step();
+    open.set(true);
+    renderer.key_down("Escape");
+    assert!(open.get());
+}
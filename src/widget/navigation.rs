@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::router::RouteParams;
+use crate::shadow_tree::render::find_context;
+
+/// A navigator the pages of which may contain other navigators.
+///
+/// public to bound `Router::new`, the module is private and only
+/// `StackNavigator` and `TabNavigator` implement it.
+pub trait ParentNavigator: Send + Sync {
+    /// go back in the navigator, bubbles to its own parent
+    fn goback(&self);
+    /// go to the page at the path, the last page receives the params
+    fn route(&self, path: &str, params: Option<RouteParams>);
+    /// the visible page and the params it was routed with
+    fn location(&self) -> Option<(String, Option<RouteParams>)>;
+    fn links(&self) -> &NavigatorLinks;
+}
+
+/// provided to the pages of a navigator, a nested navigator finds its parent with it
+pub(crate) struct NavigatorScope {
+    pub parent: Box<dyn ParentNavigator>,
+    /// the page of the parent the nested navigator is in
+    pub page: String,
+}
+
+/// links of a navigator to the navigators it is nested in and nested in it
+#[derive(Default)]
+pub struct NavigatorLinks {
+    /// set when the navigator is rendered in the page of another navigator
+    parent: Mutex<Option<Arc<NavigatorScope>>>,
+    /// remaining paths by page, navigated to once the nested navigator is rendered
+    pending: Mutex<HashMap<String, (String, Option<RouteParams>)>>,
+    /// navigators nested in the pages, by page
+    children: Mutex<HashMap<String, Arc<dyn ParentNavigator>>>,
+}
+
+impl NavigatorLinks {
+    pub(crate) fn parent(&self) -> Option<Arc<NavigatorScope>> {
+        self.parent.lock().clone()
+    }
+
+    pub(crate) fn set_parent(&self, scope: Option<Arc<NavigatorScope>>) {
+        *self.parent.lock() = scope;
+    }
+
+    pub(crate) fn set_pending(&self, page: &str, path: &str, params: Option<RouteParams>) {
+        self.pending
+            .lock()
+            .insert(page.to_string(), (path.to_string(), params));
+    }
+
+    pub(crate) fn take_pending(&self, page: &str) -> Option<(String, Option<RouteParams>)> {
+        self.pending.lock().remove(page)
+    }
+
+    /// the navigator nested in the page, if rendered
+    pub(crate) fn child(&self, page: &str) -> Option<Arc<dyn ParentNavigator>> {
+        self.children.lock().get(page).cloned()
+    }
+
+    fn set_child(&self, page: &str, navigator: Arc<dyn ParentNavigator>) {
+        self.children.lock().insert(page.to_string(), navigator);
+    }
+}
+
+impl std::fmt::Debug for NavigatorLinks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NavigatorLinks")
+            .field("nested", &self.parent.lock().is_some())
+            .field("pending", &*self.pending.lock())
+            .field("children", &self.children.lock().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// called when a navigator is rendered, links it to the navigator it is nested in.
+/// returns the path and params the parent left to navigate to, if any
+pub(crate) fn link_to_parent<N>(navigator: &N) -> Option<(String, Option<RouteParams>)>
+where
+    N: ParentNavigator + Clone + 'static,
+{
+    let scope = find_context::<NavigatorScope>();
+
+    let pending = scope.as_ref().and_then(|scope| {
+        let links = scope.parent.links();
+        links.set_child(&scope.page, Arc::new(navigator.clone()));
+        links.take_pending(&scope.page)
+    });
+
+    navigator.links().set_parent(scope);
+
+    return pending;
+}
+
+/// split `settings/profile` into the page `settings` and the path `profile` within it
+pub(crate) fn split_path(path: &str) -> (&str, Option<&str>) {
+    let path = path.trim_matches('/');
+
+    match path.split_once('/') {
+        Some((page, rest)) => (page, Some(rest)),
+        None => (path, None),
+    }
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_nested_navigate() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{StackNavigator, Text};
+
+    let outer = StackNavigator::new();
+    let inner = StackNavigator::new();
+    let (o, i) = (outer.clone(), inner.clone());
+
+    let mut renderer = TestRenderer::new(move || {
+        let i = i.clone();
+
+        o.navigator()
+            .with_page("home", |_| Text::new("home page"))
+            .with_page("settings", move |_| {
+                i.navigator()
+                    .with_page("general", |_| Text::new("general page"))
+                    .with_page("profile", |_| Text::new("profile page"))
+            })
+    });
+
+    renderer.step();
+    assert!(renderer.find_by_text("home page").is_some());
+
+    // the nested navigator navigates once the settings page is rendered
+    outer.navigate("settings/profile");
+    renderer.step_n(2);
+    assert!(renderer.find_by_text("profile page").is_some());
+    assert_eq!(outer.history(), ["home", "settings"]);
+    assert_eq!(inner.history(), ["general", "profile"]);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_nested_goback() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{StackNavigator, Text};
+
+    let outer = StackNavigator::new();
+    let inner = StackNavigator::new();
+    let (o, i) = (outer.clone(), inner.clone());
+
+    let mut renderer = TestRenderer::new(move || {
+        let i = i.clone();
+
+        o.navigator()
+            .with_page("home", |_| Text::new("home page"))
+            .with_page("settings", move |_| {
+                i.navigator()
+                    .with_page("general", |_| Text::new("general page"))
+                    .with_page("profile", |_| Text::new("profile page"))
+            })
+    });
+
+    renderer.step();
+    assert!(renderer.find_by_text("home page").is_some());
+
+    outer.navigate("settings/profile");
+    renderer.step_n(2);
+
+    inner.goback();
+    renderer.step();
+    assert!(renderer.find_by_text("general page").is_some());
+    assert_eq!(outer.history(), ["home", "settings"]);
+
+    // the nested navigator is at its first page, the outer navigator goes back
+    inner.goback();
+    renderer.step();
+    assert!(renderer.find_by_text("home page").is_some());
+    assert_eq!(outer.history(), ["home"]);
+}
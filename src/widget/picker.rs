@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use crate::private::{ElementLike, NativeElement};
+use crate::shadow_tree::component::{CoreComponent, PickerNode};
+use crate::style::StyleRef;
+
+/// A dropdown to pick one of a list of labelled options.
+///
+/// The picker is controlled: it always shows the `selected` prop,
+/// `on_select` should update the state the prop is rendered from.
+pub struct Picker<T> {
+    options: Vec<T>,
+    on_select: Option<Arc<dyn Fn(usize, &T) + Send + Sync>>,
+    tree_node: PickerNode,
+}
+
+impl<T: Send + Sync + 'static> Picker<T> {
+    pub fn new() -> Self {
+        Self {
+            options: Vec::new(),
+            on_select: None,
+            tree_node: Default::default(),
+        }
+    }
+
+    pub fn with_style<S: Into<StyleRef>>(mut self, style: S) -> Self {
+        self.set_style(style);
+        return self;
+    }
+
+    pub fn set_style<S: Into<StyleRef>>(&mut self, style: S) {
+        self.tree_node.style = style.into();
+    }
+
+    /// append an option shown as `label`
+    pub fn with_option<S: Into<String>>(mut self, label: S, value: T) -> Self {
+        self.add_option(label, value);
+        return self;
+    }
+
+    pub fn add_option<S: Into<String>>(&mut self, label: S, value: T) {
+        self.tree_node.options.push(label.into());
+        self.options.push(value);
+    }
+
+    /// append options from `(label, value)` pairs
+    pub fn with_options<S, I>(mut self, options: I) -> Self
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = (S, T)>,
+    {
+        for (label, value) in options {
+            self.add_option(label, value);
+        }
+        return self;
+    }
+
+    /// the index of the selected option, none to show no selection
+    pub fn with_selected(mut self, selected: Option<usize>) -> Self {
+        self.set_selected(selected);
+        return self;
+    }
+
+    pub fn set_selected(&mut self, selected: Option<usize>) {
+        self.tree_node.selected = selected;
+    }
+
+    /// called with the index and the value of the option picked by the user
+    pub fn with_on_select<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize, &T) + Send + Sync + 'static,
+    {
+        self.set_on_select(callback);
+        return self;
+    }
+
+    pub fn set_on_select<F>(&mut self, callback: F)
+    where
+        F: Fn(usize, &T) + Send + Sync + 'static,
+    {
+        self.on_select = Some(Arc::new(callback))
+    }
+}
+
+impl<T: Send + Sync + 'static> Default for Picker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl_key_events!(Picker<T>, tree_node.meta.keyboard);
+impl_focus_events!(Picker<T>, tree_node.meta.focus);
+
+impl<T: Clone + Send + Sync + 'static> NativeElement for Picker<T> {
+    fn core_component(&mut self) -> CoreComponent {
+        let mut node = self.tree_node.clone();
+
+        // the native picker only knows the index of the option
+        if let Some(callback) = &self.on_select {
+            let callback = callback.clone();
+            let options: Arc<[T]> = self.options.clone().into();
+
+            node.on_select = Some(Arc::new(move |index| {
+                if let Some(value) = options.get(index) {
+                    callback(index, value)
+                }
+            }));
+        }
+
+        CoreComponent::Picker(Box::new(node))
+    }
+    fn render(&mut self) {}
+}
+
+impl<T: Clone + Send + Sync + 'static> ElementLike for Picker<T> {
+    fn as_native(&mut self) -> Option<&mut dyn NativeElement> {
+        Some(self)
+    }
+    fn as_element(&mut self) -> Option<&mut dyn crate::Element> {
+        None
+    }
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_picker_select() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+    use crate::State;
+
+    let picked = State::new(None);
+    let p = picked.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let p1 = p.clone();
+        let selected = p.get().map(|index: usize| index - 1);
+
+        Picker::new()
+            .with_option("Apple", 1)
+            .with_option("Cherry", 2)
+            .with_selected(selected)
+            .with_on_select(move |_, value| p1.set(Some(*value)))
+            .test_id("fruit")
+    });
+
+    let picker = renderer.find_by_test_id("fruit").unwrap();
+    assert_eq!(picker.options(), ["Apple", "Cherry"]);
+    assert_eq!(picker.selected(), None);
+
+    // the value of the option is passed to on_select
+    picker.pick(1);
+    renderer.step();
+    assert_eq!(picked.get(), Some(2));
+
+    let picker = renderer.find_by_test_id("fruit").unwrap();
+    assert_eq!(picker.selected(), Some(1));
+    assert_eq!(picker.text().as_deref(), Some("Cherry"));
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_picker_options() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+    use crate::State;
+
+    let fruits = State::new(vec!["Apple", "Cherry"]);
+    let f = fruits.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let fruits = f.get();
+        let selected = fruits.iter().position(|f| *f == "Cherry");
+
+        Picker::new()
+            .with_options(fruits.into_iter().map(|f| (f, f)))
+            .with_selected(selected)
+            .test_id("fruit")
+    });
+
+    let widget = renderer.find_by_test_id("fruit").unwrap().widget();
+
+    // the option is inserted into the same picker and the selection follows the option
+    fruits.set(vec!["Apple", "Banana", "Cherry"]);
+    renderer.step();
+
+    let picker = renderer.find_by_test_id("fruit").unwrap();
+    assert!(picker.widget().ptr_eq(&widget));
+    assert_eq!(picker.options(), ["Apple", "Banana", "Cherry"]);
+    assert_eq!(picker.selected(), Some(2));
+
+    fruits.set(vec!["Cherry"]);
+    renderer.step();
+
+    let picker = renderer.find_by_test_id("fruit").unwrap();
+    assert_eq!(picker.options(), ["Cherry"]);
+    assert_eq!(picker.selected(), Some(0));
+}
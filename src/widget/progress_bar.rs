@@ -0,0 +1,181 @@
+use crate::private::{ElementLike, NativeElement};
+use crate::shadow_tree::component::{CoreComponent, ProgressBarNode};
+use crate::style::StyleRef;
+
+/// A bar showing the progress of an operation.
+///
+/// The bar is filled to `fraction` of its width, or pulses back and forth
+/// if the progress is indeterminate. The colour of the style fills the bar.
+pub struct ProgressBar {
+    tree_node: ProgressBarNode,
+}
+
+impl ProgressBar {
+    pub fn new() -> Self {
+        Self {
+            tree_node: Default::default(),
+        }
+    }
+
+    pub fn with_style<S: Into<StyleRef>>(mut self, style: S) -> Self {
+        self.set_style(style);
+        return self;
+    }
+
+    pub fn set_style<S: Into<StyleRef>>(&mut self, style: S) {
+        self.tree_node.style = style.into();
+    }
+
+    /// the completed fraction from 0 to 1
+    pub fn with_fraction(mut self, fraction: f64) -> Self {
+        self.set_fraction(fraction);
+        return self;
+    }
+
+    pub fn set_fraction(&mut self, fraction: f64) {
+        self.tree_node.fraction = fraction.clamp(0.0, 1.0);
+    }
+
+    /// pulse the bar instead of showing the fraction when the progress is unknown
+    pub fn with_indeterminate(mut self, indeterminate: bool) -> Self {
+        self.set_indeterminate(indeterminate);
+        return self;
+    }
+
+    pub fn set_indeterminate(&mut self, indeterminate: bool) {
+        self.tree_node.indeterminate = indeterminate;
+    }
+
+    /// text shown alongside the bar, e.g. "3 of 10 files"
+    pub fn with_text<S: Into<String>>(mut self, text: S) -> Self {
+        self.set_text(text);
+        return self;
+    }
+
+    pub fn set_text<S: Into<String>>(&mut self, text: S) {
+        self.tree_node.text = Some(text.into());
+    }
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NativeElement for ProgressBar {
+    fn core_component(&mut self) -> CoreComponent {
+        CoreComponent::ProgressBar(Box::new(self.tree_node.clone()))
+    }
+    fn render(&mut self) {}
+}
+
+impl ElementLike for ProgressBar {
+    fn as_native(&mut self) -> Option<&mut dyn NativeElement> {
+        Some(self)
+    }
+    fn as_element(&mut self) -> Option<&mut dyn crate::Element> {
+        None
+    }
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_progress_fraction() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+
+    let renderer = TestRenderer::new(|| {
+        ProgressBar::new()
+            .with_fraction(0.5)
+            .with_text("50%")
+            .test_id("progress")
+    });
+
+    let progress = renderer.find_by_test_id("progress").unwrap();
+    assert!(!progress.is_animating());
+    assert_eq!(progress.value(), 0.5);
+    assert_eq!(progress.text().as_deref(), Some("50%"));
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_progress_indeterminate() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+    use crate::State;
+
+    let loaded = State::new(None);
+    let l = loaded.clone();
+
+    let mut renderer = TestRenderer::new(move || match l.get() {
+        Some(fraction) => ProgressBar::new()
+            .with_fraction(fraction)
+            .test_id("progress"),
+        None => ProgressBar::new()
+            .with_indeterminate(true)
+            .test_id("progress"),
+    });
+
+    let progress = renderer.find_by_test_id("progress").unwrap();
+    assert!(progress.is_animating());
+    assert_eq!(progress.text(), None);
+    let widget = progress.widget();
+
+    loaded.set(Some(0.5));
+    renderer.step();
+
+    // the same bar stops pulsing and shows the fraction
+    let progress = renderer.find_by_test_id("progress").unwrap();
+    assert!(progress.widget().ptr_eq(&widget));
+    assert!(!progress.is_animating());
+    assert_eq!(progress.value(), 0.5);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_progress_colour() {
+    use crate::style::{Colour, Style};
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+    use crate::State;
+
+    static GREEN: Style = Style {
+        colour: Some(Colour {
+            r: 0,
+            g: 128,
+            b: 0,
+            a: 255,
+        }),
+        background_colour: Some(Colour::BLACK),
+        ..Style::DEFAULT
+    };
+    static RED: Style = Style {
+        colour: Some(Colour {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        }),
+        ..Style::DEFAULT
+    };
+
+    let failed = State::new(false);
+    let f = failed.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        ProgressBar::new()
+            .with_style(if f.get() { &RED } else { &GREEN })
+            .test_id("progress")
+    });
+
+    let record = renderer.find_by_test_id("progress").unwrap().widget();
+    assert_eq!(record.record().style.colour, GREEN.colour);
+    assert_eq!(record.record().style.background_colour, Some(Colour::BLACK));
+
+    // the colours are applied again when the style changes
+    failed.set(true);
+    renderer.step();
+    let record = renderer.find_by_test_id("progress").unwrap().widget();
+    assert_eq!(record.record().style.colour, RED.colour);
+}
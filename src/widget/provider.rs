@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use crate::private::{ElementLike, NativeElement};
+use crate::shadow_tree::component::CoreComponent;
+use crate::shadow_tree::render::provide_context;
+
+/// Provides a value to all the descendants of its child.
+///
+/// The value can be retrieved during `render` with `hooks::use_context::<T>()`,
+/// which resolves the nearest ancestor provider of type `T`.
+pub struct Provider<T: Send + Sync + 'static> {
+    value: Arc<T>,
+    child: Option<Box<dyn ElementLike>>,
+}
+
+impl<T: Send + Sync + 'static> Provider<T> {
+    pub fn new(value: T, child: impl ElementLike) -> Self {
+        Self::from_arc(Arc::new(value), child)
+    }
+
+    /// provide a shared value without cloning it
+    pub fn from_arc(value: Arc<T>, child: impl ElementLike) -> Self {
+        Self {
+            value,
+            child: Some(Box::new(child)),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> ElementLike for Provider<T> {
+    fn as_native(&mut self) -> Option<&mut dyn NativeElement> {
+        None
+    }
+    fn as_element(&mut self) -> Option<&mut dyn crate::Element> {
+        None
+    }
+    fn render(&mut self) -> Result<CoreComponent, Box<dyn ElementLike>> {
+        // the value is visible until the child is fully rendered
+        provide_context(self.value.clone());
+
+        Err(self.child.take().expect("provider rendered twice"))
+    }
+}
+
+/// shows the nearest theme and size
+#[cfg(all(test, feature = "headless"))]
+struct Label(&'static str);
+
+#[cfg(all(test, feature = "headless"))]
+impl crate::Element for Label {
+    fn render(&self) -> crate::ElementLike {
+        use crate::hooks::use_context;
+        use crate::widget::{ElementExt, Text};
+
+        let theme = use_context::<&'static str>().map_or("none", |t| *t);
+        let size = use_context::<u32>().map_or(0, |s| *s);
+
+        Box::new(Text::new(format!("{} {}", theme, size)).test_id(self.0))
+    }
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_provider() {
+    use crate::testing::TestRenderer;
+    use crate::widget::View;
+    use crate::State;
+
+    let theme = State::new("light");
+    let t = theme.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        View::new()
+            .with_child(Label("outside"))
+            .with_child(Provider::new(
+                t.get(),
+                View::new()
+                    .with_child(Label("inner"))
+                    // the nearest provider of a type is used
+                    .with_child(Provider::new("dark", Label("nested")))
+                    // providers of other types are visible
+                    .with_child(Provider::new(12u32, Label("sized"))),
+            ))
+            // the value is not visible to the siblings of the provider
+            .with_child(Label("sibling"))
+    });
+
+    let text = |renderer: &TestRenderer, id: &str| renderer.find_by_test_id(id).unwrap().text();
+
+    assert_eq!(text(&renderer, "outside").as_deref(), Some("none 0"));
+    assert_eq!(text(&renderer, "inner").as_deref(), Some("light 0"));
+    assert_eq!(text(&renderer, "nested").as_deref(), Some("dark 0"));
+    assert_eq!(text(&renderer, "sized").as_deref(), Some("light 12"));
+    assert_eq!(text(&renderer, "sibling").as_deref(), Some("none 0"));
+
+    // the descendants read the new value
+    theme.set("blue");
+    renderer.step();
+    assert_eq!(text(&renderer, "inner").as_deref(), Some("blue 0"));
+    assert_eq!(text(&renderer, "nested").as_deref(), Some("dark 0"));
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_provider_scroll_view() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ScrollView;
+    use crate::State;
+
+    let theme = State::new("light");
+    let t = theme.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        Provider::new(t.get(), ScrollView::new().with_child(Label("scrolled")))
+    });
+
+    let text = |renderer: &TestRenderer| renderer.find_by_test_id("scrolled").unwrap().text();
+    assert_eq!(text(&renderer).as_deref(), Some("light 0"));
+
+    theme.set("dark");
+    renderer.step();
+    assert_eq!(text(&renderer).as_deref(), Some("dark 0"));
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_provider_stack_page() {
+    use crate::testing::TestRenderer;
+    use crate::widget::StackNavigator;
+    use crate::State;
+
+    let theme = State::new("light");
+    let navigator = StackNavigator::new();
+    let (t, nav) = (theme.clone(), navigator.clone());
+
+    let mut renderer = TestRenderer::new(move || {
+        Provider::new(
+            t.get(),
+            nav.navigator()
+                .with_page("home", |_| Label("home"))
+                .with_page("details", |_| Provider::new(3u32, Label("details"))),
+        )
+    });
+
+    let text = |renderer: &TestRenderer, id: &str| renderer.find_by_test_id(id).unwrap().text();
+    assert_eq!(text(&renderer, "home").as_deref(), Some("light 0"));
+
+    navigator.push("details");
+    renderer.step();
+    assert_eq!(text(&renderer, "details").as_deref(), Some("light 3"));
+
+    theme.set("dark");
+    renderer.step();
+    assert_eq!(text(&renderer, "details").as_deref(), Some("dark 3"));
+}
@@ -1,5 +1,6 @@
 use crate::private::{ElementLike, NativeElement};
 use crate::shadow_tree::component::{CoreComponent, ScrollViewNode};
+use crate::shadow_tree::render::render_element;
 use crate::style::StyleRef;
 
 pub struct ScrollView {
@@ -42,21 +43,13 @@ impl NativeElement for ScrollView {
             id: None,
             style: self.style.clone(),
             child: self.rendered_child.take(),
+            meta: Default::default(),
         }))
     }
     fn render(&mut self) {
         if let Some(child) = &mut self.child {
-            // render the child
-            let mut elem = child.render();
-            // keep rendering until core component is reached
-            let comp = loop {
-                match elem {
-                    Ok(c) => break c,
-                    Err(mut e) => {
-                        elem = e.render();
-                    }
-                }
-            };
+            // render the child until core component is reached
+            let comp = render_element(child, 0);
 
             self.rendered_child = Some(comp)
         }
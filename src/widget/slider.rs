@@ -0,0 +1,224 @@
+use std::sync::Arc;
+
+use crate::private::{ElementLike, NativeElement};
+use crate::shadow_tree::component::{CoreComponent, SliderNode};
+use crate::style::StyleRef;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+
+/// A slider to pick a number in a range.
+///
+/// The slider is controlled: it always shows the `value` prop,
+/// `on_change` should update the state the prop is rendered from.
+pub struct Slider {
+    tree_node: SliderNode,
+}
+
+impl Slider {
+    /// a continuous slider from 0 to 1
+    pub fn new() -> Self {
+        Self {
+            tree_node: SliderNode {
+                max: 1.0,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn with_style<S: Into<StyleRef>>(mut self, style: S) -> Self {
+        self.set_style(style);
+        return self;
+    }
+
+    pub fn set_style<S: Into<StyleRef>>(&mut self, style: S) {
+        self.tree_node.style = style.into();
+    }
+
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.set_range(min, max);
+        return self;
+    }
+
+    pub fn set_range(&mut self, min: f64, max: f64) {
+        self.tree_node.min = min;
+        self.tree_node.max = max;
+    }
+
+    /// snap the value to multiples of `step` from the minimum, zero for a continuous slider
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.set_step(step);
+        return self;
+    }
+
+    pub fn set_step(&mut self, step: f64) {
+        self.tree_node.step = step;
+    }
+
+    pub fn with_value(mut self, value: f64) -> Self {
+        self.set_value(value);
+        return self;
+    }
+
+    pub fn set_value(&mut self, value: f64) {
+        self.tree_node.value = value;
+    }
+
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.set_orientation(orientation);
+        return self;
+    }
+
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.tree_node.orientation = orientation;
+    }
+
+    /// called with the new value while the user moves the slider
+    pub fn with_on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(f64) + Send + Sync + 'static,
+    {
+        self.set_on_change(callback);
+        return self;
+    }
+
+    pub fn set_on_change<F>(&mut self, callback: F)
+    where
+        F: Fn(f64) + Send + Sync + 'static,
+    {
+        self.tree_node.on_change = Some(Arc::new(callback))
+    }
+
+    /// called with the final value once the user releases the slider
+    pub fn with_on_release<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(f64) + Send + Sync + 'static,
+    {
+        self.set_on_release(callback);
+        return self;
+    }
+
+    pub fn set_on_release<F>(&mut self, callback: F)
+    where
+        F: Fn(f64) + Send + Sync + 'static,
+    {
+        self.tree_node.on_release = Some(Arc::new(callback))
+    }
+}
+
+impl Default for Slider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl_key_events!(Slider, tree_node.meta.keyboard);
+impl_focus_events!(Slider, tree_node.meta.focus);
+
+impl NativeElement for Slider {
+    fn core_component(&mut self) -> CoreComponent {
+        CoreComponent::Slider(Box::new(self.tree_node.clone()))
+    }
+    fn render(&mut self) {}
+}
+
+impl ElementLike for Slider {
+    fn as_native(&mut self) -> Option<&mut dyn NativeElement> {
+        Some(self)
+    }
+    fn as_element(&mut self) -> Option<&mut dyn crate::Element> {
+        None
+    }
+}
+
+/// clamp the value to the range and round it to the nearest step
+pub(crate) fn snap_value(value: f64, min: f64, max: f64, step: f64) -> f64 {
+    let (low, high) = if min <= max { (min, max) } else { (max, min) };
+
+    let mut value = value.clamp(low, high);
+
+    if step > 0.0 {
+        value = low + ((value - low) / step).round() * step;
+        // the last step may exceed the range
+        value = value.min(high);
+    }
+
+    return value;
+}
+
+#[test]
+fn test_snap_value() {
+    // rounded to the nearest step from the minimum
+    assert_eq!(snap_value(43.0, 0.0, 100.0, 10.0), 40.0);
+    assert_eq!(snap_value(45.0, 5.0, 100.0, 10.0), 45.0);
+    assert_eq!(snap_value(12.0, 5.0, 100.0, 10.0), 15.0);
+
+    // clamped to the range, the last step is cut at the maximum
+    assert_eq!(snap_value(250.0, 0.0, 100.0, 10.0), 100.0);
+    assert_eq!(snap_value(-5.0, 0.0, 100.0, 10.0), 0.0);
+    assert_eq!(snap_value(99.0, 0.0, 95.0, 10.0), 95.0);
+    assert_eq!(snap_value(89.0, 0.0, 95.0, 10.0), 90.0);
+
+    // a reversed range and no step
+    assert_eq!(snap_value(150.0, 100.0, 0.0, 0.0), 100.0);
+    assert_eq!(snap_value(43.3, 0.0, 100.0, 0.0), 43.3);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_slider_change() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+    use crate::State;
+
+    let volume = State::new(20.0);
+    let released = State::new(None);
+    let (v, r) = (volume.clone(), released.clone());
+
+    let mut renderer = TestRenderer::new(move || {
+        let (v1, r1) = (v.clone(), r.clone());
+
+        Slider::new()
+            .with_range(0.0, 100.0)
+            .with_step(10.0)
+            .with_value(v.get())
+            .with_on_change(move |value| v1.set(value))
+            .with_on_release(move |value| r1.set(Some(value)))
+            .test_id("volume")
+    });
+
+    assert_eq!(renderer.find_by_test_id("volume").unwrap().value(), 20.0);
+
+    // the value passed to the callbacks is snapped
+    renderer.find_by_test_id("volume").unwrap().slide_to(43.0);
+    renderer.step();
+    assert_eq!(volume.get(), 40.0);
+    assert_eq!(released.get(), Some(40.0));
+    assert_eq!(renderer.find_by_test_id("volume").unwrap().value(), 40.0);
+
+    renderer.find_by_test_id("volume").unwrap().slide_to(250.0);
+    renderer.step();
+    assert_eq!(volume.get(), 100.0);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_slider_orientation() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, View};
+
+    let renderer = TestRenderer::new(|| {
+        View::new().with_child(
+            Slider::new()
+                .with_orientation(Orientation::Vertical)
+                .test_id("vertical"),
+        )
+    });
+
+    let vertical = renderer.find_by_test_id("vertical").unwrap();
+    assert!(vertical.layout().height > vertical.layout().width);
+}
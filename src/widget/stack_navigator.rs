@@ -1,22 +1,74 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
 use crossbeam_channel::{Receiver, Sender};
+use parking_lot::{Mutex, RwLock};
 
 use crate::private::{ElementLike, NativeElement};
+use crate::router::RouteParams;
 use crate::shadow_tree::component::{CoreComponent, NavigatorCommand, StackNavigaterNode};
+use crate::shadow_tree::render::render_element;
 use crate::shadow_tree::NodeID;
+use crate::state::mark_dirty;
 use crate::style::StyleRef;
+use crate::widget::keyed::Keyed;
+use crate::widget::navigation::{
+    link_to_parent, split_path, NavigatorLinks, NavigatorScope, ParentNavigator,
+};
+use crate::widget::provider::Provider;
 
+/// Animation shown when the visible page of a `StackNavigator` changes.
+///
+/// Transitions are direction aware: a pushed page slides in from the right
+/// and slides back out to the right when going back.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum StackNavigatorTransition {
     None,
     Fade,
+    #[default]
     Slide,
 }
 
+/// navigation events of a page, called by the native navigator
+#[derive(Clone, Default)]
+pub struct PageEvents {
+    pub(crate) on_focus: Option<Arc<dyn Fn() + Send + Sync>>,
+    pub(crate) on_blur: Option<Arc<dyn Fn() + Send + Sync>>,
+    pub(crate) on_before_leave: Option<Arc<dyn Fn() -> bool + Send + Sync>>,
+}
+
+/// a page pushed with params
+#[derive(Debug)]
+struct PageInstance {
+    /// name of the page registered with `with_page`
+    page: String,
+    params: Arc<dyn Any + Send + Sync>,
+    /// set once the instance is in the native history,
+    /// the instance is dropped when it leaves the history
+    pushed: bool,
+}
+
+#[derive(Debug, Default)]
+struct PageInstances {
+    /// instances by the name of their native page
+    instances: HashMap<String, PageInstance>,
+    next_instance: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct StackNavigator {
     /// node id of the corresponding navigator widget
     id: NodeID,
     command_sender: Sender<NavigatorCommand>,
     command_recv: Receiver<NavigatorCommand>,
+    /// names of the native pages in history, updated by the native navigator
+    history: Arc<RwLock<Vec<String>>>,
+    instances: Arc<Mutex<PageInstances>>,
+    links: Arc<NavigatorLinks>,
+    /// params of the page instance the handle is given to
+    params: Option<Arc<dyn Any + Send + Sync>>,
 }
 
 impl StackNavigator {
@@ -27,20 +79,26 @@ impl StackNavigator {
             id: NodeID::new_unique(),
             command_sender: rx,
             command_recv: tx,
+            history: Default::default(),
+            instances: Default::default(),
+            links: Default::default(),
+            params: None,
         }
     }
 
     /// should be called during `render` to construct a widget
     pub fn navigator(&self) -> StackNavigatorElement {
         return StackNavigatorElement {
-            id: self.id,
+            handle: self.clone(),
             style: StyleRef::DEFAULT,
-            command_sender: self.command_sender.clone(),
-            command_recv: self.command_recv.clone(),
             children: Vec::new(),
+            transition: StackNavigatorTransition::default(),
+            transition_duration: Duration::from_millis(200),
+            page_events: HashMap::new(),
 
             rendered_children: Vec::new(),
             child_names: Vec::new(),
+            child_events: Vec::new(),
         };
     }
 
@@ -50,17 +108,157 @@ impl StackNavigator {
             .send(NavigatorCommand::Push(name.to_string()));
     }
 
-    /// go to the page in index, noop if index is not valid
-    pub fn goto(&self, name: &str) {
+    /// push a new instance of the page, the page receives the params from `params`.
+    ///
+    /// each push creates an instance, the same page may be on the stack
+    /// several times with different params.
+    pub fn push_with<P: Send + Sync + 'static>(&self, name: &str, params: P) {
+        let key = {
+            let mut instances = self.instances.lock();
+            instances.next_instance += 1;
+
+            let key = format!("{}#{}", name, instances.next_instance);
+
+            instances.instances.insert(
+                key.clone(),
+                PageInstance {
+                    page: name.to_string(),
+                    params: Arc::new(params),
+                    pushed: false,
+                },
+            );
+
+            key
+        };
+
+        // the instance is rendered before it is pushed
+        mark_dirty();
+
+        let _ = self.command_sender.send(NavigatorCommand::Push(key));
+    }
+
+    /// the params the page was pushed with, none if the page was pushed without params
+    /// or the params are not of type `P`
+    pub fn params<P: Clone + 'static>(&self) -> Option<P> {
+        self.params.as_ref()?.downcast_ref::<P>().cloned()
+    }
+
+    /// push the page with a transition other than the one of the navigator,
+    /// going back from the page reverses the same transition
+    pub fn push_with_transition(&self, name: &str, transition: StackNavigatorTransition) {
         let _ = self
             .command_sender
-            .send(NavigatorCommand::Goto(name.to_string()));
+            .send(NavigatorCommand::PushWithTransition(
+                name.to_string(),
+                transition,
+            ));
+    }
+
+    /// go back to the last instance of the page in history, push the page if not in history
+    pub fn goto(&self, name: &str) {
+        let key = {
+            let instances = self.instances.lock();
+
+            self.history
+                .read()
+                .iter()
+                .rev()
+                .find(|key| match instances.instances.get(*key) {
+                    Some(instance) => instance.page == name,
+                    None => *key == name,
+                })
+                .cloned()
+                .unwrap_or(name.to_string())
+        };
+
+        let _ = self.command_sender.send(NavigatorCommand::Goto(key));
     }
 
-    /// goback to the last page, noop if cannot go back
+    /// goback to the last page.
+    ///
+    /// a navigator nested in the page of another navigator goes back
+    /// in the parent when at its first page, noop otherwise.
     pub fn goback(&self) {
+        if !self.can_goback() {
+            if let Some(scope) = self.links.parent() {
+                scope.parent.goback();
+                return;
+            }
+        }
+
         let _ = self.command_sender.send(NavigatorCommand::Goback);
     }
+
+    /// go to the page at the path, `settings/profile` goes to the page `settings`
+    /// and to the page `profile` of the navigator nested in `settings`.
+    ///
+    /// the nested navigator navigates once it is rendered.
+    pub fn navigate(&self, path: &str) {
+        self.route(path, None);
+    }
+
+    /// names of the pages in history, the visible page is last.
+    ///
+    /// the history is updated once the navigator handles the navigation.
+    pub fn history(&self) -> Vec<String> {
+        let instances = self.instances.lock();
+
+        self.history
+            .read()
+            .iter()
+            .map(|key| match instances.instances.get(key) {
+                Some(instance) => instance.page.clone(),
+                None => key.clone(),
+            })
+            .collect()
+    }
+
+    /// name of the visible page
+    pub fn current(&self) -> Option<String> {
+        self.history().pop()
+    }
+
+    /// returns true if there is a page to go back to
+    pub fn can_goback(&self) -> bool {
+        self.history.read().len() > 1
+    }
+}
+
+impl ParentNavigator for StackNavigator {
+    fn goback(&self) {
+        StackNavigator::goback(self)
+    }
+    fn route(&self, path: &str, params: Option<RouteParams>) {
+        let (page, rest) = split_path(path);
+
+        if let Some(rest) = rest {
+            self.links.set_pending(page, rest, params);
+            mark_dirty();
+            self.goto(page);
+            return;
+        }
+
+        // a page routed with params is pushed as a new instance
+        match params {
+            Some(params) => self.push_with(page, params),
+            None => self.goto(page),
+        }
+    }
+    fn location(&self) -> Option<(String, Option<RouteParams>)> {
+        let key = self.history.read().last()?.clone();
+        let instances = self.instances.lock();
+
+        match instances.instances.get(&key) {
+            Some(instance) => Some((
+                instance.page.clone(),
+                instance.params.downcast_ref::<RouteParams>().cloned(),
+            )),
+            None => Some((key, None)),
+        }
+    }
+    fn links(&self) -> &NavigatorLinks {
+        &self.links
+    }
 }
 
 impl Default for StackNavigator {
@@ -70,14 +268,17 @@ impl Default for StackNavigator {
 }
 
 pub struct StackNavigatorElement {
-    id: NodeID,
+    handle: StackNavigator,
     style: StyleRef,
-    command_sender: Sender<NavigatorCommand>,
-    command_recv: Receiver<NavigatorCommand>,
-    children: Vec<(String, Box<dyn ElementLike>)>,
+    /// the native page name, the page name and the page
+    children: Vec<(String, String, Box<dyn ElementLike>)>,
+    transition: StackNavigatorTransition,
+    transition_duration: Duration,
+    page_events: HashMap<String, PageEvents>,
 
     rendered_children: Vec<CoreComponent>,
     child_names: Vec<String>,
+    child_events: Vec<PageEvents>,
 }
 
 impl StackNavigatorElement {
@@ -86,28 +287,68 @@ impl StackNavigatorElement {
         F: Fn(StackNavigator) -> T,
         T: ElementLike,
     {
-        let component = component(StackNavigator {
-            id: self.id,
-            command_sender: self.command_sender.clone(),
-            command_recv: self.command_recv.clone(),
-        });
-
-        self.children.push((name.to_string(), Box::new(component)));
+        self.add_page(name, component);
         return self;
     }
 
+    /// the page is constructed once, and once more for each instance pushed with params
     pub fn add_page<F, T>(&mut self, name: &str, component: F)
     where
         F: Fn(StackNavigator) -> T,
         T: ElementLike,
     {
-        let component = component(StackNavigator {
-            id: self.id,
-            command_sender: self.command_sender.clone(),
-            command_recv: self.command_recv.clone(),
+        let instances: Vec<(String, Arc<dyn Any + Send + Sync>)> = {
+            let history = self.handle.history.read();
+            let mut instances = self.handle.instances.lock();
+
+            // drop the instances that left the history
+            instances.instances.retain(|key, instance| {
+                if history.contains(key) {
+                    instance.pushed = true;
+                }
+                return !instance.pushed || history.contains(key);
+            });
+
+            instances
+                .instances
+                .iter()
+                .filter(|(_, instance)| instance.page == name)
+                .map(|(key, instance)| (key.clone(), instance.params.clone()))
+                .collect()
+        };
+
+        let mut handle = self.handle.clone();
+        handle.params = None;
+
+        // navigators nested in the page find the navigator with the scope
+        let scope = Arc::new(NavigatorScope {
+            parent: Box::new(handle.clone()),
+            page: name.to_string(),
         });
 
-        self.children.push((name.to_string(), Box::new(component)));
+        let page = component(handle.clone());
+
+        // pages are matched by name across renders
+        self.children.push((
+            name.to_string(),
+            name.to_string(),
+            Box::new(Provider::from_arc(scope.clone(), Keyed::new(name, page))),
+        ));
+
+        for (key, params) in instances {
+            handle.params = Some(params);
+
+            let page = component(handle.clone());
+
+            self.children.push((
+                key.clone(),
+                name.to_string(),
+                Box::new(Provider::from_arc(
+                    scope.clone(),
+                    Keyed::new(key.as_str(), page),
+                )),
+            ));
+        }
     }
 
     pub fn with_style<S: Into<StyleRef>>(mut self, style: S) -> Self {
@@ -118,37 +359,117 @@ impl StackNavigatorElement {
     pub fn set_style<S: Into<StyleRef>>(&mut self, style: S) {
         self.style = style.into();
     }
+
+    /// the transition used when navigating between pages, defaults to slide
+    pub fn with_transition(mut self, transition: StackNavigatorTransition) -> Self {
+        self.set_transition(transition);
+        return self;
+    }
+
+    pub fn set_transition(&mut self, transition: StackNavigatorTransition) {
+        self.transition = transition;
+    }
+
+    /// duration of the transitions, defaults to 200ms
+    pub fn with_transition_duration(mut self, duration: Duration) -> Self {
+        self.set_transition_duration(duration);
+        return self;
+    }
+
+    pub fn set_transition_duration(&mut self, duration: Duration) {
+        self.transition_duration = duration;
+    }
+
+    /// called when the page becomes the visible page
+    pub fn with_on_focus<F>(mut self, page: &str, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.set_on_focus(page, callback);
+        return self;
+    }
+
+    pub fn set_on_focus<F>(&mut self, page: &str, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.page_events
+            .entry(page.to_string())
+            .or_default()
+            .on_focus = Some(Arc::new(callback));
+    }
+
+    /// called when the page is no longer the visible page
+    pub fn with_on_blur<F>(mut self, page: &str, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.set_on_blur(page, callback);
+        return self;
+    }
+
+    pub fn set_on_blur<F>(&mut self, page: &str, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.page_events
+            .entry(page.to_string())
+            .or_default()
+            .on_blur = Some(Arc::new(callback));
+    }
+
+    /// called before going back from the page, the page is not left if the callback returns false
+    pub fn with_on_before_leave<F>(mut self, page: &str, callback: F) -> Self
+    where
+        F: Fn() -> bool + Send + Sync + 'static,
+    {
+        self.set_on_before_leave(page, callback);
+        return self;
+    }
+
+    pub fn set_on_before_leave<F>(&mut self, page: &str, callback: F)
+    where
+        F: Fn() -> bool + Send + Sync + 'static,
+    {
+        self.page_events
+            .entry(page.to_string())
+            .or_default()
+            .on_before_leave = Some(Arc::new(callback));
+    }
 }
 
 impl NativeElement for StackNavigatorElement {
     fn core_component(&mut self) -> CoreComponent {
         CoreComponent::StackNavigator(Box::new(StackNavigaterNode {
-            id: self.id,
+            id: self.handle.id,
             style: self.style.clone(),
-            command_reciever: self.command_recv.clone(),
-            children: core::mem::replace(&mut self.rendered_children, Vec::new()),
-            child_names: core::mem::replace(&mut self.child_names, Vec::new()),
+            command_reciever: self.handle.command_recv.clone(),
+            history: self.handle.history.clone(),
+            transition: self.transition,
+            transition_duration: self.transition_duration,
+            children: std::mem::take(&mut self.rendered_children),
+            child_names: std::mem::take(&mut self.child_names),
+            child_events: std::mem::take(&mut self.child_events),
+            meta: Default::default(),
         }))
     }
     fn render(&mut self) {
+        // the parent may have left a path to navigate to
+        if let Some((path, params)) = link_to_parent(&self.handle) {
+            self.handle.route(&path, params);
+        }
+
         // render all the children
-        for (name, child) in &mut self.children {
-            // render the child
-            let mut elem = child.render();
-
-            // keep rendering until a core component is reached
-            let component = loop {
-                match elem {
-                    Err(mut e) => {
-                        elem = e.render();
-                    }
-                    Ok(c) => break c,
-                }
-            };
+        for (i, (name, page, child)) in self.children.iter_mut().enumerate() {
+            // render the child until a core component is reached
+            let component = render_element(child, i);
 
             // push child
             self.rendered_children.push(component);
             self.child_names.push(name.clone());
+            // instances share the events of the page
+            self.child_events
+                .push(self.page_events.get(page).cloned().unwrap_or_default());
         }
     }
 }
@@ -161,3 +482,282 @@ impl ElementLike for StackNavigatorElement {
         None
     }
 }
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_stack_transition() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Text};
+
+    let navigator = StackNavigator::new();
+    let nav = navigator.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        nav.navigator()
+            .with_transition(StackNavigatorTransition::Fade)
+            .with_transition_duration(Duration::from_millis(300))
+            .with_page("home", |_| Text::new("home page"))
+            .with_page("details", |_| Text::new("details page"))
+            .test_id("stack")
+    });
+
+    // the first page is shown without transition
+    let stack = renderer.find_by_test_id("stack").unwrap();
+    assert_eq!(stack.last_transition(), None);
+
+    navigator.push("details");
+    renderer.step();
+    let stack = renderer.find_by_test_id("stack").unwrap();
+    assert_eq!(
+        stack.last_transition(),
+        Some((StackNavigatorTransition::Fade, false))
+    );
+
+    navigator.goback();
+    renderer.step();
+    let stack = renderer.find_by_test_id("stack").unwrap();
+    assert_eq!(
+        stack.last_transition(),
+        Some((StackNavigatorTransition::Fade, true))
+    );
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_push_with_transition() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Text};
+
+    let navigator = StackNavigator::new();
+    let nav = navigator.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        nav.navigator()
+            .with_transition(StackNavigatorTransition::Fade)
+            .with_page("home", |_| Text::new("home page"))
+            .with_page("details", |_| Text::new("details page"))
+            .with_page("about", |_| Text::new("about page"))
+            .test_id("stack")
+    });
+
+    navigator.push("details");
+    renderer.step();
+    navigator.push_with_transition("about", StackNavigatorTransition::Slide);
+    renderer.step();
+    let stack = renderer.find_by_test_id("stack").unwrap();
+    assert_eq!(
+        stack.last_transition(),
+        Some((StackNavigatorTransition::Slide, false))
+    );
+
+    // the page pushed with its own transition leaves with it
+    navigator.goback();
+    renderer.step();
+    let stack = renderer.find_by_test_id("stack").unwrap();
+    assert_eq!(
+        stack.last_transition(),
+        Some((StackNavigatorTransition::Slide, true))
+    );
+    assert!(renderer.find_by_text("details page").is_some());
+
+    // going back to an earlier page uses the transition of the navigator
+    navigator.goto("home");
+    renderer.step();
+    let stack = renderer.find_by_test_id("stack").unwrap();
+    assert_eq!(
+        stack.last_transition(),
+        Some((StackNavigatorTransition::Fade, true))
+    );
+    assert!(renderer.find_by_text("home page").is_some());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_stack_params() {
+    use crate::testing::TestRenderer;
+    use crate::widget::Text;
+
+    let navigator = StackNavigator::new();
+    let nav = navigator.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        nav.navigator()
+            .with_page("home", |_| Text::new("home page"))
+            .with_page("details", |nav| match nav.params::<u32>() {
+                Some(id) => Text::new(format!("item {}", id)),
+                None => Text::new("no item"),
+            })
+    });
+
+    navigator.push_with("details", 1u32);
+    renderer.step();
+    assert!(renderer.find_by_text("item 1").is_some());
+
+    // the same page is pushed again with other params
+    navigator.push_with("details", 2u32);
+    renderer.step();
+    assert!(renderer.find_by_text("item 2").is_some());
+    assert!(renderer.find_by_text("item 1").is_none());
+
+    navigator.goback();
+    renderer.step();
+    assert!(renderer.find_by_text("item 1").is_some());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_stack_without_params() {
+    use crate::testing::TestRenderer;
+    use crate::widget::Text;
+
+    let navigator = StackNavigator::new();
+    let nav = navigator.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        nav.navigator()
+            .with_page("home", |_| Text::new("home page"))
+            .with_page("details", |nav| match nav.params::<u32>() {
+                Some(id) => Text::new(format!("item {}", id)),
+                None => Text::new("no item"),
+            })
+    });
+
+    navigator.push_with("details", 1u32);
+    renderer.step();
+
+    // the page pushed without params is a separate instance
+    navigator.push("details");
+    renderer.step();
+    assert!(renderer.find_by_text("no item").is_some());
+    assert!(renderer.find_by_text("item 1").is_none());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_stack_unmount_popped() {
+    use crate::testing::TestRenderer;
+    use crate::widget::Text;
+
+    let navigator = StackNavigator::new();
+    let nav = navigator.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        nav.navigator()
+            .with_page("home", |_| Text::new("home page"))
+            .with_page("details", |nav| match nav.params::<u32>() {
+                Some(id) => Text::new(format!("item {}", id)),
+                None => Text::new("no item"),
+            })
+    });
+
+    navigator.push_with("details", 1u32);
+    renderer.step();
+    navigator.push_with("details", 2u32);
+    renderer.step();
+
+    navigator.goto("home");
+    renderer.step();
+    assert!(renderer.find_by_text("home page").is_some());
+
+    // the popped instances are unmounted
+    renderer.step();
+    let root = renderer.root().unwrap();
+    assert_eq!(root.component.child().len(), 2);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_stack_focus_events() {
+    use crate::testing::TestRenderer;
+    use crate::widget::Text;
+    use crate::State;
+
+    let events = State::new(Vec::<&'static str>::new());
+    let navigator = StackNavigator::new();
+    let (e, nav) = (events.clone(), navigator.clone());
+
+    let mut renderer = TestRenderer::new(move || {
+        let (e1, e2, e3) = (e.clone(), e.clone(), e.clone());
+
+        nav.navigator()
+            .with_page("home", |_| Text::new("home page"))
+            .with_page("edit", |_| Text::new("edit page"))
+            .with_on_focus("home", move || e1.update(|e| e.push("home focus")))
+            .with_on_blur("home", move || e2.update(|e| e.push("home blur")))
+            .with_on_focus("edit", move || e3.update(|e| e.push("edit focus")))
+    });
+
+    renderer.step();
+    assert_eq!(events.get(), ["home focus"]);
+
+    navigator.push("edit");
+    renderer.step();
+    assert_eq!(events.get(), ["home focus", "home blur", "edit focus"]);
+
+    navigator.goback();
+    renderer.step();
+    assert_eq!(events.get().last(), Some(&"home focus"));
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_stack_before_leave() {
+    use crate::testing::TestRenderer;
+    use crate::widget::Text;
+    use crate::State;
+
+    let saved = State::new(false);
+    let navigator = StackNavigator::new();
+    let (s, nav) = (saved.clone(), navigator.clone());
+
+    let mut renderer = TestRenderer::new(move || {
+        let s = s.clone();
+
+        nav.navigator()
+            .with_page("home", |_| Text::new("home page"))
+            .with_page("edit", |_| Text::new("edit page"))
+            .with_on_before_leave("edit", move || s.get())
+    });
+
+    navigator.push("edit");
+    renderer.step();
+
+    // the page can not be left with unsaved edits
+    navigator.goback();
+    renderer.step();
+    assert!(renderer.find_by_text("edit page").is_some());
+    assert_eq!(navigator.history(), ["home", "edit"]);
+
+    saved.set(true);
+    navigator.goback();
+    renderer.step();
+    assert!(renderer.find_by_text("home page").is_some());
+    assert_eq!(navigator.history(), ["home"]);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_stack_history() {
+    use crate::testing::TestRenderer;
+    use crate::widget::Text;
+
+    let navigator = StackNavigator::new();
+    let nav = navigator.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        nav.navigator()
+            .with_page("home", |_| Text::new("home page"))
+            .with_page("edit", |_| Text::new("edit page"))
+    });
+
+    renderer.step();
+    assert_eq!(navigator.history(), ["home"]);
+    assert_eq!(navigator.current().as_deref(), Some("home"));
+    assert!(!navigator.can_goback());
+
+    navigator.push("edit");
+    renderer.step();
+    assert_eq!(navigator.history(), ["home", "edit"]);
+    assert_eq!(navigator.current().as_deref(), Some("edit"));
+    assert!(navigator.can_goback());
+}
@@ -0,0 +1,93 @@
+use crate::shadow_tree::component::{ToggleKind, ToggleNode};
+
+/// A switch that can be turned on or off, with an optional label.
+///
+/// The switch is controlled: it is on if and only if the `checked` prop is true,
+/// `on_toggle` should update the state the prop is rendered from.
+pub struct Switch {
+    tree_node: ToggleNode,
+}
+
+impl_toggle!(Switch, ToggleKind::Switch);
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_switch_kind() {
+    use crate::testing::{NodeType, TestRenderer};
+    use crate::widget::{Checkbox, ElementExt, View};
+    use crate::State;
+
+    let switch = State::new(false);
+    let s = switch.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        let mut view = View::new();
+
+        match s.get() {
+            true => view.add_child(Switch::new().with_checked(true).test_id("toggle")),
+            false => view.add_child(Checkbox::new().with_checked(true).test_id("toggle")),
+        }
+
+        view
+    });
+
+    let toggle = renderer.find_by_test_id("toggle").unwrap();
+    assert_eq!(toggle.node_type(), NodeType::Checkbox);
+    assert_eq!(toggle.widget().record().kind, "checkbox");
+
+    // the checkbox is replaced by a switch
+    switch.set(true);
+    renderer.step();
+
+    let toggle = renderer.find_by_test_id("toggle").unwrap();
+    assert_eq!(toggle.node_type(), NodeType::Switch);
+    assert_eq!(toggle.widget().record().kind, "switch");
+    assert!(toggle.is_checked());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_switch_rejected_toggle() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+
+    let mut renderer = TestRenderer::new(|| {
+        // the toggle is rejected
+        Switch::new()
+            .with_checked(false)
+            .with_on_toggle(|_| {})
+            .test_id("locked")
+    });
+
+    renderer.find_by_test_id("locked").unwrap().toggle();
+    assert!(renderer.find_by_test_id("locked").unwrap().is_checked());
+
+    // the switch is reset to the checked prop on the next frame
+    assert!(renderer.step());
+    assert!(!renderer.find_by_test_id("locked").unwrap().is_checked());
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_switch_disabled() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+    use crate::State;
+
+    let toggled = State::new(false);
+    let t = toggled.clone();
+
+    let renderer = TestRenderer::new(move || {
+        let t = t.clone();
+
+        Switch::new()
+            .with_disabled(true)
+            .with_on_toggle(move |_| t.set(true))
+            .test_id("disabled")
+    });
+
+    let disabled = renderer.find_by_test_id("disabled").unwrap();
+    disabled.toggle();
+    assert!(!disabled.is_checked());
+    assert!(!toggled.get());
+}
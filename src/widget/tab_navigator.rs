@@ -0,0 +1,457 @@
+use std::sync::Arc;
+
+use crossbeam_channel::{Receiver, Sender};
+use parking_lot::Mutex;
+
+use crate::private::{ElementLike, NativeElement};
+use crate::router::RouteParams;
+use crate::shadow_tree::component::{CoreComponent, NavigatorCommand, TabNavigatorNode};
+use crate::shadow_tree::render::render_element;
+use crate::shadow_tree::NodeID;
+use crate::state::mark_dirty;
+use crate::style::StyleRef;
+use crate::widget::keyed::Keyed;
+use crate::widget::navigation::{
+    link_to_parent, split_path, NavigatorLinks, NavigatorScope, ParentNavigator,
+};
+use crate::widget::provider::Provider;
+
+/// A tab shown in the tab bar of a `TabNavigator`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tab {
+    pub(crate) name: String,
+    pub(crate) label: String,
+    pub(crate) icon: Option<String>,
+}
+
+impl Tab {
+    /// a tab labelled with its name
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            label: name.to_string(),
+            icon: None,
+        }
+    }
+
+    pub fn with_label<S: Into<String>>(mut self, label: S) -> Self {
+        self.label = label.into();
+        return self;
+    }
+
+    /// name of an icon from the icon theme, shown above the label
+    pub fn with_icon<S: Into<String>>(mut self, icon: S) -> Self {
+        self.icon = Some(icon.into());
+        return self;
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TabBarPosition {
+    Top,
+    #[default]
+    Bottom,
+}
+
+/// tabs selected in the navigator, updated when the selected tab changes
+#[derive(Debug, Default)]
+struct TabSelection {
+    /// none until a tab is selected, the first tab is shown
+    selected: Option<String>,
+    /// tabs that have been shown, their pages stay rendered
+    visited: Vec<String>,
+    /// the first tab, shown until a tab is selected
+    first: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TabNavigator {
+    /// node id of the corresponding navigator widget
+    id: NodeID,
+    command_sender: Sender<NavigatorCommand>,
+    command_recv: Receiver<NavigatorCommand>,
+    selection: Arc<Mutex<TabSelection>>,
+    links: Arc<NavigatorLinks>,
+}
+
+impl TabNavigator {
+    pub fn new() -> Self {
+        let (tx, rx) = crossbeam_channel::unbounded();
+
+        Self {
+            id: NodeID::new_unique(),
+            command_sender: tx,
+            command_recv: rx,
+            selection: Default::default(),
+            links: Default::default(),
+        }
+    }
+
+    /// should be called during `render` to construct a widget
+    pub fn navigator(&self) -> TabNavigatorElement {
+        return TabNavigatorElement {
+            handle: self.clone(),
+            style: StyleRef::DEFAULT,
+            tabs: Vec::new(),
+            pages: Vec::new(),
+            tab_bar_position: TabBarPosition::default(),
+            lazy: false,
+            on_tab_change: None,
+
+            rendered_children: Vec::new(),
+            child_names: Vec::new(),
+        };
+    }
+
+    /// select the tab, noop if there is no tab with the name
+    pub fn goto(&self, name: &str) {
+        let _ = self
+            .command_sender
+            .send(NavigatorCommand::Goto(name.to_string()));
+    }
+
+    /// select the previously selected tab, noop if no tab was selected before
+    pub fn goback(&self) {
+        let _ = self.command_sender.send(NavigatorCommand::Goback);
+    }
+
+    /// select the tab at the path, `feed/post` selects the tab `feed`
+    /// and goes to the page `post` of the navigator nested in `feed`.
+    ///
+    /// the nested navigator navigates once it is rendered.
+    pub fn navigate(&self, path: &str) {
+        self.route(path, None);
+    }
+
+    /// the selected tab, none if the first tab is shown and no tab has been selected
+    pub fn selected(&self) -> Option<String> {
+        self.selection.lock().selected.clone()
+    }
+}
+
+impl ParentNavigator for TabNavigator {
+    fn goback(&self) {
+        TabNavigator::goback(self)
+    }
+    fn route(&self, path: &str, params: Option<RouteParams>) {
+        let (tab, rest) = split_path(path);
+
+        // tabs are not instantiated, only nested navigators receive params
+        if let Some(rest) = rest {
+            self.links.set_pending(tab, rest, params);
+            mark_dirty();
+        }
+
+        self.goto(tab);
+    }
+    fn location(&self) -> Option<(String, Option<RouteParams>)> {
+        let selection = self.selection.lock();
+        let tab = selection.selected.clone().or(selection.first.clone())?;
+
+        return Some((tab, None));
+    }
+    fn links(&self) -> &NavigatorLinks {
+        &self.links
+    }
+}
+
+impl Default for TabNavigator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct TabNavigatorElement {
+    handle: TabNavigator,
+    style: StyleRef,
+    tabs: Vec<Tab>,
+    pages: Vec<Box<dyn ElementLike>>,
+    tab_bar_position: TabBarPosition,
+    lazy: bool,
+    on_tab_change: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+
+    rendered_children: Vec<CoreComponent>,
+    child_names: Vec<String>,
+}
+
+impl TabNavigatorElement {
+    pub fn with_tab<F, T>(mut self, tab: Tab, component: F) -> Self
+    where
+        F: Fn(TabNavigator) -> T,
+        T: ElementLike,
+    {
+        self.add_tab(tab, component);
+        return self;
+    }
+
+    pub fn add_tab<F, T>(&mut self, tab: Tab, component: F)
+    where
+        F: Fn(TabNavigator) -> T,
+        T: ElementLike,
+    {
+        let component = component(self.handle.clone());
+
+        // navigators nested in the page find the navigator with the scope
+        let scope = NavigatorScope {
+            parent: Box::new(self.handle.clone()),
+            page: tab.name.clone(),
+        };
+
+        // pages are matched by name across renders
+        self.pages.push(Box::new(Provider::new(
+            scope,
+            Keyed::new(tab.name.as_str(), component),
+        )));
+        self.tabs.push(tab);
+    }
+
+    pub fn with_style<S: Into<StyleRef>>(mut self, style: S) -> Self {
+        self.set_style(style);
+        return self;
+    }
+
+    pub fn set_style<S: Into<StyleRef>>(&mut self, style: S) {
+        self.style = style.into();
+    }
+
+    pub fn with_tab_bar_position(mut self, position: TabBarPosition) -> Self {
+        self.set_tab_bar_position(position);
+        return self;
+    }
+
+    pub fn set_tab_bar_position(&mut self, position: TabBarPosition) {
+        self.tab_bar_position = position;
+    }
+
+    /// only render the pages of tabs that have been selected
+    pub fn with_lazy(mut self, lazy: bool) -> Self {
+        self.set_lazy(lazy);
+        return self;
+    }
+
+    pub fn set_lazy(&mut self, lazy: bool) {
+        self.lazy = lazy;
+    }
+
+    /// called with the name of the tab selected by the user or by `TabNavigator::goto`
+    pub fn with_on_tab_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.set_on_tab_change(callback);
+        return self;
+    }
+
+    pub fn set_on_tab_change<F>(&mut self, callback: F)
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_tab_change = Some(Arc::new(callback));
+    }
+}
+
+impl NativeElement for TabNavigatorElement {
+    fn core_component(&mut self) -> CoreComponent {
+        // the selection is tracked to render the pages of visited tabs
+        let selection = self.handle.selection.clone();
+        let callback = self.on_tab_change.clone();
+
+        let on_tab_change: Arc<dyn Fn(&str) + Send + Sync> = Arc::new(move |name: &str| {
+            selection.lock().selected = Some(name.to_string());
+
+            // the page of a lazy tab is rendered once selected
+            mark_dirty();
+
+            if let Some(cb) = &callback {
+                cb(name);
+            }
+        });
+
+        CoreComponent::TabNavigator(Box::new(TabNavigatorNode {
+            id: self.handle.id,
+            style: self.style.clone(),
+            command_reciever: self.handle.command_recv.clone(),
+            tabs: self.tabs.clone(),
+            tab_bar_position: self.tab_bar_position,
+            on_tab_change: Some(on_tab_change),
+            children: std::mem::take(&mut self.rendered_children),
+            child_names: std::mem::take(&mut self.child_names),
+            meta: Default::default(),
+        }))
+    }
+    fn render(&mut self) {
+        // the parent may have left a path to navigate to
+        if let Some((path, params)) = link_to_parent(&self.handle) {
+            self.handle.route(&path, params);
+        }
+
+        let mut selection = self.handle.selection.lock();
+        selection.first = self.tabs.first().map(|t| t.name.clone());
+
+        for (i, (tab, page)) in self.tabs.iter().zip(self.pages.iter_mut()).enumerate() {
+            let selected = match &selection.selected {
+                Some(name) => name == &tab.name,
+                None => i == 0,
+            };
+
+            if self.lazy && !selected && !selection.visited.contains(&tab.name) {
+                continue;
+            }
+
+            // the page of the first tab is shown before any tab is selected
+            if selected && !selection.visited.contains(&tab.name) {
+                selection.visited.push(tab.name.clone());
+            }
+
+            // render the child until a core component is reached
+            let component = render_element(page, i);
+
+            self.rendered_children.push(component);
+            self.child_names.push(tab.name.clone());
+        }
+    }
+}
+
+impl ElementLike for TabNavigatorElement {
+    fn as_native(&mut self) -> Option<&mut dyn NativeElement> {
+        Some(self)
+    }
+    fn as_element(&mut self) -> Option<&mut dyn crate::Element> {
+        None
+    }
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_tab_bar() {
+    use crate::headless::DEFAULT_FONT_SIZE;
+    use crate::style::{Dimension, Style};
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Text};
+    use crate::State;
+
+    static STYLE: Style = Style {
+        width: Dimension::Points(200.0),
+        height: Dimension::Points(100.0),
+        ..Style::DEFAULT
+    };
+
+    let position = State::new(TabBarPosition::Bottom);
+    let navigator = TabNavigator::new();
+    let (p, nav) = (position.clone(), navigator.clone());
+
+    let mut renderer = TestRenderer::new(move || {
+        nav.navigator()
+            .with_style(&STYLE)
+            .with_tab_bar_position(p.get())
+            .with_tab(Tab::new("home").with_label("Home"), |_| {
+                Text::new("home page").test_id("home")
+            })
+            .with_tab(Tab::new("search").with_icon("system-search"), |_| {
+                Text::new("search page")
+            })
+            .test_id("tabs")
+    });
+
+    // a tab without label shows its name
+    let tabs = renderer.find_by_test_id("tabs").unwrap();
+    assert_eq!(tabs.tabs(), ["Home", "search"]);
+    assert_eq!(tabs.selected_tab().as_deref(), Some("home"));
+
+    // the page is laid out above the tab bar
+    let home = renderer.find_by_test_id("home").unwrap();
+    assert_eq!(home.layout().y, 0.0);
+    assert_eq!(home.layout().height, 100.0 - 2.0 * DEFAULT_FONT_SIZE);
+
+    position.set(TabBarPosition::Top);
+    renderer.step();
+    let home = renderer.find_by_test_id("home").unwrap();
+    assert_eq!(home.layout().y, 2.0 * DEFAULT_FONT_SIZE);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_tab_change() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Text};
+    use crate::State;
+
+    let changes = State::new(Vec::<String>::new());
+    let navigator = TabNavigator::new();
+    let (c, nav) = (changes.clone(), navigator.clone());
+
+    let mut renderer = TestRenderer::new(move || {
+        let c = c.clone();
+
+        nav.navigator()
+            .with_on_tab_change(move |name| c.update(|c| c.push(name.to_string())))
+            .with_tab(Tab::new("home"), |_| Text::new("home page"))
+            .with_tab(Tab::new("search"), |_| Text::new("search page"))
+            .test_id("tabs")
+    });
+
+    // selected by the user
+    renderer
+        .find_by_test_id("tabs")
+        .unwrap()
+        .select_tab("search");
+    renderer.step();
+    assert_eq!(changes.get(), ["search"]);
+    assert_eq!(navigator.selected().as_deref(), Some("search"));
+    assert!(renderer.find_by_text("search page").is_some());
+
+    // selected by the navigator
+    navigator.goback();
+    renderer.step();
+    assert_eq!(changes.get(), ["search", "home"]);
+    assert!(renderer.find_by_text("home page").is_some());
+
+    // going to the selected tab does not change it
+    navigator.goto("home");
+    renderer.step();
+    assert_eq!(changes.get().len(), 2);
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_tab_lazy() {
+    use crate::testing::TestRenderer;
+    use crate::widget::{ElementExt, Text};
+
+    let navigator = TabNavigator::new();
+    let nav = navigator.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        nav.navigator()
+            .with_lazy(true)
+            .with_tab(Tab::new("home"), |_| Text::new("home page"))
+            .with_tab(Tab::new("search"), |_| Text::new("search page"))
+            .test_id("tabs")
+    });
+
+    // the lazy tab is not rendered until it is selected
+    let tabs = renderer.find_by_test_id("tabs").unwrap();
+    assert_eq!(tabs.component.child().len(), 1);
+
+    navigator.goto("search");
+    renderer.step();
+    navigator.goto("home");
+    renderer.step();
+
+    // the visited page stays rendered
+    let tabs = renderer.find_by_test_id("tabs").unwrap();
+    assert_eq!(tabs.component.child().len(), 2);
+    assert!(renderer.find_by_text("search page").is_none());
+}
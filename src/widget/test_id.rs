@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use crate::private::{ElementLike, NativeElement};
+use crate::shadow_tree::component::CoreComponent;
+use crate::shadow_tree::render::set_test_id;
+
+/// Assigns an identifier to its child, used to find the element in tests.
+///
+/// The identifier has no effect on rendering.
+pub struct TestId {
+    id: Arc<str>,
+    child: Option<Box<dyn ElementLike>>,
+}
+
+impl TestId {
+    pub fn new(id: &str, child: impl ElementLike) -> Self {
+        Self {
+            id: Arc::from(id),
+            child: Some(Box::new(child)),
+        }
+    }
+}
+
+impl ElementLike for TestId {
+    fn as_native(&mut self) -> Option<&mut dyn NativeElement> {
+        None
+    }
+    fn as_element(&mut self) -> Option<&mut dyn crate::Element> {
+        None
+    }
+    fn render(&mut self) -> Result<CoreComponent, Box<dyn ElementLike>> {
+        set_test_id(self.id.clone());
+
+        Err(self.child.take().expect("test id rendered twice"))
+    }
+}
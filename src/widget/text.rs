@@ -1,11 +1,13 @@
+use crate::event::PointerHandlers;
 use crate::private::{ElementLike, NativeElement};
-use crate::shadow_tree::component::{CoreComponent, TextNode};
+use crate::shadow_tree::component::{CoreComponent, NodeMeta, TextNode};
 use crate::style::StyleRef;
 
 pub struct Text {
     /// shadow tree
     text: String,
     style: StyleRef,
+    pointer: PointerHandlers,
 }
 
 impl Text {
@@ -13,6 +15,7 @@ impl Text {
         Self {
             text: text.into(),
             style: StyleRef::DEFAULT,
+            pointer: PointerHandlers::default(),
         }
     }
 
@@ -26,12 +29,18 @@ impl Text {
     }
 }
 
+impl_pointer_events!(Text);
+
 impl NativeElement for Text {
     fn core_component(&mut self) -> crate::shadow_tree::component::CoreComponent {
         CoreComponent::Text(Box::new(TextNode {
             id: None,
             style: self.style.clone(),
             text: self.text.clone(),
+            meta: NodeMeta {
+                pointer: self.pointer.clone(),
+                ..Default::default()
+            },
         }))
     }
 
@@ -1,15 +1,122 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::event::KeyHandlers;
+use crate::focus::FocusProps;
 use crate::private::{ElementLike, NativeElement};
-use crate::shadow_tree::component::{CoreComponent, TextEditNode};
+use crate::shadow_tree::component::{CoreComponent, NodeMeta, TextEditNode};
 use crate::style::StyleRef;
 
+/// how lines longer than the width of a `TextEdit` are broken
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WrapMode {
+    /// lines are not wrapped, the edit scrolls horizontally
+    None,
+    /// wrap between any characters
+    Char,
+    /// wrap between words
+    #[default]
+    Word,
+    /// wrap between words, or between characters if a word does not fit
+    WordChar,
+}
+
+/// A handle to read the content and selection of the `TextEdit` it is attached to.
+///
+/// Cloning a `TextEditHandle` returns a handle to the same edit.
+/// The handle is updated as the user edits the text or moves the cursor.
+#[derive(Clone, Default)]
+pub struct TextEditHandle {
+    inner: Arc<Mutex<TextEditHandleState>>,
+}
+
+#[derive(Default)]
+struct TextEditHandleState {
+    text: String,
+    selection: Range<usize>,
+    /// the props last applied to the edit
+    text_prop: Option<String>,
+    selection_prop: Option<Range<usize>>,
+}
+
+impl TextEditHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// the content of the edit
+    pub fn text(&self) -> String {
+        self.inner.lock().text.clone()
+    }
+
+    /// the selected range in characters, empty if nothing is selected
+    pub fn selection(&self) -> Range<usize> {
+        self.inner.lock().selection.clone()
+    }
+
+    /// position of the cursor in characters
+    pub fn cursor(&self) -> usize {
+        self.inner.lock().selection.end
+    }
+
+    /// record the props of a render, the edit only applies props that have changed
+    fn apply_props(&self, text: &Option<String>, selection: &Option<Range<usize>>) {
+        let mut state = self.inner.lock();
+
+        if text.is_some() && *text != state.text_prop {
+            state.text_prop = text.clone();
+            state.text = text.clone().unwrap_or_default();
+        }
+
+        if selection.is_some() && *selection != state.selection_prop {
+            state.selection_prop = selection.clone();
+            state.selection = selection.clone().unwrap_or_default();
+        }
+    }
+}
+
+impl std::fmt::Debug for TextEditHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.inner.lock();
+        f.debug_struct("TextEditHandle")
+            .field("text", &state.text)
+            .field("selection", &state.selection)
+            .finish()
+    }
+}
+
+/// A multi-line text editor.
+///
+/// The content and selection props are only applied when they change
+/// between renders, edits made by the user are kept until then.
 pub struct TextEdit {
     style: StyleRef,
+    text: Option<String>,
+    selection: Option<Range<usize>>,
+    read_only: bool,
+    wrap_mode: WrapMode,
+    handle: Option<TextEditHandle>,
+    on_change: Option<Arc<dyn Fn(&str) + Send + Sync + 'static>>,
+    on_selection_change: Option<Arc<dyn Fn(Range<usize>) + Send + Sync + 'static>>,
+    keyboard: KeyHandlers,
+    focus: FocusProps,
 }
 
 impl TextEdit {
     pub fn new() -> Self {
         Self {
             style: StyleRef::DEFAULT,
+            text: None,
+            selection: None,
+            read_only: false,
+            wrap_mode: WrapMode::default(),
+            handle: None,
+            on_change: None,
+            on_selection_change: None,
+            keyboard: KeyHandlers::default(),
+            focus: FocusProps::default(),
         }
     }
 
@@ -21,13 +128,152 @@ impl TextEdit {
     pub fn set_style<S: Into<StyleRef>>(&mut self, style: S) {
         self.style = style.into();
     }
+
+    /// replace the content whenever `text` differs from the previous render
+    pub fn with_text<S: Into<String>>(mut self, text: S) -> Self {
+        self.set_text(text);
+        return self;
+    }
+
+    pub fn set_text<S: Into<String>>(&mut self, text: S) {
+        self.text = Some(text.into())
+    }
+
+    /// select a range in characters whenever `selection` differs from the previous render
+    pub fn with_selection(mut self, selection: Range<usize>) -> Self {
+        self.set_selection(selection);
+        return self;
+    }
+
+    pub fn set_selection(&mut self, selection: Range<usize>) {
+        self.selection = Some(selection)
+    }
+
+    /// move the cursor whenever `cursor` differs from the previous render
+    pub fn with_cursor(mut self, cursor: usize) -> Self {
+        self.set_cursor(cursor);
+        return self;
+    }
+
+    pub fn set_cursor(&mut self, cursor: usize) {
+        self.selection = Some(cursor..cursor)
+    }
+
+    /// prevent the user from editing the content
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.set_read_only(read_only);
+        return self;
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only
+    }
+
+    pub fn with_wrap_mode(mut self, mode: WrapMode) -> Self {
+        self.set_wrap_mode(mode);
+        return self;
+    }
+
+    pub fn set_wrap_mode(&mut self, mode: WrapMode) {
+        self.wrap_mode = mode
+    }
+
+    /// attach a handle to read the content and selection
+    pub fn with_handle(mut self, handle: &TextEditHandle) -> Self {
+        self.set_handle(handle);
+        return self;
+    }
+
+    pub fn set_handle(&mut self, handle: &TextEditHandle) {
+        self.handle = Some(handle.clone())
+    }
+
+    /// called with the new content whenever the user edits the text
+    pub fn with_on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.set_on_change(callback);
+        return self;
+    }
+
+    pub fn set_on_change<F>(&mut self, callback: F)
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Arc::new(callback))
+    }
+
+    /// called with the selected range in characters whenever the cursor or selection moves
+    pub fn with_on_selection_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Range<usize>) + Send + Sync + 'static,
+    {
+        self.set_on_selection_change(callback);
+        return self;
+    }
+
+    pub fn set_on_selection_change<F>(&mut self, callback: F)
+    where
+        F: Fn(Range<usize>) + Send + Sync + 'static,
+    {
+        self.on_selection_change = Some(Arc::new(callback))
+    }
+}
+
+impl Default for TextEdit {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
+impl_key_events!(TextEdit, keyboard);
+impl_focus_events!(TextEdit, focus);
+
 impl NativeElement for TextEdit {
     fn core_component(&mut self) -> CoreComponent {
+        let mut on_change = self.on_change.clone();
+        let mut on_selection_change = self.on_selection_change.clone();
+
+        // the handle is updated before the callbacks are invoked
+        if let Some(handle) = &self.handle {
+            handle.apply_props(&self.text, &self.selection);
+
+            let h = handle.clone();
+            let callback = on_change.take();
+            on_change = Some(Arc::new(move |text: &str| {
+                h.inner.lock().text = text.to_string();
+
+                if let Some(cb) = &callback {
+                    cb(text)
+                }
+            }));
+
+            let h = handle.clone();
+            let callback = on_selection_change.take();
+            on_selection_change = Some(Arc::new(move |selection: Range<usize>| {
+                h.inner.lock().selection = selection.clone();
+
+                if let Some(cb) = &callback {
+                    cb(selection)
+                }
+            }));
+        }
+
         CoreComponent::TextEdit(Box::new(TextEditNode {
             id: None,
             style: self.style.clone(),
+            text: self.text.clone(),
+            selection: self.selection.clone(),
+            read_only: self.read_only,
+            wrap_mode: self.wrap_mode,
+            on_change,
+            on_selection_change,
+            meta: NodeMeta {
+                keyboard: self.keyboard.clone(),
+                focus: self.focus.clone(),
+                ..Default::default()
+            },
         }))
     }
     fn render(&mut self) {}
@@ -41,3 +287,95 @@ impl ElementLike for TextEdit {
         None
     }
 }
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_text_edit_props() {
+    use crate::native_tree::node::NativeComponent;
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+
+    let renderer = TestRenderer::new(|| {
+        TextEdit::new()
+            .with_text("hello")
+            .with_cursor(5)
+            .with_wrap_mode(WrapMode::Char)
+            .test_id("notes")
+    });
+
+    let node = renderer.find_by_test_id("notes").unwrap();
+    match node.native() {
+        NativeComponent::TextEdit(t) => {
+            assert_eq!(t.text(), "hello");
+            assert_eq!(t.selection(), 5..5);
+            assert_eq!(t.wrap_mode(), WrapMode::Char);
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_text_edit_user_edits() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+
+    let handle = TextEditHandle::new();
+    let h = handle.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        TextEdit::new()
+            .with_text("hello")
+            .with_handle(&h)
+            .test_id("notes")
+    });
+
+    assert_eq!(handle.text(), "hello");
+
+    let node = renderer.find_by_test_id("notes").unwrap();
+    node.input_text("hello world");
+    node.select(0..5);
+    assert_eq!(handle.text(), "hello world");
+    assert_eq!(handle.selection(), 0..5);
+
+    // the edits and selection of the user survive renders with unchanged props
+    renderer.render();
+    assert_eq!(handle.text(), "hello world");
+    assert_eq!(handle.selection(), 0..5);
+    let node = renderer.find_by_test_id("notes").unwrap();
+    assert_eq!(node.text().as_deref(), Some("hello world"));
+}
+
+#[cfg(feature = "headless")]
+#[test]
+fn test_text_edit_changed_text() {
+    use crate::testing::TestRenderer;
+    use crate::widget::ElementExt;
+    use crate::State;
+
+    let content = State::new(String::from("hello"));
+    let c = content.clone();
+
+    let handle = TextEditHandle::new();
+    let h = handle.clone();
+
+    let mut renderer = TestRenderer::new(move || {
+        TextEdit::new()
+            .with_text(c.get())
+            .with_handle(&h)
+            .test_id("notes")
+    });
+
+    let node = renderer.find_by_test_id("notes").unwrap();
+    node.input_text("hello world");
+    node.select(0..11);
+
+    // a changed prop replaces the content, the selection is clamped to it
+    content.set("bye".into());
+    renderer.step();
+
+    assert_eq!(handle.text(), "bye");
+    assert_eq!(handle.selection(), 0..3);
+    let node = renderer.find_by_test_id("notes").unwrap();
+    assert_eq!(node.text().as_deref(), Some("bye"));
+}
@@ -1,11 +1,14 @@
+use crate::event::PointerHandlers;
 use crate::private::{ElementLike, NativeElement};
-use crate::shadow_tree::component::{CoreComponent, ViewNode};
+use crate::shadow_tree::component::{CoreComponent, NodeMeta, ViewNode};
+use crate::shadow_tree::render::render_element;
 use crate::style::StyleRef;
 
 pub struct View {
     style: StyleRef,
     children: Vec<Box<dyn ElementLike>>,
     rendered_children: Vec<CoreComponent>,
+    pointer: PointerHandlers,
 }
 
 impl View {
@@ -14,6 +17,7 @@ impl View {
             style: StyleRef::DEFAULT,
             children: Vec::new(),
             rendered_children: Vec::new(),
+            pointer: PointerHandlers::default(),
         }
     }
     pub fn with_child<T>(mut self, child: T) -> Self
@@ -45,6 +49,8 @@ impl View {
     }
 }
 
+impl_pointer_events!(View);
+
 impl NativeElement for View {
     fn core_component(&mut self) -> CoreComponent {
         let children = core::mem::replace(&mut self.rendered_children, Vec::new());
@@ -53,22 +59,17 @@ impl NativeElement for View {
             id: None,
             style: self.style.clone(),
             children: children,
+            meta: NodeMeta {
+                pointer: self.pointer.clone(),
+                ..Default::default()
+            },
         }))
     }
 
     fn render(&mut self) {
-        for child in &mut self.children {
-            // render the child
-            let mut elem = child.render();
-            // keep rendering until core component is reached
-            let comp = loop {
-                match elem {
-                    Ok(c) => break c,
-                    Err(mut e) => {
-                        elem = e.render();
-                    }
-                }
-            };
+        for (i, child) in self.children.iter_mut().enumerate() {
+            // render the child until core component is reached
+            let comp = render_element(child, i);
 
             self.rendered_children.push(comp);
         }